@@ -0,0 +1,362 @@
+//! Conversion of BDF fonts into proportional bitmap fonts.
+
+use crate::AssetError;
+use std::{
+    fmt::Write as _,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+/// Options for converting a [`BdfFont`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontOptions {
+    /// The ranges of characters to include, or every character in the font if this is
+    /// empty.
+    pub chars: Vec<RangeInclusive<char>>,
+    /// The horizontal space between characters when converting a fixed width font into a
+    /// proportional one, or `None` to keep the character widths of the font.
+    pub proportional: Option<u32>,
+    /// The character drawn in place of those not in the font.
+    pub replacement: char,
+    /// The path of the `watchy::text` module in the generated code.
+    pub module: String,
+}
+impl Default for FontOptions {
+    fn default() -> Self {
+        Self {
+            chars: Vec::new(),
+            proportional: None,
+            replacement: '?',
+            module: "::watchy::text".into(),
+        }
+    }
+}
+
+/// A single character of a [`BdfFont`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BdfGlyph {
+    /// The character.
+    character: char,
+    /// The horizontal distance to the start of the next character.
+    advance: u32,
+    /// The width of the bitmap.
+    width: u32,
+    /// The height of the bitmap.
+    height: u32,
+    /// The horizontal offset of the left of the bitmap from the start of the character.
+    x_offset: i32,
+    /// The vertical offset of the bottom of the bitmap above the baseline.
+    y_offset: i32,
+    /// The pixels of the bitmap in rows from the top left, which are set where drawn.
+    pixels: Vec<bool>,
+}
+impl BdfGlyph {
+    /// Crops the bitmap to the pixels that are drawn.
+    fn crop(&mut self) {
+        let width = self.width as usize;
+        let drawn = |x: usize, y: usize| self.pixels[y * width + x];
+        let rows: Vec<_> = (0..self.height as usize)
+            .filter(|y| (0..width).any(|x| drawn(x, *y)))
+            .collect();
+        let columns: Vec<_> = (0..width)
+            .filter(|x| rows.iter().any(|y| drawn(*x, *y)))
+            .collect();
+
+        let (Some(&top), Some(&bottom), Some(&left), Some(&right)) =
+            (rows.first(), rows.last(), columns.first(), columns.last())
+        else {
+            *self = Self {
+                width: 0,
+                height: 0,
+                x_offset: 0,
+                y_offset: 0,
+                pixels: Vec::new(),
+                ..*self
+            };
+            return;
+        };
+
+        self.pixels = (top..=bottom)
+            .flat_map(|y| (left..=right).map(move |x| (x, y)))
+            .map(|(x, y)| drawn(x, y))
+            .collect();
+        self.x_offset += left as i32;
+        self.y_offset += (self.height as usize - 1 - bottom) as i32;
+        self.width = (right - left + 1) as u32;
+        self.height = (bottom - top + 1) as u32;
+    }
+}
+
+/// A font read from a [BDF](https://en.wikipedia.org/wiki/Glyph_Bitmap_Distribution_Format)
+/// file, which can be converted into a `watchy::text::Font`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BdfFont {
+    /// The number of rows at and above the baseline.
+    ascent: u32,
+    /// The number of rows below the baseline.
+    descent: u32,
+    /// The glyphs sorted by character.
+    glyphs: Vec<BdfGlyph>,
+}
+impl BdfFont {
+    /// Parses the contents of a BDF file.
+    pub fn parse(source: &str) -> Result<Self, AssetError> {
+        let mut lines = source.lines().enumerate().map(|(i, line)| (i + 1, line));
+        let (mut ascent, mut descent, mut bounding_box) = (None, None, None);
+        let mut glyphs = Vec::new();
+
+        while let Some((number, line)) = lines.next() {
+            let error = |message| AssetError::Bdf {
+                line: number,
+                message,
+            };
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let mut values = || -> Result<Vec<i32>, AssetError> {
+                words
+                    .by_ref()
+                    .map(|word| word.parse().map_err(|_| error("invalid number")))
+                    .collect()
+            };
+
+            match keyword {
+                "FONTBOUNDINGBOX" => match values()?[..] {
+                    [_, height, _, y_offset] => bounding_box = Some((height, y_offset)),
+                    _ => return Err(error("expected four values")),
+                },
+                "FONT_ASCENT" => ascent = values()?.first().copied(),
+                "FONT_DESCENT" => descent = values()?.first().copied(),
+                "STARTCHAR" => {
+                    if let Some(glyph) = Self::parse_glyph(&mut lines)? {
+                        glyphs.push(glyph);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // The bounding box gives the line height if the properties are missing
+        let (height, y_offset) = bounding_box.unwrap_or_default();
+        let ascent = ascent.unwrap_or(height + y_offset);
+        let descent = descent.unwrap_or(-y_offset);
+        let invalid = |_| AssetError::InvalidFont("negative ascent or descent");
+
+        glyphs.sort_by_key(|glyph: &BdfGlyph| glyph.character);
+        glyphs.dedup_by_key(|glyph| glyph.character);
+        Ok(Self {
+            ascent: ascent.try_into().map_err(invalid)?,
+            descent: descent.try_into().map_err(invalid)?,
+            glyphs,
+        })
+    }
+
+    /// Parses a glyph after its `STARTCHAR` line, which is `None` if it has no
+    /// character.
+    fn parse_glyph<'a>(
+        lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<Option<BdfGlyph>, AssetError> {
+        let (mut character, mut advance, mut bounding_box) = (None, None, None);
+        let mut pixels = Vec::new();
+        let mut last = 0;
+
+        while let Some((number, line)) = lines.next() {
+            last = number;
+            let error = |message| AssetError::Bdf {
+                line: number,
+                message,
+            };
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let mut values = || -> Result<Vec<i32>, AssetError> {
+                words
+                    .by_ref()
+                    .map(|word| word.parse().map_err(|_| error("invalid number")))
+                    .collect()
+            };
+
+            match keyword {
+                // Characters without an encoding have a negative one
+                "ENCODING" => {
+                    character = values()?
+                        .first()
+                        .and_then(|code| u32::try_from(*code).ok())
+                        .and_then(char::from_u32)
+                }
+                "DWIDTH" => advance = values()?.first().copied(),
+                "BBX" => match values()?[..] {
+                    [width, height, x_offset, y_offset] if width >= 0 && height >= 0 => {
+                        bounding_box = Some((width as u32, height as u32, x_offset, y_offset))
+                    }
+                    _ => return Err(error("expected four values")),
+                },
+                "BITMAP" => {
+                    let (width, height, ..) = bounding_box.ok_or(error("missing BBX"))?;
+                    for _ in 0..height {
+                        let (number, row) = lines.next().ok_or(error("missing bitmap rows"))?;
+                        let error = |message| AssetError::Bdf {
+                            line: number,
+                            message,
+                        };
+                        let row = row.trim();
+                        let bytes = (0..row.len())
+                            .step_by(2)
+                            .map(|i| {
+                                row.get(i..i + 2)
+                                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                                    .ok_or(error("invalid bitmap row"))
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        if bytes.len() * 8 < width as usize {
+                            return Err(error("bitmap row is too short"));
+                        }
+                        pixels.extend(
+                            (0..width as usize).map(|x| bytes[x / 8] & (0x80 >> (x % 8)) != 0),
+                        );
+                    }
+                }
+                "ENDCHAR" => {
+                    let Some(character) = character else {
+                        return Ok(None);
+                    };
+                    let (width, height, x_offset, y_offset) =
+                        bounding_box.ok_or(error("missing BBX"))?;
+                    return Ok(Some(BdfGlyph {
+                        character,
+                        advance: advance
+                            .and_then(|advance| advance.try_into().ok())
+                            .ok_or(error("missing or invalid DWIDTH"))?,
+                        width,
+                        height,
+                        x_offset,
+                        y_offset,
+                        pixels,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        Err(AssetError::Bdf {
+            line: last,
+            message: "missing ENDCHAR",
+        })
+    }
+
+    /// Reads and parses a BDF file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AssetError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Returns an iterator over the characters in the font in order.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.iter().map(|glyph| glyph.character)
+    }
+
+    /// Converts into a Rust expression that creates a `watchy::text::Font`.
+    pub fn to_rust(&self, options: &FontOptions) -> Result<String, AssetError> {
+        let included = |character: char| {
+            options.chars.is_empty()
+                || character == options.replacement
+                || options.chars.iter().any(|range| range.contains(&character))
+        };
+        let module = &options.module;
+        let out_of_range = |character| AssetError::GlyphTooLarge(character);
+
+        let mut glyphs = format!("{{\n    use {module}::{{Font, Glyph}};\n\n");
+        glyphs.push_str("    const GLYPHS: &[Glyph] = &[\n");
+        let mut data = Vec::new();
+        for glyph in self.glyphs.iter().filter(|glyph| included(glyph.character)) {
+            let mut glyph = glyph.clone();
+            let original_advance = glyph.advance;
+            glyph.crop();
+            if let Some(spacing) = options.proportional {
+                glyph.advance = if glyph.pixels.is_empty() {
+                    original_advance.div_ceil(2)
+                } else {
+                    glyph.width
+                } + spacing;
+                glyph.x_offset = 0;
+            }
+
+            let character = glyph.character;
+            writeln!(
+                glyphs,
+                "        Glyph::new({character:?}, {}, {}, {}, {}, {}, {}),",
+                u8::try_from(glyph.advance).map_err(|_| out_of_range(character))?,
+                u8::try_from(glyph.width).map_err(|_| out_of_range(character))?,
+                u8::try_from(glyph.height).map_err(|_| out_of_range(character))?,
+                i8::try_from(glyph.x_offset).map_err(|_| out_of_range(character))?,
+                i8::try_from(glyph.y_offset).map_err(|_| out_of_range(character))?,
+                data.len(),
+            )
+            .unwrap();
+
+            // Pack each row the same as a bitmap, but with a set bit being drawn
+            for row in glyph.pixels.chunks(glyph.width.max(1) as usize) {
+                data.extend(row.chunks(8).map(|byte| {
+                    byte.iter().enumerate().fold(0u8, |packed, (x, drawn)| {
+                        packed | u8::from(*drawn) << (7 - x)
+                    })
+                }));
+            }
+        }
+        glyphs.push_str("    ];\n");
+
+        let mut rust = glyphs;
+        rust.push_str("    const DATA: &[u8] = &[");
+        for (i, byte) in data.iter().enumerate() {
+            if i % 16 == 0 {
+                rust.push_str("\n        ");
+            } else {
+                rust.push(' ');
+            }
+            write!(rust, "0x{byte:02X},").unwrap();
+        }
+        writeln!(
+            rust,
+            "\n    ];\n\n    Font::new(GLYPHS, DATA, {}, {}, {:?})\n}}",
+            self.ascent, self.descent, options.replacement
+        )
+        .unwrap();
+        Ok(rust)
+    }
+}
+
+/// Converts a BDF font file for inclusion using the `watchy::include_font` macro with the
+/// same `name`.
+///
+/// This must be called from a build script, and tells Cargo to rerun it when the font
+/// file changes.
+///
+/// # Example
+/// In `build.rs`, to convert only the ASCII and Cyrillic characters of a font:
+/// ```no_run
+/// use watchy_assets::FontOptions;
+///
+/// fn main() {
+///     let options = FontOptions {
+///         chars: vec![' '..='~', 'Ѐ'..='ӿ'],
+///         ..Default::default()
+///     };
+///     watchy_assets::include_font("cyrillic", "fonts/cyrillic.bdf", &options).unwrap();
+/// }
+/// ```
+/// Then in the binary crate:
+/// ```ignore
+/// const CYRILLIC: watchy::text::Font = watchy::include_font!("cyrillic");
+/// ```
+pub fn include_font(
+    name: &str,
+    path: impl AsRef<Path>,
+    options: &FontOptions,
+) -> Result<(), AssetError> {
+    let path = path.as_ref();
+    println!("cargo::rerun-if-changed={}", path.display());
+
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").ok_or(AssetError::NoOutDir)?);
+    std::fs::write(
+        out_dir.join(format!("{name}.font.rs")),
+        BdfFont::open(path)?.to_rust(options)?,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,342 @@
+//! Compilation of watch face layouts from TOML files into the binary layout format.
+
+use crate::{AssetError, Bitmap, Dither};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The bytes at the start of every layout, followed by the format version.
+const HEADER: &[u8; 4] = b"WFL\x01";
+
+/// The kind bytes of the elements.
+mod kind {
+    /// The time.
+    pub const TIME: u8 = 1;
+    /// The date.
+    pub const DATE: u8 = 2;
+    /// Fixed text.
+    pub const TEXT: u8 = 3;
+    /// A complication.
+    pub const COMPLICATION: u8 = 4;
+    /// An image.
+    pub const IMAGE: u8 = 5;
+}
+
+/// The font of text in a layout file.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum FontSpec {
+    /// The small font.
+    #[default]
+    Small,
+    /// The large digits.
+    LargeDigits,
+    /// Seven-segment digits.
+    SevenSegment {
+        /// The width of a full width character.
+        width: u8,
+        /// The height of a character.
+        height: u8,
+        /// The thickness of the segments.
+        segment: u8,
+    },
+}
+
+/// The horizontal alignment of text in a layout file.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum AlignSpec {
+    /// The position is the left of the text.
+    #[default]
+    Left,
+    /// The position is the middle of the text.
+    Center,
+    /// The position is the right of the text.
+    Right,
+}
+
+/// The vertical alignment of text in a layout file.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum BaselineSpec {
+    /// The position is the top of the text.
+    Top,
+    /// The position is the middle of the text.
+    Middle,
+    /// The position is the bottom of the text.
+    Bottom,
+    /// The position is on the baseline of the text.
+    #[default]
+    Alphabetic,
+}
+
+/// Where and how text is drawn in a layout file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+struct PlacementSpec {
+    /// The horizontal position.
+    x: i16,
+    /// The vertical position.
+    y: i16,
+    /// The font.
+    #[serde(default)]
+    font: FontSpec,
+    /// The horizontal alignment.
+    #[serde(default)]
+    align: AlignSpec,
+    /// The vertical alignment.
+    #[serde(default)]
+    baseline: BaselineSpec,
+}
+impl PlacementSpec {
+    /// Appends the placement in the binary format.
+    fn write(&self, data: &mut Vec<u8>) {
+        data.extend(self.x.to_le_bytes());
+        data.extend(self.y.to_le_bytes());
+        match self.font {
+            FontSpec::Small => data.push(0),
+            FontSpec::LargeDigits => data.push(1),
+            FontSpec::SevenSegment {
+                width,
+                height,
+                segment,
+            } => data.extend([2, width, height, segment]),
+        }
+        data.push(self.align as u8 | (self.baseline as u8) << 4);
+    }
+}
+
+/// A built-in complication in a layout file.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ComplicationSpec {
+    /// The battery icon.
+    Battery,
+    /// The step count.
+    Steps,
+    /// The date.
+    Date,
+    /// The time of the next alarm.
+    NextAlarm,
+}
+
+/// An element of a layout file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "kebab-case",
+    rename_all_fields = "kebab-case"
+)]
+enum ElementSpec {
+    /// The time.
+    Time {
+        /// Where the time is drawn.
+        #[serde(flatten)]
+        placement: PlacementSpec,
+        /// Whether to use the 12 hour clock.
+        #[serde(default)]
+        twelve_hour: bool,
+    },
+    /// The date.
+    Date {
+        /// Where the date is drawn.
+        #[serde(flatten)]
+        placement: PlacementSpec,
+    },
+    /// Fixed text.
+    Text {
+        /// Where the text is drawn.
+        #[serde(flatten)]
+        placement: PlacementSpec,
+        /// The text.
+        text: String,
+    },
+    /// A built-in complication.
+    Complication {
+        /// The complication.
+        complication: ComplicationSpec,
+        /// The horizontal position of the left of the area.
+        x: i16,
+        /// The vertical position of the top of the area.
+        y: i16,
+        /// The width of the area.
+        width: u16,
+        /// The height of the area.
+        height: u16,
+    },
+    /// An image.
+    Image {
+        /// The horizontal position of the left of the image.
+        x: i16,
+        /// The vertical position of the top of the image.
+        y: i16,
+        /// The path of the image file relative to the layout file.
+        path: PathBuf,
+        /// How the image is reduced to black and white.
+        #[serde(default)]
+        dither: Dither,
+    },
+}
+
+/// The contents of a layout file.
+#[derive(Debug, Clone, Deserialize)]
+struct LayoutSpec {
+    /// The elements in the order that they are drawn.
+    #[serde(default, rename = "element")]
+    elements: Vec<ElementSpec>,
+}
+
+/// A watch face layout compiled from a TOML file into the binary format read by
+/// `watchy::face::Layout`.
+///
+/// The file has an `[[element]]` table for each element in the order that they are
+/// drawn, the `kind` of which is one of:
+/// - `time`, with `twelve-hour = true` for the 12 hour clock.
+/// - `date`.
+/// - `text`, with the `text` to draw.
+/// - `complication`, with the `complication` being `battery`, `steps`, `date`, or
+///   `next-alarm`, drawn in the area at `x` and `y` with the `width` and `height`.
+/// - `image`, with the `path` of an image file relative to the layout file drawn with
+///   its top left corner at `x` and `y`, and optionally how to `dither` it.
+///
+/// Text is drawn at `x` and `y` in the `font`, which is `small` by default,
+/// `large-digits`, or `{ seven-segment = { width = 36, height = 64, segment = 7 } }`.
+/// It is aligned to the position according to the `align`, which is `left` by default,
+/// `center`, or `right`, and the `baseline`, which is `alphabetic` by default, `top`,
+/// `middle`, or `bottom`.
+///
+/// # Example
+/// ```toml
+/// [[element]]
+/// kind = "time"
+/// x = 100
+/// y = 90
+/// font = "large-digits"
+/// align = "center"
+/// baseline = "middle"
+///
+/// [[element]]
+/// kind = "date"
+/// x = 100
+/// y = 140
+/// align = "center"
+///
+/// [[element]]
+/// kind = "complication"
+/// complication = "battery"
+/// x = 150
+/// y = 6
+/// width = 40
+/// height = 18
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    /// The layout in the binary format.
+    data: Vec<u8>,
+    /// The image files that were read.
+    images: Vec<PathBuf>,
+}
+impl Layout {
+    /// Parses the contents of a layout file, with the paths of images relative to a
+    /// directory.
+    pub fn parse(source: &str, dir: impl AsRef<Path>) -> Result<Self, AssetError> {
+        let spec: LayoutSpec = toml::from_str(source)?;
+        let mut data = HEADER.to_vec();
+        let mut images = Vec::new();
+
+        for element in spec.elements {
+            match element {
+                ElementSpec::Time {
+                    placement,
+                    twelve_hour,
+                } => {
+                    data.push(kind::TIME);
+                    placement.write(&mut data);
+                    data.push(twelve_hour.into());
+                }
+                ElementSpec::Date { placement } => {
+                    data.push(kind::DATE);
+                    placement.write(&mut data);
+                }
+                ElementSpec::Text { placement, text } => {
+                    data.push(kind::TEXT);
+                    placement.write(&mut data);
+                    data.push(
+                        text.len()
+                            .try_into()
+                            .map_err(|_| AssetError::InvalidLayout("text is too long"))?,
+                    );
+                    data.extend(text.as_bytes());
+                }
+                ElementSpec::Complication {
+                    complication,
+                    x,
+                    y,
+                    width,
+                    height,
+                } => {
+                    data.extend([kind::COMPLICATION, complication as u8]);
+                    for value in [x as u16, y as u16, width, height] {
+                        data.extend(value.to_le_bytes());
+                    }
+                }
+                ElementSpec::Image { x, y, path, dither } => {
+                    let path = dir.as_ref().join(path);
+                    let bitmap = Bitmap::open(&path, dither)?;
+                    let size = [bitmap.width(), bitmap.height()].map(u16::try_from);
+                    let [Ok(width), Ok(height)] = size else {
+                        return Err(AssetError::InvalidLayout("image is too large"));
+                    };
+
+                    data.push(kind::IMAGE);
+                    for value in [x as u16, y as u16, width, height] {
+                        data.extend(value.to_le_bytes());
+                    }
+                    data.extend(bitmap.as_bytes());
+                    images.push(path);
+                }
+            }
+        }
+        Ok(Self { data, images })
+    }
+
+    /// Reads and compiles a layout file, with the paths of images relative to the file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AssetError> {
+        let path = path.as_ref();
+        Self::parse(
+            &std::fs::read_to_string(path)?,
+            path.parent().unwrap_or(Path::new("")),
+        )
+    }
+
+    /// Returns the layout in the binary format.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Compiles a layout file for inclusion using the `watchy::include_layout` macro with the
+/// same `name`.
+///
+/// This must be called from a build script, and tells Cargo to rerun it when the layout
+/// file or any of its images change.
+///
+/// # Example
+/// In the `main` function of `build.rs`:
+/// ```no_run
+/// watchy_assets::include_layout("digital", "layouts/digital.toml").unwrap();
+/// ```
+/// Then in the binary crate:
+/// ```ignore
+/// let layout = watchy::face::Layout::new(watchy::include_layout!("digital")).unwrap();
+/// ```
+pub fn include_layout(name: &str, path: impl AsRef<Path>) -> Result<(), AssetError> {
+    let path = path.as_ref();
+    println!("cargo::rerun-if-changed={}", path.display());
+
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").ok_or(AssetError::NoOutDir)?);
+    let layout = Layout::open(path)?;
+    for image in &layout.images {
+        println!("cargo::rerun-if-changed={}", image.display());
+    }
+    std::fs::write(out_dir.join(format!("{name}.layout")), layout.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Build-time conversion of images into packed bitmaps, and of fonts into bitmap fonts, for
+//! the [Watchy](https://watchy.sqfmi.com/) programmable smartwatch.
+//!
+//! PNG and BMP images are converted into packed one bit per pixel data in the same layout
+//! as the display framebuffer, optionally dithering them to better represent photos and
+//! gradients.
+//! Transparent pixels are treated as white.
+//!
+//! This is intended to be used from the build script of a binary crate, which makes the
+//! bitmaps available to the `watchy::include_bitmap` macro.
+//! For example, in `build.rs`:
+//! ```no_run
+//! use watchy_assets::Dither;
+//!
+//! fn main() {
+//!     watchy_assets::include("icon", "assets/icon.png", Dither::None).unwrap();
+//!     watchy_assets::include("photo", "assets/photo.png", Dither::FloydSteinberg).unwrap();
+//! }
+//! ```
+//! Then in the binary crate:
+//! ```ignore
+//! const ICON: watchy::display::Bitmap = watchy::include_bitmap!("icon");
+//! ```
+//!
+//! Similarly, BDF fonts are converted using [`include_font`] and included using the
+//! `watchy::include_font` macro, optionally with only some of their characters.
+//! Watch face layouts are compiled from TOML files using [`include_layout`] and included
+//! using the `watchy::include_layout` macro.
+//!
+//! This crate must be added to the `build-dependencies` of the binary crate, since it runs
+//! on the host.
+
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+mod font;
+mod layout;
+
+pub use font::{include_font, BdfFont, FontOptions};
+pub use layout::{include_layout, Layout};
+
+use image::DynamicImage;
+use serde::Deserialize;
+use std::{
+    fmt::Write as _,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Error for image conversion problems.
+#[derive(Error, Debug)]
+pub enum AssetError {
+    /// The image could not be read or decoded.
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+    /// The converted bitmap could not be written.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The `OUT_DIR` environment variable is not set, because this is not being run from
+    /// a build script.
+    #[error("The OUT_DIR environment variable is not set")]
+    NoOutDir,
+    /// A BDF font file is malformed.
+    #[error("BDF error on line {line}: {message}")]
+    Bdf {
+        /// The line number.
+        line: usize,
+        /// What is wrong.
+        message: &'static str,
+    },
+    /// A font is not valid.
+    #[error("Invalid font: {0}")]
+    InvalidFont(&'static str),
+    /// A glyph of a font is too large to be converted.
+    #[error("The glyph for {0:?} is too large")]
+    GlyphTooLarge(char),
+    /// A layout file is malformed.
+    #[error("Layout error: {0}")]
+    Toml(#[from] toml::de::Error),
+    /// A layout is not valid.
+    #[error("Invalid layout: {0}")]
+    InvalidLayout(&'static str),
+}
+
+/// How the gray levels of an image are reduced to black and white.
+///
+/// In layout files, this is `none`, `floyd-steinberg`, or `ordered`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dither {
+    /// Each pixel is black if it is darker than mid gray, which is best for line art and
+    /// icons.
+    #[default]
+    None,
+    /// Floyd–Steinberg error diffusion, which is best for photos.
+    FloydSteinberg,
+    /// Ordered dithering with an 8x8 Bayer matrix, which gives a regular pattern that is
+    /// best for gradients and flat areas of gray.
+    Ordered,
+}
+
+/// The 8x8 Bayer matrix used for [ordered](Dither::Ordered) dithering.
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// The gray level at and above which pixels are white without dithering.
+const THRESHOLD: f32 = 128.0;
+
+/// An image packed one bit per pixel in the same layout as the display framebuffer.
+///
+/// The pixels are packed in rows from the top left, with the most significant bit of each
+/// byte being the leftmost pixel and each row padded to a whole byte with white pixels.
+/// A set bit is white.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    /// The width in pixels.
+    width: u32,
+    /// The height in pixels.
+    height: u32,
+    /// The packed pixel data.
+    data: Vec<u8>,
+}
+impl Bitmap {
+    /// Converts a decoded image.
+    pub fn from_image(image: &DynamicImage, dither: Dither) -> Self {
+        let image = image.to_luma_alpha8();
+        let (width, height) = image.dimensions();
+
+        // Composite over white
+        let mut levels: Vec<f32> = image
+            .pixels()
+            .map(|pixel| {
+                let [luma, alpha] = pixel.0.map(f32::from);
+                (luma * alpha + 255.0 * (255.0 - alpha)) / 255.0
+            })
+            .collect();
+
+        let row_bytes = width.div_ceil(8) as usize;
+        let mut data = vec![0xFF; row_bytes * height as usize];
+        let (w, h) = (width as usize, height as usize);
+        for y in 0..h {
+            for x in 0..w {
+                let level = levels[y * w + x];
+                let white = match dither {
+                    Dither::None => level >= THRESHOLD,
+                    Dither::FloydSteinberg => {
+                        let white = level >= THRESHOLD;
+                        let error = level - if white { 255.0 } else { 0.0 };
+                        let mut diffuse = |dx: isize, dy: usize, weight: f32| {
+                            let nx = x as isize + dx;
+                            if (0..w as isize).contains(&nx) && y + dy < h {
+                                levels[(y + dy) * w + nx as usize] += error * weight / 16.0;
+                            }
+                        };
+                        diffuse(1, 0, 7.0);
+                        diffuse(-1, 1, 3.0);
+                        diffuse(0, 1, 5.0);
+                        diffuse(1, 1, 1.0);
+                        white
+                    }
+                    Dither::Ordered => level >= (f32::from(BAYER_8[y % 8][x % 8]) + 0.5) * 4.0,
+                };
+                if !white {
+                    data[y * row_bytes + x / 8] &= !(0x80 >> (x % 8));
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Reads and converts an image file, the format of which is determined from its
+    /// contents.
+    pub fn open(path: impl AsRef<Path>, dither: Dither) -> Result<Self, AssetError> {
+        let image = image::ImageReader::open(path)?
+            .with_guessed_format()?
+            .decode()?;
+        Ok(Self::from_image(&image, dither))
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the packed pixel data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Converts into a binary [PBM](https://netpbm.sourceforge.net/doc/pbm.html) image,
+    /// which can be viewed with most image viewers.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        // NOTE: A set bit is black in PBM, but the layout is otherwise the same.
+        let mut pbm = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        pbm.extend(self.data.iter().map(|byte| !byte));
+        pbm
+    }
+
+    /// Converts into a Rust expression that creates a `watchy::display::Bitmap`.
+    pub fn to_rust(&self) -> String {
+        let mut rust = format!("::watchy::display::Bitmap::new({}, &[", self.width);
+        for (i, byte) in self.data.iter().enumerate() {
+            if i % 16 == 0 {
+                rust.push_str("\n    ");
+            } else {
+                rust.push(' ');
+            }
+            write!(rust, "0x{byte:02X},").unwrap();
+        }
+        rust.push_str("\n])\n");
+        rust
+    }
+}
+
+/// Converts an image file for inclusion using the `watchy::include_bitmap` macro with the
+/// same `name`.
+///
+/// This must be called from a build script, and tells Cargo to rerun it when the image
+/// file changes.
+pub fn include(name: &str, path: impl AsRef<Path>, dither: Dither) -> Result<(), AssetError> {
+    let path = path.as_ref();
+    println!("cargo::rerun-if-changed={}", path.display());
+
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").ok_or(AssetError::NoOutDir)?);
+    std::fs::write(
+        out_dir.join(format!("{name}.bitmap.rs")),
+        Bitmap::open(path, dither)?.to_rust(),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Helpers shared by the tests that compare output against golden files.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files with the
+//! current output after checking that it looks correct.
+
+use std::path::PathBuf;
+
+/// Returns the path of a file relative to the root of this crate.
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(name)
+}
+
+/// Returns the path of a test data file.
+pub fn data_path(name: &str) -> PathBuf {
+    path("tests/data").join(name)
+}
+
+/// Compares data against a golden file relative to the root of this crate.
+pub fn assert_golden(data: &[u8], golden: &str) {
+    let golden_path = path(golden);
+    if std::env::var_os("WATCHY_BLESS").is_some() {
+        std::fs::write(&golden_path, data).unwrap();
+    } else {
+        assert!(
+            std::fs::read(&golden_path).unwrap() == data,
+            "output does not match {golden}"
+        );
+    }
+}
+
+/// Encodes the bytes of a 200x200 framebuffer as a PBM file.
+pub fn frame_pbm(bytes: &[u8]) -> Vec<u8> {
+    // NOTE: A set bit is black in PBM, but the layout is otherwise the same.
+    let mut pbm = b"P4\n200 200\n".to_vec();
+    pbm.extend(bytes.iter().map(|byte| !byte));
+    pbm
+}
@@ -0,0 +1,16 @@
+{
+    use crate::text::{Font, Glyph};
+
+    const GLYPHS: &[Glyph] = &[
+        Glyph::new(' ', 6, 0, 0, 0, 0, 0),
+        Glyph::new('?', 6, 5, 6, 0, 0, 0),
+        Glyph::new('A', 6, 5, 6, 0, 0, 6),
+        Glyph::new('g', 6, 5, 6, 0, -2, 12),
+    ];
+    const DATA: &[u8] = &[
+        0x70, 0x88, 0x10, 0x20, 0x00, 0x20, 0x20, 0x50, 0x88, 0xF8, 0x88, 0x88, 0x78, 0x88, 0x88, 0x78,
+        0x08, 0x70,
+    ];
+
+    Font::new(GLYPHS, DATA, 6, 2, '?')
+}
@@ -0,0 +1,378 @@
+//! Tests of the runtime image decoders of the `watchy` crate, which are pure `no_std`
+//! code that is included directly so that it can run on the host.
+
+// Not everything in the included modules is used here
+#[allow(dead_code)]
+#[path = "../../src/display/decode.rs"]
+mod decode;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+
+use decode::{DecodeError, DecodedImage, ImageFormat};
+use embedded_graphics::{image::Image, pixelcolor::BinaryColor, prelude::*};
+use framebuffer::{FrameBuffer, HEIGHT, WIDTH};
+use proptest::prelude::*;
+use std::fmt::Write as _;
+
+/// A test image, with each pixel being `true` if it is black.
+#[derive(Debug, Clone)]
+struct TestImage {
+    /// The width in pixels.
+    width: usize,
+    /// The pixels in rows from the top left.
+    pixels: Vec<bool>,
+}
+impl TestImage {
+    /// Creates an image from rows of `#` for black and `.` for white.
+    fn from_rows(rows: &[&str]) -> Self {
+        Self {
+            width: rows[0].len(),
+            pixels: rows
+                .iter()
+                .flat_map(|row| row.bytes().map(|b| b == b'#'))
+                .collect(),
+        }
+    }
+
+    /// Returns the height in pixels.
+    fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+
+    /// Returns a row of pixels.
+    fn row(&self, y: usize) -> &[bool] {
+        &self.pixels[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Packs a row into bytes, with a set bit being `black` and the padding clear.
+    fn pack_row(&self, y: usize, lsb_first: bool, black: bool) -> Vec<u8> {
+        let mut bytes = vec![0; self.width.div_ceil(8)];
+        for (x, pixel) in self.row(y).iter().enumerate() {
+            if *pixel == black {
+                bytes[x / 8] |= if lsb_first {
+                    1 << (x % 8)
+                } else {
+                    0x80 >> (x % 8)
+                };
+            }
+        }
+        bytes
+    }
+
+    /// Encodes as a raw PBM image.
+    fn to_raw_pbm(&self) -> Vec<u8> {
+        let mut data = format!("P4\n{} {}\n", self.width, self.height()).into_bytes();
+        for y in 0..self.height() {
+            data.extend(self.pack_row(y, false, true));
+        }
+        data
+    }
+
+    /// Encodes as a plain PBM image, with comments and irregular whitespace.
+    fn to_plain_pbm(&self) -> Vec<u8> {
+        let mut data = format!("P1\n# A comment\n{}  {}\n", self.width, self.height());
+        for y in 0..self.height() {
+            for (x, pixel) in self.row(y).iter().enumerate() {
+                data.push(if *pixel { '1' } else { '0' });
+                // Digits do not need to be separated
+                if x % 3 == 0 {
+                    data.push(' ');
+                }
+            }
+            data.push_str("\n# Another comment\n");
+        }
+        data.into_bytes()
+    }
+
+    /// Encodes as an XBM image.
+    fn to_xbm(&self) -> Vec<u8> {
+        let mut data = format!(
+            "#define test_width {}\n#define test_height {}\n#define test_x_hot 1\n\
+             static unsigned char test_bits[] = {{\n",
+            self.width,
+            self.height()
+        );
+        for y in 0..self.height() {
+            for byte in self.pack_row(y, true, true) {
+                write!(data, " 0x{byte:02x},").unwrap();
+            }
+            data.push('\n');
+        }
+        data.push_str("};\n");
+        data.into_bytes()
+    }
+
+    /// Encodes as a one bit per pixel BMP image.
+    ///
+    /// The palette has black first if `black_first` is set, and the rows are stored
+    /// from the top if `top_down` is set, which is not possible with the OS/2 header.
+    fn to_bmp(&self, os2_header: bool, top_down: bool, black_first: bool) -> Vec<u8> {
+        let (header_size, entry_size) = if os2_header { (12, 3) } else { (40, 4) };
+        let pixel_offset = 14 + header_size + 2 * entry_size;
+        let height = self.height() as i32;
+
+        let mut data = b"BM".to_vec();
+        data.extend(0u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend((pixel_offset as u32).to_le_bytes());
+        data.extend((header_size as u32).to_le_bytes());
+        if os2_header {
+            data.extend((self.width as u16).to_le_bytes());
+            data.extend((height as u16).to_le_bytes());
+            data.extend(1u16.to_le_bytes());
+            data.extend(1u16.to_le_bytes());
+        } else {
+            data.extend((self.width as i32).to_le_bytes());
+            data.extend(if top_down { -height } else { height }.to_le_bytes());
+            data.extend(1u16.to_le_bytes());
+            data.extend(1u16.to_le_bytes());
+            data.extend([0; 24]);
+        }
+        for black in [black_first, !black_first] {
+            // Not quite black and white to check the threshold
+            let level = if black { 0x30 } else { 0xD0 };
+            data.extend([level; 3]);
+            if !os2_header {
+                data.push(0);
+            }
+        }
+
+        let mut rows: Vec<_> = (0..self.height())
+            .map(|y| {
+                let mut row = self.pack_row(y, false, !black_first);
+                row.resize(self.width.div_ceil(32) * 4, 0);
+                row
+            })
+            .collect();
+        if !top_down {
+            rows.reverse();
+        }
+        data.extend(rows.concat());
+        data
+    }
+
+    /// Returns every encoding of the image.
+    fn encodings(&self) -> Vec<(ImageFormat, Vec<u8>)> {
+        let mut encodings = vec![
+            (ImageFormat::Pbm, self.to_raw_pbm()),
+            (ImageFormat::Pbm, self.to_plain_pbm()),
+            (ImageFormat::Xbm, self.to_xbm()),
+        ];
+        for (os2_header, top_down, black_first) in [
+            (false, false, false),
+            (false, true, false),
+            (false, false, true),
+            (true, false, true),
+        ] {
+            encodings.push((
+                ImageFormat::Bmp,
+                self.to_bmp(os2_header, top_down, black_first),
+            ));
+        }
+        encodings
+    }
+}
+
+/// Checks that an image was blitted correctly into an initially white framebuffer.
+fn assert_blitted(frame: &FrameBuffer, image: &TestImage, top_left: Point, invert: bool) {
+    for point in frame.bounding_box().points() {
+        let offset = point - top_left;
+        let inside = (0..image.width as i32).contains(&offset.x)
+            && (0..image.height() as i32).contains(&offset.y);
+        let expected = if inside {
+            BinaryColor::from(
+                image.pixels[offset.y as usize * image.width + offset.x as usize] != invert,
+            )
+        } else {
+            BinaryColor::Off
+        };
+        assert_eq!(frame.pixel(point), Some(expected), "pixel at {point:?}");
+    }
+}
+
+/// Decodes every encoding of an image and checks that it is drawn correctly.
+fn assert_decodes(image: &TestImage, top_left: Point, invert: bool) {
+    for (format, data) in image.encodings() {
+        let decoded = DecodedImage::decode(&data).unwrap();
+        assert_eq!(decoded.format(), format);
+        assert_eq!(
+            decoded.size(),
+            Size::new(image.width as u32, image.height() as u32)
+        );
+
+        let mut frame = FrameBuffer::new();
+        decoded.blit(&mut frame, top_left, invert);
+        assert_blitted(&frame, image, top_left, invert);
+
+        let mut frame = FrameBuffer::new();
+        Image::new(&decoded, top_left).draw(&mut frame).unwrap();
+        assert_blitted(&frame, image, top_left, false);
+    }
+}
+
+/// A small asymmetric image that is not a whole number of bytes wide.
+fn arrow() -> TestImage {
+    TestImage::from_rows(&[
+        "...#.......",
+        "...##......",
+        "#######....",
+        "########...",
+        "#######....",
+        "...##......",
+        "...#......#",
+    ])
+}
+
+#[test]
+fn decodes_all_formats() {
+    assert_decodes(&arrow(), Point::new(10, 20), false);
+}
+
+#[test]
+fn inverts() {
+    assert_decodes(&arrow(), Point::new(10, 20), true);
+}
+
+#[test]
+fn clips_at_every_edge() {
+    for top_left in [
+        Point::new(-4, -3),
+        Point::new(195, 196),
+        Point::new(-4, 196),
+        Point::new(195, -3),
+        Point::new(-100, 0),
+        Point::new(0, HEIGHT as i32),
+    ] {
+        assert_decodes(&arrow(), top_left, false);
+    }
+
+    // Nothing is drawn far off the display
+    for (_, data) in arrow().encodings() {
+        for top_left in [
+            Point::new(i32::MIN, i32::MIN),
+            Point::new(i32::MAX, i32::MAX),
+        ] {
+            let mut frame = FrameBuffer::new();
+            DecodedImage::decode(&data)
+                .unwrap()
+                .blit(&mut frame, top_left, false);
+            assert_eq!(frame, FrameBuffer::new());
+        }
+    }
+}
+
+#[test]
+fn matches_converted_assets() {
+    let path = |name: &str| format!("{}/tests/data/{name}", env!("CARGO_MANIFEST_DIR"));
+    let data = std::fs::read(path("icon.pbm")).unwrap();
+    let bitmap =
+        watchy_assets::Bitmap::open(path("icon.png"), watchy_assets::Dither::None).unwrap();
+
+    let mut frame = FrameBuffer::new();
+    DecodedImage::decode(&data)
+        .unwrap()
+        .blit(&mut frame, Point::zero(), false);
+
+    // Both have the same layout as the framebuffer
+    let row_bytes = bitmap.width().div_ceil(8) as usize;
+    for (y, row) in bitmap.as_bytes().chunks(row_bytes).enumerate() {
+        let start = y * FrameBuffer::ROW_BYTES;
+        let frame_row = &frame.as_bytes()[start..start + row_bytes];
+        // The padding is not drawn, so is always white in the framebuffer
+        assert_eq!(frame_row[..row_bytes - 1], row[..row_bytes - 1], "row {y}");
+        assert_eq!(
+            frame_row[row_bytes - 1] | 0x0F,
+            row[row_bytes - 1] | 0x0F,
+            "row {y}"
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_images() {
+    let image = arrow();
+    let decode = |data: &[u8]| DecodedImage::decode(data).map(|_| ());
+
+    assert_eq!(decode(b""), Err(DecodeError::UnknownFormat));
+    assert_eq!(decode(b"GIF89a"), Err(DecodeError::UnknownFormat));
+    assert_eq!(decode(b"P4 0 5\n"), Err(DecodeError::InvalidHeader));
+    assert_eq!(decode(b"P4 x 5\n"), Err(DecodeError::InvalidHeader));
+    assert_eq!(decode(b"P4 5"), Err(DecodeError::Truncated));
+    assert_eq!(decode(b"P1 2 1\n1 2"), Err(DecodeError::InvalidData));
+    assert_eq!(decode(b"P4 99999 1\n"), Err(DecodeError::Unsupported));
+    assert_eq!(
+        decode(b"#define a_width 8\n#define a_height 1\nstatic char a_bits[] = { 0x100 };"),
+        Err(DecodeError::InvalidData)
+    );
+    assert_eq!(
+        decode(b"#define a_width 8\nstatic char a_bits[] = { 0x00 };"),
+        Err(DecodeError::InvalidHeader)
+    );
+    assert_eq!(
+        decode(b"#define a_width 16\n#define a_height 1\nstatic short a_bits[] = { 0x00 };"),
+        Err(DecodeError::Unsupported)
+    );
+
+    // Anything other than one bit per pixel without compression
+    let mut bmp = image.to_bmp(false, false, false);
+    bmp[28] = 8;
+    assert_eq!(decode(&bmp), Err(DecodeError::Unsupported));
+    let mut bmp = image.to_bmp(false, false, false);
+    bmp[30] = 1;
+    assert_eq!(decode(&bmp), Err(DecodeError::Unsupported));
+
+    for (_, data) in image.encodings() {
+        let data = &data[..data.len() * 3 / 4];
+        assert_eq!(decode(data), Err(DecodeError::Truncated));
+    }
+}
+
+/// Returns a strategy for test images.
+fn test_image() -> impl Strategy<Value = TestImage> {
+    (1..40usize, 1..40usize).prop_flat_map(|(width, height)| {
+        prop::collection::vec(any::<bool>(), width * height)
+            .prop_map(move |pixels| TestImage { width, pixels })
+    })
+}
+
+proptest! {
+    #[test]
+    fn decodes_random_images(
+        image in test_image(),
+        x in -50..(WIDTH as i32 + 10),
+        y in -50..(HEIGHT as i32 + 10),
+        invert in any::<bool>(),
+    ) {
+        assert_decodes(&image, Point::new(x, y), invert);
+    }
+
+    #[test]
+    fn never_panics_on_arbitrary_data(data in prop::collection::vec(any::<u8>(), 0..512)) {
+        if let Ok(image) = DecodedImage::decode(&data) {
+            let size = image.size();
+            prop_assert_eq!(image.pixels().count(), (size.width * size.height) as usize);
+            image.blit(&mut FrameBuffer::new(), Point::zero(), false);
+        }
+    }
+
+    #[test]
+    fn never_panics_on_corrupted_images(
+        image in test_image(),
+        encoding in 0..7usize,
+        index in any::<prop::sample::Index>(),
+        byte in any::<u8>(),
+        truncate in any::<prop::sample::Index>(),
+    ) {
+        let mut data = image.encodings().swap_remove(encoding).1;
+        let index = index.index(data.len());
+        data[index] = byte;
+        data.truncate(truncate.index(data.len()) + 1);
+
+        if let Ok(image) = DecodedImage::decode(&data) {
+            let size = image.size();
+            prop_assert_eq!(image.pixels().count(), (size.width * size.height) as usize);
+            image.blit(&mut FrameBuffer::new(), Point::new(-3, -3), true);
+        }
+    }
+}
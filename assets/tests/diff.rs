@@ -0,0 +1,98 @@
+//! Tests of the comparison of frames against images of the `watchy` crate, which is
+//! pure `no_std` code that is included directly so that it can run on the host.
+
+// Not everything in the included modules is used here
+#[allow(dead_code)]
+mod common;
+#[allow(dead_code)]
+#[path = "../../src/display/decode.rs"]
+mod decode;
+#[path = "../../src/display/diff.rs"]
+mod diff;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+
+use decode::DecodedImage;
+use diff::Diff;
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+use framebuffer::{FrameBuffer, HEIGHT, WIDTH};
+
+/// Returns a frame with some black pixels.
+fn frame(points: &[Point]) -> FrameBuffer {
+    let mut frame = FrameBuffer::new();
+    for &point in points {
+        frame.set_pixel(point, BinaryColor::On);
+    }
+    frame
+}
+
+/// Returns the black pixels of a frame, in rows from the top left.
+fn black_pixels(frame: &FrameBuffer) -> Vec<Point> {
+    frame
+        .bounding_box()
+        .points()
+        .filter(|&point| frame.pixel(point) == Some(BinaryColor::On))
+        .collect()
+}
+
+/// Encodes a frame as a PBM image.
+fn pbm(frame: &FrameBuffer) -> Vec<u8> {
+    common::frame_pbm(frame.as_bytes())
+}
+
+#[test]
+fn matches_identical_frames() {
+    let expected = frame(&[Point::new(3, 4), Point::new(199, 199)]);
+    let data = pbm(&expected);
+    let diff = Diff::new(&expected, &DecodedImage::decode(&data).unwrap());
+    assert!(diff.is_empty());
+    assert_eq!(diff.pixels, 0);
+    assert!(diff.frame == FrameBuffer::new());
+}
+
+#[test]
+fn counts_differing_pixels() {
+    let data = pbm(&frame(&[
+        Point::new(0, 0),
+        Point::new(10, 20),
+        Point::new(199, 199),
+    ]));
+    let image = DecodedImage::decode(&data).unwrap();
+
+    // One pixel that is missing and two that are extra
+    let actual = frame(&[
+        Point::new(0, 0),
+        Point::new(10, 20),
+        Point::new(10, 21),
+        Point::new(100, 0),
+    ]);
+    let diff = Diff::new(&actual, &image);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.pixels, 3);
+    assert_eq!(
+        black_pixels(&diff.frame),
+        [Point::new(100, 0), Point::new(10, 21), Point::new(199, 199)]
+    );
+}
+
+#[test]
+fn compares_differently_sized_images() {
+    // A small image is compared as if the rest of it were white
+    let data = b"P1\n2 2\n1 0 0 1";
+    let image = DecodedImage::decode(data).unwrap();
+    let diff = Diff::new(&frame(&[Point::new(0, 0), Point::new(1, 1)]), &image);
+    assert!(diff.is_empty());
+    let diff = Diff::new(&frame(&[Point::new(0, 0), Point::new(5, 5)]), &image);
+    assert_eq!(
+        black_pixels(&diff.frame),
+        [Point::new(1, 1), Point::new(5, 5)]
+    );
+
+    // Pixels beyond the display always differ, but are not in the frame of differences
+    let mut data = format!("P1\n{} 1\n", WIDTH + 2).into_bytes();
+    data.extend(b"0".repeat(WIDTH as usize + 2));
+    let diff = Diff::new(&FrameBuffer::new(), &DecodedImage::decode(&data).unwrap());
+    assert_eq!(diff.pixels, 2);
+    assert!(diff.frame == FrameBuffer::new());
+}
@@ -0,0 +1,213 @@
+//! Tests of the conversion of BDF fonts, and of the text rendering of the `watchy` crate,
+//! which is pure `no_std` code that is included directly so that it can run on the host.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files, and the
+//! generated built-in fonts of the `watchy` crate, with the current output after
+//! checking that it looks correct.
+
+#[allow(dead_code)]
+mod common;
+#[allow(dead_code)]
+#[path = "../../src/text/font.rs"]
+mod font;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+#[path = "../../src/text/seven_segment.rs"]
+mod seven_segment;
+/// The `watchy::text` module, which the generated fonts refer to.
+mod text {
+    pub use super::{
+        font::{Font, FontStyle, Glyph},
+        seven_segment::SevenSegmentStyle,
+    };
+
+    pub const LARGE_DIGITS: Font<'static> = include!("../../src/text/large_digits.rs");
+    pub const SMALL: Font<'static> = include!("../../src/text/small.rs");
+}
+
+use common::{assert_golden, frame_pbm, path};
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use framebuffer::FrameBuffer;
+use text::{Font, FontStyle, SevenSegmentStyle, LARGE_DIGITS, SMALL};
+use watchy_assets::{AssetError, BdfFont, FontOptions};
+
+/// Draws text in the middle of an otherwise white framebuffer and compares it against a
+/// golden PBM file.
+fn assert_text_golden<S>(text: &str, style: S, golden: &str)
+where
+    S: embedded_graphics::text::renderer::TextRenderer<Color = BinaryColor>,
+{
+    let mut frame = FrameBuffer::new();
+    Text::with_text_style(
+        text,
+        Point::new(100, 100),
+        style,
+        TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build(),
+    )
+    .draw(&mut frame)
+    .unwrap();
+    assert_golden(&frame_pbm(frame.as_bytes()), golden);
+}
+
+#[test]
+fn converts_bdf() {
+    let font = BdfFont::open(path("tests/data/test.bdf")).unwrap();
+    // The unencoded glyph is skipped
+    assert!(font.chars().eq([' ', '?', 'A', 'g']));
+
+    let options = FontOptions {
+        module: "crate::text".into(),
+        ..Default::default()
+    };
+    assert_golden(
+        font.to_rust(&options).unwrap().as_bytes(),
+        "tests/data/test.font.rs",
+    );
+    let options = FontOptions {
+        chars: vec!['A'..='Z'],
+        proportional: Some(1),
+        ..options
+    };
+    assert_golden(
+        font.to_rust(&options).unwrap().as_bytes(),
+        "tests/data/test_proportional.font.rs",
+    );
+}
+
+#[test]
+fn rejects_malformed_bdf() {
+    let source = std::fs::read_to_string(path("tests/data/test.bdf")).unwrap();
+
+    let malformed = source.replacen("BBX 6 8 0 -2", "BBX 6 8 0", 1);
+    assert!(matches!(
+        BdfFont::parse(&malformed),
+        Err(AssetError::Bdf { line: 15, .. })
+    ));
+    let malformed = source.replacen("88\n", "8\n", 1);
+    assert!(matches!(
+        BdfFont::parse(&malformed),
+        Err(AssetError::Bdf { line: 18, .. })
+    ));
+    let malformed = &source[..source.find("ENDCHAR").unwrap()];
+    assert!(matches!(
+        BdfFont::parse(malformed),
+        Err(AssetError::Bdf { .. })
+    ));
+}
+
+#[test]
+fn builtin_fonts_are_current() {
+    let convert = |bdf: &str, options: FontOptions| {
+        BdfFont::open(path(bdf))
+            .unwrap()
+            .to_rust(&FontOptions {
+                module: "crate::text".into(),
+                ..options
+            })
+            .unwrap()
+    };
+
+    assert_golden(
+        convert(
+            "fonts/large_digits.bdf",
+            FontOptions {
+                replacement: ' ',
+                ..Default::default()
+            },
+        )
+        .as_bytes(),
+        "../src/text/large_digits.rs",
+    );
+    assert_golden(
+        convert(
+            "fonts/small.bdf",
+            FontOptions {
+                proportional: Some(1),
+                ..Default::default()
+            },
+        )
+        .as_bytes(),
+        "../src/text/small.rs",
+    );
+}
+
+#[test]
+fn draws_large_digits() {
+    assert_text_golden(
+        "12:34",
+        FontStyle::new(&LARGE_DIGITS, BinaryColor::On),
+        "tests/data/text_large_digits.pbm",
+    );
+}
+
+#[test]
+fn draws_small() {
+    let mut style = FontStyle::new(&SMALL, BinaryColor::Off);
+    style.background_color = Some(BinaryColor::On);
+    assert_text_golden("Grüße, café №1", style, "tests/data/text_small.pbm");
+}
+
+#[test]
+fn draws_seven_segment() {
+    assert_text_golden(
+        "-8.5:AF",
+        SevenSegmentStyle::new(Size::new(20, 40), 5, BinaryColor::On),
+        "tests/data/text_seven_segment.pbm",
+    );
+}
+
+#[test]
+fn measures_strings() {
+    /// Checks that text is drawn inside its measured bounding box.
+    fn assert_inside(font: &Font, text: &str, alignment: Alignment, baseline: Baseline) {
+        let mut frame = FrameBuffer::new();
+        let text = Text::with_text_style(
+            text,
+            Point::new(100, 100),
+            FontStyle::new(font, BinaryColor::On),
+            TextStyleBuilder::new()
+                .alignment(alignment)
+                .baseline(baseline)
+                .build(),
+        );
+        text.draw(&mut frame).unwrap();
+
+        let bounds = text.bounding_box();
+        assert_eq!(bounds.size.height, font.line_height());
+        assert_eq!(bounds.size.width, font.measure(text.text));
+        for point in frame.bounding_box().points() {
+            if frame.pixel(point) == Some(BinaryColor::On) {
+                assert!(bounds.contains(point), "{point:?} is outside {bounds:?}");
+            }
+        }
+    }
+
+    for alignment in [Alignment::Left, Alignment::Center, Alignment::Right] {
+        for baseline in [
+            Baseline::Top,
+            Baseline::Middle,
+            Baseline::Bottom,
+            Baseline::Alphabetic,
+        ] {
+            assert_inside(&SMALL, "Jolly quiz, Ågot!", alignment, baseline);
+            assert_inside(&LARGE_DIGITS, "08:59", alignment, baseline);
+        }
+    }
+
+    // Characters that are not in the font are replaced
+    assert_eq!(SMALL.measure("→"), SMALL.measure("?"));
+    assert_eq!(LARGE_DIGITS.measure("1:2"), 44 + 16 + 44);
+    assert_eq!(LARGE_DIGITS.measure("1x"), 44 + 44);
+
+    let style = SevenSegmentStyle::new(Size::new(20, 40), 5, BinaryColor::On);
+    // Unsupported characters are not drawn
+    assert_eq!(style.measure("1:2.x"), 25 + 10 + 25 + 10);
+}
@@ -0,0 +1,52 @@
+//! Tests that compare converted images against golden PBM files.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files with the
+//! current output after checking that it looks correct.
+
+#[allow(dead_code)]
+mod common;
+
+use common::data_path;
+use watchy_assets::{Bitmap, Dither};
+
+/// Converts an image and compares it against a golden PBM file.
+fn assert_golden(image: &str, dither: Dither, golden: &str) {
+    let pbm = Bitmap::open(data_path(image), dither).unwrap().to_pbm();
+    common::assert_golden(&pbm, &format!("tests/data/{golden}"));
+}
+
+#[test]
+fn gradient_threshold() {
+    assert_golden("gradient.png", Dither::None, "gradient_none.pbm");
+}
+
+#[test]
+fn gradient_floyd_steinberg() {
+    assert_golden(
+        "gradient.png",
+        Dither::FloydSteinberg,
+        "gradient_floyd_steinberg.pbm",
+    );
+}
+
+#[test]
+fn gradient_ordered() {
+    assert_golden("gradient.png", Dither::Ordered, "gradient_ordered.pbm");
+}
+
+#[test]
+fn icon_png_with_transparency() {
+    assert_golden("icon.png", Dither::None, "icon.pbm");
+}
+
+#[test]
+fn icon_bmp() {
+    assert_golden("icon.bmp", Dither::None, "icon.pbm");
+}
+
+#[test]
+fn rows_are_padded_with_white() {
+    let bitmap = Bitmap::open(data_path("icon.png"), Dither::None).unwrap();
+    assert_eq!(bitmap.as_bytes().len(), 3 * 20);
+    assert!(bitmap.as_bytes().chunks(3).all(|row| row[2] & 0x0F == 0x0F));
+}
@@ -0,0 +1,295 @@
+//! Tests of the compilation of layout files, and of the layout interpreter of the `watchy`
+//! crate, which is pure `no_std` code that is included directly along with the
+//! complications so that layouts can be previewed on the host.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files with the
+//! current output after checking that it looks correct.
+
+#[allow(dead_code)]
+#[path = "../../src/battery/status.rs"]
+mod battery;
+#[allow(dead_code)]
+#[path = "../../src/display/bitmap.rs"]
+mod bitmap;
+#[allow(dead_code)]
+mod common;
+#[allow(dead_code)]
+#[path = "../../src/face/complication.rs"]
+mod complication;
+#[allow(dead_code)]
+#[path = "../../src/text/font.rs"]
+mod font;
+#[path = "../../src/text/format.rs"]
+mod format;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+#[path = "../../src/face/layout.rs"]
+mod layout;
+#[allow(dead_code, unexpected_cfgs)]
+#[path = "../../src/rtc.rs"]
+mod rtc;
+#[path = "../../src/text/seven_segment.rs"]
+mod seven_segment;
+#[allow(dead_code)]
+#[path = "../../src/traits.rs"]
+mod traits;
+/// The `watchy::button` module, of which only the button enum is mirrored since the
+/// drivers need the hardware.
+mod button {
+    /// One of the four buttons.
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Button {
+        /// Button 1.
+        Menu,
+        /// Button 2.
+        Back,
+        /// Button 3.
+        Up,
+        /// Button 4.
+        Down,
+    }
+}
+/// The `watchy::display` module, which the layouts refer to.
+mod display {
+    pub use super::bitmap::Bitmap;
+}
+/// The `watchy::face` module, of which the context and the trait are mirrored since the
+/// runtime needs the hardware.
+mod face {
+    use super::{battery::BatteryStatus, button::Button, rtc::DateTime};
+    use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+    /// Why the watch woke up.
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WakeReason {
+        /// The ESP32 was powered on or reset rather than waking from deep sleep.
+        Boot,
+        /// The minute ticked over.
+        Tick,
+        /// A button was pressed.
+        Button(Button),
+        /// Something else woke the ESP32.
+        Other,
+    }
+
+    /// Everything that a watch face needs to know to draw itself.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FaceContext {
+        /// The current date and time.
+        pub now: Option<DateTime>,
+        /// The battery status.
+        pub battery: BatteryStatus,
+        /// The number of steps taken.
+        pub steps: Option<u32>,
+        /// When the next alarm goes off.
+        pub next_alarm: Option<DateTime>,
+        /// Why the watch woke up.
+        pub wake_reason: WakeReason,
+    }
+
+    /// A watch face.
+    pub trait WatchFace {
+        /// Draws the face onto a target that has been cleared to white.
+        fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+        where
+            D: DrawTarget<Color = BinaryColor>;
+    }
+}
+/// The `watchy::text` module, which the layouts refer to.
+mod text {
+    pub(crate) use super::format::Formatted;
+    pub use super::{
+        font::{Font, FontStyle, Glyph},
+        seven_segment::SevenSegmentStyle,
+    };
+
+    pub const LARGE_DIGITS: Font<'static> = include!("../../src/text/large_digits.rs");
+    pub const SMALL: Font<'static> = include!("../../src/text/small.rs");
+}
+
+use battery::BatteryStatus;
+use common::{assert_golden, data_path, frame_pbm};
+use complication::{Battery, Complication, Date, NextAlarm, Slot, Steps};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+use face::{FaceContext, WakeReason, WatchFace};
+use framebuffer::FrameBuffer;
+use layout::{Layout, LayoutError};
+use proptest::prelude::*;
+use rtc::DateTime;
+use watchy_assets::AssetError;
+
+/// Returns a context with everything set.
+fn context() -> FaceContext {
+    let now = DateTime {
+        year: 2024,
+        month: 10,
+        day: 19,
+        hour: 13,
+        minute: 5,
+        second: 0,
+    };
+    FaceContext {
+        now: Some(now),
+        battery: BatteryStatus::from_voltage(3800),
+        steps: Some(1234),
+        next_alarm: Some(DateTime {
+            hour: 7,
+            minute: 30,
+            ..now
+        }),
+        wake_reason: WakeReason::Tick,
+    }
+}
+
+#[test]
+fn draws_layout() {
+    let compiled = watchy_assets::Layout::open(data_path("layout.toml")).unwrap();
+    let mut layout = Layout::new(compiled.as_bytes()).unwrap();
+    assert_eq!(layout.elements().count(), 8);
+
+    let mut frame = FrameBuffer::new();
+    layout.draw(&context(), &mut frame).unwrap();
+    assert_golden(&frame_pbm(frame.as_bytes()), "tests/data/layout.pbm");
+}
+
+#[test]
+fn rejects_malformed_layouts() {
+    assert_eq!(Layout::new(b"P4\n"), Err(LayoutError::NotLayout));
+    assert_eq!(
+        Layout::new(b"WFL\x02"),
+        Err(LayoutError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        Layout::new(b"WFL\x01\x09"),
+        Err(LayoutError::UnknownElement(9))
+    );
+    // A date in an unknown font
+    assert_eq!(
+        Layout::new(b"WFL\x01\x02\x00\x00\x00\x00\x07\x00"),
+        Err(LayoutError::InvalidValue)
+    );
+
+    // Cutting the layout short anywhere within an element
+    let compiled = watchy_assets::Layout::open(data_path("layout.toml")).unwrap();
+    let data = compiled.as_bytes();
+    let truncated = (4..data.len())
+        .filter(|len| Layout::new(&data[..*len]).is_ok())
+        .count();
+    assert_eq!(truncated, 8, "only the element boundaries should be valid");
+    assert!((5..data.len()).all(|len| {
+        matches!(
+            Layout::new(&data[..len]),
+            Ok(_) | Err(LayoutError::Truncated)
+        )
+    }));
+}
+
+#[test]
+fn skips_empty_images() {
+    // Many empty images in a row followed by a date
+    let mut data = b"WFL\x01".to_vec();
+    for _ in 0..100_000 {
+        data.extend([5, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+    data.extend([2, 0, 0, 0, 0, 0, 0]);
+    let layout = Layout::new(&data).unwrap();
+    assert_eq!(layout.elements().count(), 1);
+}
+
+#[test]
+fn reports_layout_file_errors() {
+    assert!(matches!(
+        watchy_assets::Layout::parse("[[element]]\nkind = \"clock\"\n", "."),
+        Err(AssetError::Toml(_))
+    ));
+    let long = format!(
+        "[[element]]\nkind = \"text\"\ntext = \"{}\"\nx = 0\ny = 0\n",
+        "a".repeat(256)
+    );
+    assert!(matches!(
+        watchy_assets::Layout::parse(&long, "."),
+        Err(AssetError::InvalidLayout(_))
+    ));
+}
+
+#[test]
+fn complications_match_layout() {
+    let ctx = context();
+    assert_eq!(Battery.value(&ctx), 50);
+    assert_eq!(Steps.value(&ctx), Some(1234));
+    assert_eq!(NextAlarm.value(&ctx), Some((7, 30)));
+    assert_eq!(Date.value(&ctx).map(|date| date.day), Some(19));
+}
+
+#[test]
+fn battery_percentages() {
+    let percentage = |voltage| BatteryStatus::from_voltage(voltage).percentage();
+    assert_eq!(percentage(0), 0);
+    assert_eq!(percentage(3400), 0);
+    // Rounded to the nearest percent
+    assert_eq!(percentage(3403), 0);
+    assert_eq!(percentage(3404), 1);
+    assert_eq!(percentage(3800), 50);
+    assert_eq!(percentage(4195), 99);
+    assert_eq!(percentage(4196), 100);
+    assert_eq!(percentage(4200), 100);
+    assert_eq!(percentage(u32::MAX), 100);
+}
+
+#[test]
+fn slots_clear_their_area() {
+    let mut frame = FrameBuffer::new();
+    let steps_area = Rectangle::new(Point::new(10, 150), Size::new(100, 20));
+    let steps = Slot::new(Steps, steps_area);
+    let mut ctx = context();
+
+    // Only the area of the slot is drawn over
+    frame
+        .fill_solid(&frame.bounding_box(), BinaryColor::On)
+        .unwrap();
+    steps.draw(&ctx, &mut frame).unwrap();
+    assert_eq!(frame.pixel(Point::new(10, 149)), Some(BinaryColor::On));
+    assert_eq!(frame.pixel(Point::new(10, 150)), Some(BinaryColor::Off));
+    assert_eq!(frame.pixel(Point::new(110, 150)), Some(BinaryColor::On));
+
+    // Drawing a changed value over the last leaves nothing of it behind
+    ctx.steps = Some(1);
+    steps.draw(&ctx, &mut frame).unwrap();
+    let mut fresh = FrameBuffer::new();
+    fresh
+        .fill_solid(&fresh.bounding_box(), BinaryColor::On)
+        .unwrap();
+    steps.draw(&ctx, &mut fresh).unwrap();
+    assert_eq!(frame.as_bytes(), fresh.as_bytes());
+}
+
+#[test]
+fn draws_complications_in_tiny_areas() {
+    let ctx = context();
+    let mut frame = FrameBuffer::new();
+    for size in [Size::zero(), Size::new(1, 1), Size::new(3, 2)] {
+        let area = Rectangle::new(Point::new(10, 10), size);
+        Battery
+            .draw(&Battery.value(&ctx), area, &mut frame)
+            .unwrap();
+        Steps.draw(&Steps.value(&ctx), area, &mut frame).unwrap();
+        Date.draw(&Date.value(&ctx), area, &mut frame).unwrap();
+        NextAlarm
+            .draw(&NextAlarm.value(&ctx), area, &mut frame)
+            .unwrap();
+    }
+}
+
+proptest! {
+    #[test]
+    fn interprets_any_data(data in prop::collection::vec(any::<u8>(), 0..64)) {
+        let mut bytes = b"WFL\x01".to_vec();
+        bytes.extend(data);
+        if let Ok(mut layout) = Layout::new(&bytes) {
+            layout.draw(&context(), &mut FrameBuffer::new()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Tests of the display orientation of the `watchy` crate, which is pure `no_std` code
+//! that is included directly so that it can run on the host.
+
+// Not everything in the included modules is used here
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+#[allow(dead_code)]
+#[path = "../../src/display/orientation.rs"]
+mod orientation;
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+use framebuffer::{FrameBuffer, HEIGHT, WIDTH};
+use orientation::{Orientation, Oriented, Rotation};
+use proptest::prelude::*;
+
+/// All of the rotations.
+const ROTATIONS: [Rotation; 4] = [
+    Rotation::Deg0,
+    Rotation::Deg90,
+    Rotation::Deg180,
+    Rotation::Deg270,
+];
+
+/// A display that is not square, so that swapped dimensions are caught.
+const DISPLAY: Size = Size::new(200, 100);
+
+/// Returns the rotation that undoes a rotation.
+fn inverse(rotation: Rotation) -> Rotation {
+    match rotation {
+        Rotation::Deg0 => Rotation::Deg0,
+        Rotation::Deg90 => Rotation::Deg270,
+        Rotation::Deg180 => Rotation::Deg180,
+        Rotation::Deg270 => Rotation::Deg90,
+    }
+}
+
+/// Returns the orientations with every rotation and mirroring.
+fn orientations() -> impl Iterator<Item = Orientation> {
+    ROTATIONS.into_iter().flat_map(|rotation| {
+        [(false, false), (true, false), (false, true), (true, true)].map(
+            |(mirror_horizontal, mirror_vertical)| Orientation {
+                rotation,
+                mirror_horizontal,
+                mirror_vertical,
+            },
+        )
+    })
+}
+
+#[test]
+fn rotations_round_trip() {
+    for rotation in ROTATIONS {
+        let orientation = Orientation::new(rotation);
+        let size = orientation.size(DISPLAY);
+        let undo = Orientation::new(inverse(rotation));
+        assert_eq!(undo.size(size), DISPLAY);
+
+        // The drawn image is the display for the inverse rotation
+        for point in [
+            Point::zero(),
+            Point::new(size.width as i32 - 1, 0),
+            Point::new(0, size.height as i32 - 1),
+            Point::new(17, 42),
+        ] {
+            let displayed = orientation.transform(point, DISPLAY);
+            assert_eq!(undo.transform(displayed, size), point, "{rotation:?}");
+        }
+    }
+
+    // Four quarter turns are no turn at all
+    let quarter = Orientation::new(Rotation::Deg90);
+    let mut point = Point::new(17, 42);
+    let mut display = DISPLAY;
+    for _ in 0..4 {
+        display = quarter.size(display);
+        point = quarter.transform(point, display);
+    }
+    assert_eq!((point, display), (Point::new(17, 42), DISPLAY));
+}
+
+#[test]
+fn mirroring_round_trips() {
+    for orientation in orientations().filter(|o| o.rotation == Rotation::Deg0) {
+        for point in [Point::zero(), Point::new(199, 99), Point::new(17, 42)] {
+            let mirrored = orientation.transform(point, DISPLAY);
+            assert_eq!(orientation.transform(mirrored, DISPLAY), point);
+        }
+    }
+}
+
+#[test]
+fn corners_map_to_corners() {
+    let (right, bottom) = (DISPLAY.width as i32 - 1, DISPLAY.height as i32 - 1);
+    let top_left = Point::zero();
+    let top_right = Point::new(right, 0);
+    let bottom_right = Point::new(right, bottom);
+    let bottom_left = Point::new(0, bottom);
+
+    // The drawn top left, top right, bottom right, and bottom left corners
+    let cases = [
+        (
+            Rotation::Deg0,
+            [top_left, top_right, bottom_right, bottom_left],
+        ),
+        (
+            Rotation::Deg90,
+            [top_right, bottom_right, bottom_left, top_left],
+        ),
+        (
+            Rotation::Deg180,
+            [bottom_right, bottom_left, top_left, top_right],
+        ),
+        (
+            Rotation::Deg270,
+            [bottom_left, top_left, top_right, bottom_right],
+        ),
+    ];
+    for (rotation, expected) in cases {
+        let orientation = Orientation::new(rotation);
+        let size = orientation.size(DISPLAY);
+        let (right, bottom) = (size.width as i32 - 1, size.height as i32 - 1);
+        let corners = [
+            Point::zero(),
+            Point::new(right, 0),
+            Point::new(right, bottom),
+            Point::new(0, bottom),
+        ];
+        assert_eq!(
+            corners.map(|corner| orientation.transform(corner, DISPLAY)),
+            expected,
+            "{rotation:?}"
+        );
+    }
+}
+
+#[test]
+fn draws_oriented_corners() {
+    for orientation in orientations() {
+        let mut display = Oriented::new(FrameBuffer::new(), orientation);
+        let size = display.size();
+        assert_eq!(size, orientation.size(Size::new(WIDTH, HEIGHT)));
+
+        let corners = [
+            Point::zero(),
+            Point::new(size.width as i32 - 1, 0),
+            Point::new(size.width as i32 - 1, size.height as i32 - 1),
+            Point::new(0, size.height as i32 - 1),
+        ];
+        for corner in corners {
+            Pixel(corner, BinaryColor::On).draw(&mut display).unwrap();
+        }
+        // Points outside of the drawn image are discarded
+        Pixel(Point::new(-1, 0), BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+
+        let frame = display.into_inner();
+        let black: Vec<_> = frame
+            .bounding_box()
+            .points()
+            .filter(|&point| frame.pixel(point) == Some(BinaryColor::On))
+            .collect();
+        let mut expected: Vec<_> = corners
+            .map(|corner| orientation.transform(corner, Size::new(WIDTH, HEIGHT)))
+            .to_vec();
+        expected.sort_by_key(|point| (point.y, point.x));
+        assert_eq!(black, expected, "{orientation:?}");
+    }
+}
+
+proptest! {
+    #[test]
+    fn transform_is_invertible(
+        index in 0..16usize,
+        x in 0..200i32,
+        y in 0..200i32,
+    ) {
+        let orientation = orientations().nth(index).unwrap();
+        let size = orientation.size(DISPLAY);
+        let point = Point::new(x % size.width as i32, y % size.height as i32);
+        let displayed = orientation.transform(point, DISPLAY);
+        prop_assert!(Rectangle::new(Point::zero(), DISPLAY).contains(displayed));
+
+        // Mirroring before rotating is undone by rotating back before mirroring
+        let undo = Orientation {
+            rotation: inverse(orientation.rotation),
+            ..Default::default()
+        };
+        let unrotated = undo.transform(displayed, size);
+        let unmirrored = Orientation {
+            rotation: Rotation::Deg0,
+            ..orientation
+        }
+        .transform(unrotated, size);
+        prop_assert_eq!(unmirrored, point);
+    }
+}
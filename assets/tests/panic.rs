@@ -0,0 +1,156 @@
+//! Tests of the panic screen of the `watchy` crate, which is included directly without
+//! the panic hook so that it can run on the host.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files with the
+//! current output after checking that it looks correct.
+
+// Not everything in the included modules is used here
+#[allow(dead_code)]
+#[path = "../../src/battery/status.rs"]
+mod battery;
+#[allow(dead_code)]
+mod common;
+#[allow(dead_code)]
+#[path = "../../src/text/font.rs"]
+mod font;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+#[path = "../../src/panic/screen.rs"]
+mod screen;
+/// The `watchy::display` module, which the panic screen refers to.
+mod display {
+    pub use super::framebuffer::{HEIGHT, WIDTH};
+}
+/// The `watchy::text` module, which the panic screen refers to.
+mod text {
+    pub use super::font::{Font, FontStyle, Glyph};
+
+    pub const SMALL: Font<'static> = include!("../../src/text/small.rs");
+}
+
+use battery::BatteryStatus;
+use common::{assert_golden, frame_pbm};
+use framebuffer::FrameBuffer;
+use proptest::prelude::*;
+use screen::{draw, wrap, TEXT_WIDTH};
+use text::SMALL;
+
+/// Wraps text to the width of the panic screen.
+fn wrap_screen(text: &str) -> Vec<&str> {
+    wrap(text, &SMALL, TEXT_WIDTH)
+}
+
+#[test]
+fn wraps_at_spaces() {
+    let text = "called `Result::unwrap()` on an `Err` value: the quick brown fox jumps over \
+        the lazy dog";
+    let lines = wrap_screen(text);
+    assert!(lines.len() > 1);
+    for line in &lines {
+        assert!(SMALL.measure(line) <= TEXT_WIDTH, "{line:?}");
+        assert!(!line.starts_with(' ') && !line.ends_with(' '), "{line:?}");
+    }
+    assert_eq!(lines.join(" "), text);
+
+    // Short lines and blank lines are kept as they are
+    assert_eq!(wrap_screen("one\n\ntwo"), ["one", "", "two"]);
+    assert_eq!(wrap_screen(""), Vec::<&str>::new());
+}
+
+#[test]
+fn breaks_at_the_overflowing_space() {
+    // A line that exactly fills the width, followed by a space that does not fit
+    let word = "a".repeat((TEXT_WIDTH / SMALL.measure("a")) as usize);
+    assert_eq!(SMALL.measure(&word), TEXT_WIDTH);
+    let text = format!("{word} b");
+    assert_eq!(wrap_screen(&text), [word.as_str(), "b"]);
+
+    // Spaces at the end of a paragraph do not make a line of their own
+    let text = format!("{word}   ");
+    assert_eq!(wrap_screen(&text), [word.as_str()]);
+}
+
+#[test]
+fn keeps_leading_spaces() {
+    assert_eq!(wrap_screen("    at main"), ["    at main"]);
+    assert_eq!(wrap_screen("   "), ["   "]);
+
+    // The indentation stays with the first word rather than becoming a line
+    let long = "x".repeat(100);
+    let text = format!("  {long}");
+    let lines = wrap_screen(&text);
+    assert!(lines[0].starts_with("  x"), "{lines:?}");
+    assert!(lines.iter().all(|line| !line.is_empty()), "{lines:?}");
+    assert_eq!(lines.concat(), text);
+
+    // Runs of spaces between words are dropped at a break
+    let text = format!("  {}    {}", "y".repeat(30), "z".repeat(30));
+    assert_eq!(
+        wrap_screen(&text),
+        [format!("  {}", "y".repeat(30)), "z".repeat(30)]
+    );
+}
+
+#[test]
+fn breaks_wide_glyphs() {
+    // Every glyph is wider than the line, so each is on a line of its own
+    assert_eq!(wrap("AB CD", &SMALL, 1), ["A", "B", "C", "D"]);
+
+    // A word wider than the line is broken within it
+    let word = "W".repeat(60);
+    let lines = wrap_screen(&word);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|line| SMALL.measure(line) <= TEXT_WIDTH));
+    assert_eq!(lines.concat(), word);
+}
+
+#[test]
+fn wraps_multibyte_text() {
+    let text = ["Grüße aus München, café №1"; 4].join(" ");
+    let lines = wrap_screen(&text);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|line| SMALL.measure(line) <= TEXT_WIDTH));
+    assert_eq!(lines.join(" "), text);
+
+    // Breaking within a word does not split a character
+    let word = "üß№".repeat(40);
+    let lines = wrap(&word, &SMALL, 30);
+    assert!(lines.len() > 1);
+    assert_eq!(lines.concat(), word);
+}
+
+#[test]
+fn draws_panic() {
+    let message = "called `Option::unwrap()` on a `None` value while drawing the face: \
+        Grüße, café №1\n\nThe rest of this message is far too long to fit on the display, so \
+        it is cut short to leave room for where the panic occurred and the battery status \
+        that are always shown below it, followed by how to restart the watch.";
+    let mut frame = FrameBuffer::new();
+    draw(
+        &mut frame,
+        message,
+        None,
+        Some(BatteryStatus::from_voltage(3912)),
+    )
+    .unwrap();
+    assert_golden(&frame_pbm(frame.as_bytes()), "tests/data/panic.pbm");
+}
+
+proptest! {
+    #[test]
+    fn wrapped_lines_fit(text in "[ a-zA-Zäöü№\n]{0,300}", width in 1..200u32) {
+        let lines = wrap(&text, &SMALL, width);
+        for line in &lines {
+            // Only a single character may be wider than the line
+            prop_assert!(
+                SMALL.measure(line) <= width || line.chars().count() == 1,
+                "{:?}",
+                line
+            );
+        }
+        // No text is lost other than spaces
+        let squash = |text: &str| text.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        prop_assert_eq!(squash(&lines.concat()), squash(&text));
+    }
+}
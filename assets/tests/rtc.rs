@@ -0,0 +1,300 @@
+//! Tests of the date and time handling of the RTC drivers of the `watchy` crate, which
+//! is included directly along with mock I2C buses so that it can run on the host.
+
+// Not everything in the included modules is used here
+#[allow(dead_code)]
+#[path = "../../src/battery/status.rs"]
+mod battery;
+#[allow(dead_code, unexpected_cfgs)]
+#[path = "../../src/rtc.rs"]
+mod rtc;
+#[allow(dead_code)]
+#[path = "../../src/traits.rs"]
+mod traits;
+
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+use rtc::{Alarm, DateTime, Ds3231, Pcf8563, RtcError, Weekday};
+use std::convert::Infallible;
+
+/// An I2C bus on which a chip has registers from the first one, which are read and
+/// written from the register address that is written first.
+struct Registers(Vec<u8>);
+impl ErrorType for Registers {
+    type Error = Infallible;
+}
+impl I2c for Registers {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut offset = 0;
+        for operation in operations {
+            match operation {
+                Operation::Write(data) => {
+                    let Some((&reg, data)) = data.split_first() else {
+                        continue;
+                    };
+                    offset = reg.into();
+                    self.0[offset..offset + data.len()].copy_from_slice(data);
+                    offset += data.len();
+                }
+                Operation::Read(buf) => {
+                    buf.copy_from_slice(&self.0[offset..offset + buf.len()]);
+                    offset += buf.len();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns a date and time at midday.
+fn date(year: u16, month: u8, day: u8) -> DateTime {
+    DateTime {
+        year,
+        month,
+        day,
+        hour: 12,
+        minute: 0,
+        second: 0,
+    }
+}
+
+/// Returns a time of day on the 19th of October 2024.
+fn at(hour: u8, minute: u8, second: u8) -> DateTime {
+    DateTime {
+        hour,
+        minute,
+        second,
+        ..date(2024, 10, 19)
+    }
+}
+
+#[test]
+fn weekdays() {
+    assert_eq!(date(2024, 10, 19).weekday(), Some(Weekday::Saturday));
+    assert_eq!(date(2000, 1, 1).weekday(), Some(Weekday::Saturday));
+    assert_eq!(date(2000, 2, 29).weekday(), Some(Weekday::Tuesday));
+    assert_eq!(date(2199, 12, 31).weekday(), Some(Weekday::Tuesday));
+    assert_eq!(date(1970, 1, 1).weekday(), Some(Weekday::Thursday));
+}
+
+#[test]
+fn weekdays_agree_with_timestamps() {
+    // Steps of a prime number of days so that every weekday and month are covered
+    for days in (0..200 * 365).step_by(13) {
+        let date_time = DateTime::from_timestamp(946_684_800 + days * 86400);
+        let weekday = date_time.weekday().unwrap();
+        assert_eq!(
+            i64::from(weekday.number_from_sunday()),
+            (days + 6) % 7,
+            "{date_time:?}"
+        );
+    }
+}
+
+#[test]
+fn weekday_of_invalid_dates() {
+    assert_eq!(date(2024, 0, 1).weekday(), None);
+    assert_eq!(date(2024, 13, 1).weekday(), None);
+    assert_eq!(date(2024, 255, 1).weekday(), None);
+    assert_eq!(date(2024, 1, 0).weekday(), None);
+    assert_eq!(date(2023, 2, 29).weekday(), None);
+    assert_eq!(date(2024, 4, 31).weekday(), None);
+}
+
+#[test]
+fn weekday_of_year_zero() {
+    // The proleptic Gregorian year 0 is a leap year that started on a Saturday
+    assert_eq!(date(0, 1, 1).weekday(), Some(Weekday::Saturday));
+    assert_eq!(date(0, 2, 29).weekday(), Some(Weekday::Tuesday));
+    assert_eq!(date(0, 12, 31).weekday(), Some(Weekday::Sunday));
+}
+
+#[test]
+fn pcf8563_reads_time() {
+    // Seconds, minutes, hours, days, weekdays, century and months, and years
+    let registers = [0, 0, 0x13, 0x57, 0x07, 0x19, 0x06, 0x10, 0x24];
+    let mut rtc = Pcf8563::new(Registers(registers.to_vec()));
+    assert_eq!(rtc.now().unwrap(), at(7, 57, 13));
+}
+
+#[test]
+fn pcf8563_rejects_invalid_data() {
+    let invalid = [
+        // Month 0
+        [0x13, 0x57, 0x07, 0x19, 0x06, 0x00, 0x24],
+        // Month 13
+        [0x13, 0x57, 0x07, 0x19, 0x06, 0x13, 0x24],
+        // Day 0
+        [0x13, 0x57, 0x07, 0x00, 0x06, 0x10, 0x24],
+        // February 30th
+        [0x13, 0x57, 0x07, 0x30, 0x06, 0x02, 0x24],
+        // Hour 24
+        [0x13, 0x57, 0x24, 0x19, 0x06, 0x10, 0x24],
+        // Minute 60
+        [0x13, 0x60, 0x07, 0x19, 0x06, 0x10, 0x24],
+        // Not BCD
+        [0x13, 0x4A, 0x07, 0x19, 0x06, 0x10, 0x24],
+        [0x13, 0x57, 0x07, 0x19, 0x06, 0x10, 0xA0],
+    ];
+    for time in invalid {
+        let mut registers = vec![0, 0];
+        registers.extend(time);
+        let mut rtc = Pcf8563::new(Registers(registers));
+        assert!(
+            matches!(rtc.now(), Err(RtcError::InvalidData)),
+            "{time:02X?}"
+        );
+    }
+}
+
+#[test]
+fn ds3231_reads_time() {
+    // Seconds, minutes, hours, weekdays, days, century and months, and years, followed
+    // by the alarms, control, status, aging offset, and temperature
+    let mut registers = vec![0x13, 0x57, 0x07, 0x07, 0x19, 0x10, 0x24];
+    registers.extend([0; 12]);
+    let mut rtc = Ds3231::new(Registers(registers.clone()));
+    assert_eq!(rtc.now().unwrap(), at(7, 57, 13));
+
+    // 12 hour mode at 7 PM and 12 AM
+    registers[2] = 0x67;
+    let mut rtc = Ds3231::new(Registers(registers.clone()));
+    assert_eq!(rtc.now().unwrap(), at(19, 57, 13));
+    registers[2] = 0x52;
+    let mut rtc = Ds3231::new(Registers(registers));
+    assert_eq!(rtc.now().unwrap(), at(0, 57, 13));
+}
+
+#[test]
+fn ds3231_rejects_invalid_data() {
+    let invalid = [
+        // Month 0
+        [0x13, 0x57, 0x07, 0x07, 0x19, 0x00, 0x24],
+        // Month 13
+        [0x13, 0x57, 0x07, 0x07, 0x19, 0x13, 0x24],
+        // Hour 0 in 12 hour mode
+        [0x13, 0x57, 0x40, 0x07, 0x19, 0x10, 0x24],
+        // Hour 13 in 12 hour mode
+        [0x13, 0x57, 0x53, 0x07, 0x19, 0x10, 0x24],
+    ];
+    for time in invalid {
+        let mut registers = time.to_vec();
+        registers.extend([0; 12]);
+        let mut rtc = Ds3231::new(Registers(registers));
+        assert!(
+            matches!(rtc.now(), Err(RtcError::InvalidData)),
+            "{time:02X?}"
+        );
+    }
+}
+
+#[test]
+fn set_rejects_invalid_dates() {
+    let mut rtc = Pcf8563::new(Registers(vec![0; 16]));
+    for date_time in [
+        date(2024, 0, 1),
+        date(2024, 13, 1),
+        date(0, 1, 1),
+        date(1999, 12, 31),
+        date(2200, 1, 1),
+    ] {
+        assert!(
+            matches!(rtc.set(&date_time), Err(RtcError::OutOfRange)),
+            "{date_time:?}"
+        );
+    }
+    rtc.set(&date(2199, 12, 31)).unwrap();
+}
+
+#[test]
+fn alarm_next() {
+    let alarm = Alarm {
+        hour: 7,
+        minute: 30,
+    };
+    let today = at(7, 30, 0);
+    let tomorrow = DateTime { day: 20, ..today };
+    assert_eq!(alarm.next(&at(0, 0, 0)), today);
+    assert_eq!(alarm.next(&at(7, 29, 59)), today);
+    // The alarm is still due during its minute
+    assert_eq!(alarm.next(&at(7, 30, 59)), today);
+    assert_eq!(alarm.next(&at(7, 31, 0)), tomorrow);
+    assert_eq!(alarm.next(&at(23, 59, 59)), tomorrow);
+
+    let midnight = Alarm { hour: 0, minute: 0 };
+    let new_year = midnight.next(&DateTime {
+        hour: 23,
+        minute: 59,
+        ..date(2024, 12, 31)
+    });
+    assert_eq!(
+        new_year,
+        DateTime {
+            hour: 0,
+            ..date(2025, 1, 1)
+        }
+    );
+}
+
+#[test]
+fn pcf8563_alarm() {
+    // The alarms are disabled on a reset
+    let mut registers = vec![0; 16];
+    registers[0x09..0x0D].fill(0x80);
+    let mut rtc = Pcf8563::new(Registers(registers));
+    assert_eq!(rtc.alarm().unwrap(), None);
+
+    let alarm = Alarm {
+        hour: 19,
+        minute: 45,
+    };
+    rtc.set_alarm(Some(alarm)).unwrap();
+    assert_eq!(rtc.alarm().unwrap(), Some(alarm));
+    assert!(matches!(
+        rtc.set_alarm(Some(Alarm {
+            hour: 24,
+            minute: 0
+        })),
+        Err(RtcError::OutOfRange)
+    ));
+    rtc.set_alarm(None).unwrap();
+    assert_eq!(rtc.alarm().unwrap(), None);
+
+    // Minute, hour, day, and weekday alarms
+    let mut registers = Registers(vec![0; 16]);
+    registers.0[0x09..0x0D].copy_from_slice(&[0x45, 0x19, 0x19, 0x80]);
+    let mut rtc = Pcf8563::new(registers);
+    assert_eq!(rtc.alarm().unwrap(), None, "monthly alarms are not daily");
+    let mut registers = rtc.release();
+    registers.0[0x09..0x0D].copy_from_slice(&[0x4A, 0x19, 0x80, 0x80]);
+    let mut rtc = Pcf8563::new(registers);
+    assert!(matches!(rtc.alarm(), Err(RtcError::InvalidData)));
+}
+
+#[test]
+fn ds3231_alarm() {
+    let mut rtc = Ds3231::new(Registers(vec![0; 19]));
+    assert_eq!(rtc.alarm().unwrap(), None);
+
+    let alarm = Alarm {
+        hour: 19,
+        minute: 45,
+    };
+    rtc.set_alarm(Some(alarm)).unwrap();
+    assert_eq!(rtc.alarm().unwrap(), Some(alarm));
+    rtc.set_alarm(None).unwrap();
+    assert_eq!(rtc.alarm().unwrap(), None);
+
+    // Alarm 2 at 7:45 PM in 12 hour mode, with its interrupt enabled
+    let mut registers = rtc.release();
+    registers.0[0x0B..0x0F].copy_from_slice(&[0x45, 0x67, 0x80, 0x02]);
+    let mut rtc = Ds3231::new(registers);
+    assert_eq!(rtc.alarm().unwrap(), Some(alarm));
+    let mut registers = rtc.release();
+    registers.0[0x0C] = 0x40;
+    let mut rtc = Ds3231::new(registers);
+    assert!(matches!(rtc.alarm(), Err(RtcError::InvalidData)));
+}
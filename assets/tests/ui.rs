@@ -0,0 +1,366 @@
+//! Tests of the menus of the `watchy` crate, which are pure `no_std` code that is
+//! included directly so that the navigation can be driven by button presses on the host.
+//!
+//! Set the `WATCHY_BLESS` environment variable to overwrite the golden files with the
+//! current output after checking that it looks correct.
+
+#[allow(dead_code)]
+mod common;
+#[allow(dead_code)]
+#[path = "../../src/text/font.rs"]
+mod font;
+#[path = "../../src/text/format.rs"]
+mod format;
+#[allow(dead_code)]
+#[path = "../../src/display/framebuffer.rs"]
+mod framebuffer;
+#[path = "../../src/ui/menu.rs"]
+mod menu;
+#[path = "../../src/ui/render.rs"]
+mod render;
+/// The `watchy::button` module, of which only the button enum is mirrored since the
+/// drivers need the hardware.
+mod button {
+    /// One of the four buttons.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Button {
+        /// Button 1.
+        Menu,
+        /// Button 2.
+        Back,
+        /// Button 3.
+        Up,
+        /// Button 4.
+        Down,
+    }
+}
+/// The `watchy::display` module, which the menus refer to.
+mod display {
+    pub use super::framebuffer::{HEIGHT, WIDTH};
+}
+/// The `watchy::text` module, which the menus and the small font refer to.
+mod text {
+    pub use super::font::{Font, FontStyle, Glyph};
+    pub(crate) use super::format::Formatted;
+
+    pub const SMALL: Font<'static> = include!("../../src/text/small.rs");
+}
+
+use button::Button;
+use embedded_graphics::prelude::*;
+use framebuffer::FrameBuffer;
+use menu::{Entry, Event, Menu, MenuState, MenuValues, Mode};
+use proptest::prelude::*;
+use render::VISIBLE_ROWS;
+
+/// ID of the toggle.
+const VIBRATE: u16 = 1;
+/// ID of the spinner.
+const CONTRAST: u16 = 2;
+/// ID of the confirmed action.
+const RESET: u16 = 3;
+/// ID of the first of the numbered actions.
+const ALARM: u16 = 10;
+
+/// A submenu with more entries than fit on the display.
+const ALARMS: Menu = Menu::new(
+    "Alarms",
+    &[
+        Entry::action(ALARM, "Alarm 1"),
+        Entry::action(ALARM + 1, "Alarm 2"),
+        Entry::action(ALARM + 2, "Alarm 3"),
+        Entry::action(ALARM + 3, "Alarm 4"),
+        Entry::action(ALARM + 4, "Alarm 5"),
+        Entry::action(ALARM + 5, "Alarm 6"),
+        Entry::action(ALARM + 6, "Alarm 7"),
+        Entry::action(ALARM + 7, "Alarm 8"),
+        Entry::action(ALARM + 8, "Alarm 9"),
+        Entry::action(ALARM + 9, "Alarm 10"),
+        Entry::action(ALARM + 10, "Alarm 11"),
+    ],
+);
+/// The top level menu.
+const SETTINGS: Menu = Menu::new(
+    "Settings",
+    &[
+        Entry::toggle(VIBRATE, "Vibrate"),
+        Entry::spinner(CONTRAST, "Contrast", 0, 10, 2),
+        Entry::submenu("Alarms", &ALARMS),
+        Entry::confirm(RESET, "Reset", "Erase everything?"),
+    ],
+);
+
+/// The values of the test menus.
+#[derive(Debug, Clone, Copy)]
+struct Values {
+    /// The toggle value.
+    vibrate: bool,
+    /// The spinner value.
+    contrast: i32,
+}
+impl Values {
+    /// Applies an event to the values.
+    fn apply(&mut self, event: Option<Event>) {
+        match event {
+            Some(Event::Toggled(VIBRATE, value)) => self.vibrate = value,
+            Some(Event::Changed(CONTRAST, value)) => self.contrast = value,
+            _ => {}
+        }
+    }
+}
+impl Default for Values {
+    fn default() -> Self {
+        Self {
+            vibrate: false,
+            contrast: 4,
+        }
+    }
+}
+impl MenuValues for Values {
+    fn toggle(&self, id: u16) -> bool {
+        assert_eq!(id, VIBRATE);
+        self.vibrate
+    }
+
+    fn number(&self, id: u16) -> i32 {
+        assert_eq!(id, CONTRAST);
+        self.contrast
+    }
+}
+
+/// Presses buttons in turn, applying any events to the values and returning them.
+fn press(state: &mut MenuState, values: &mut Values, buttons: &[Button]) -> Vec<Event> {
+    buttons
+        .iter()
+        .filter_map(|button| {
+            let event = state.press(*button, values).event;
+            values.apply(event);
+            event
+        })
+        .collect()
+}
+
+/// Draws a menu and compares it against a golden PBM file.
+fn assert_golden(state: &MenuState, values: &Values, golden: &str) {
+    let mut frame = FrameBuffer::new();
+    state.draw(values, &mut frame).unwrap();
+    common::assert_golden(&common::frame_pbm(frame.as_bytes()), golden);
+}
+
+#[test]
+fn navigates_menus() {
+    let mut values = Values::default();
+    let mut state = MenuState::new(&SETTINGS);
+    assert_eq!(state.selected().unwrap().label, "Vibrate");
+
+    // Moving wraps around at either end
+    press(&mut state, &mut values, &[Button::Up]);
+    assert_eq!(state.selected().unwrap().label, "Reset");
+    press(&mut state, &mut values, &[Button::Down, Button::Down]);
+    assert_eq!(state.selected().unwrap().label, "Contrast");
+
+    // Selecting past the end selects the last entry
+    state.select(10);
+    assert_eq!(state.selected().unwrap().label, "Reset");
+    state.select(1);
+
+    // Into the submenu and scroll to the second page
+    press(&mut state, &mut values, &[Button::Down, Button::Menu]);
+    assert_eq!(state.depth(), 2);
+    assert_eq!(state.menu().title, "Alarms");
+    assert_eq!(state.first_visible(), 0);
+    press(&mut state, &mut values, &[Button::Up]);
+    assert_eq!(state.selected_index(), 10);
+    assert_eq!(state.first_visible(), VISIBLE_ROWS);
+    assert_eq!(
+        press(&mut state, &mut values, &[Button::Menu]),
+        [Event::Selected(ALARM + 10)]
+    );
+
+    // Back out, which keeps the selection of the outer menu
+    press(&mut state, &mut values, &[Button::Back]);
+    assert_eq!(state.depth(), 1);
+    assert_eq!(state.selected().unwrap().label, "Alarms");
+    assert_eq!(
+        press(&mut state, &mut values, &[Button::Back]),
+        [Event::Closed]
+    );
+}
+
+#[test]
+fn edits_values() {
+    let mut values = Values::default();
+    let mut state = MenuState::new(&SETTINGS);
+
+    assert_eq!(
+        press(&mut state, &mut values, &[Button::Menu, Button::Menu]),
+        [
+            Event::Toggled(VIBRATE, true),
+            Event::Toggled(VIBRATE, false)
+        ]
+    );
+
+    // The spinner is clamped to its range, and only changed once selected
+    press(&mut state, &mut values, &[Button::Down, Button::Menu]);
+    assert_eq!(state.mode(), Mode::Editing(4));
+    press(&mut state, &mut values, &[Button::Down; 3]);
+    assert_eq!(state.mode(), Mode::Editing(0));
+    assert_eq!(values.contrast, 4);
+    press(&mut state, &mut values, &[Button::Back]);
+    assert_eq!(state.mode(), Mode::Browsing);
+    assert_eq!(values.contrast, 4);
+    assert_eq!(
+        press(
+            &mut state,
+            &mut values,
+            &[Button::Menu, Button::Up, Button::Menu]
+        ),
+        [Event::Changed(CONTRAST, 6)]
+    );
+
+    // The dialog defaults to no
+    press(
+        &mut state,
+        &mut values,
+        &[Button::Down, Button::Down, Button::Menu],
+    );
+    assert_eq!(state.mode(), Mode::Confirming(false));
+    assert!(press(&mut state, &mut values, &[Button::Menu]).is_empty());
+    assert!(press(&mut state, &mut values, &[Button::Menu, Button::Back]).is_empty());
+    assert_eq!(
+        press(
+            &mut state,
+            &mut values,
+            &[Button::Menu, Button::Down, Button::Menu]
+        ),
+        [Event::Confirmed(RESET)]
+    );
+    assert_eq!(state.mode(), Mode::Browsing);
+}
+
+#[test]
+fn selecting_cancels_editing() {
+    let mut values = Values::default();
+    let mut state = MenuState::new(&SETTINGS);
+
+    // Selecting another entry while editing must not leave it being edited
+    state.select(1);
+    press(&mut state, &mut values, &[Button::Menu]);
+    assert_eq!(state.mode(), Mode::Editing(4));
+    state.select(0);
+    assert_eq!(state.mode(), Mode::Browsing);
+    assert_eq!(
+        press(&mut state, &mut values, &[Button::Menu]),
+        [Event::Toggled(VIBRATE, true)]
+    );
+
+    // Likewise for the dialog
+    state.select(3);
+    press(&mut state, &mut values, &[Button::Menu]);
+    assert_eq!(state.mode(), Mode::Confirming(false));
+    state.select(2);
+    assert_eq!(state.mode(), Mode::Browsing);
+    assert_eq!(state.selected().unwrap().label, "Alarms");
+}
+
+#[test]
+fn handles_empty_and_deep_menus() {
+    const EMPTY: Menu = Menu::new("Empty", &[]);
+    static DEEP: Menu = Menu::new("Deep", &[Entry::submenu("Deeper", &DEEP)]);
+    let mut values = Values::default();
+
+    let mut state = MenuState::new(&EMPTY);
+    assert!(press(&mut state, &mut values, &[Button::Up, Button::Menu]).is_empty());
+    assert_eq!(
+        press(&mut state, &mut values, &[Button::Back]),
+        [Event::Closed]
+    );
+
+    let mut state = MenuState::new(&DEEP);
+    press(&mut state, &mut values, &[Button::Menu; 20]);
+    assert_eq!(state.depth(), MenuState::MAX_DEPTH);
+}
+
+#[test]
+fn draws_menus() {
+    let mut values = Values {
+        vibrate: true,
+        contrast: 4,
+    };
+    let mut state = MenuState::new(&SETTINGS);
+    press(
+        &mut state,
+        &mut values,
+        &[Button::Down, Button::Menu, Button::Up],
+    );
+    assert_golden(&state, &values, "tests/data/ui_spinner.pbm");
+
+    press(
+        &mut state,
+        &mut values,
+        &[
+            Button::Back,
+            Button::Down,
+            Button::Down,
+            Button::Menu,
+            Button::Up,
+        ],
+    );
+    assert_golden(&state, &values, "tests/data/ui_dialog.pbm");
+
+    let mut state = MenuState::new(&ALARMS);
+    press(&mut state, &mut values, &[Button::Up, Button::Up]);
+    assert_golden(&state, &values, "tests/data/ui_scrolled.pbm");
+}
+
+/// Returns any button.
+fn any_button() -> impl Strategy<Value = Button> {
+    prop_oneof![
+        Just(Button::Menu),
+        Just(Button::Back),
+        Just(Button::Up),
+        Just(Button::Down),
+    ]
+}
+
+proptest! {
+    // Each case draws the whole menu after every press, which is slow in debug builds
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn dirty_area_covers_changes(buttons in prop::collection::vec(any_button(), 1..40)) {
+        let mut values = Values::default();
+        let mut state = MenuState::new(&SETTINGS);
+        let mut before = FrameBuffer::new();
+        state.draw(&values, &mut before).unwrap();
+
+        for button in buttons {
+            let response = state.press(button, &values);
+            values.apply(response.event);
+            let mut after = FrameBuffer::new();
+            state.draw(&values, &mut after).unwrap();
+
+            let changed = before
+                .as_bytes()
+                .iter()
+                .zip(after.as_bytes())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .flat_map(|(index, (before, after))| {
+                    let y = index / FrameBuffer::ROW_BYTES;
+                    let x = index % FrameBuffer::ROW_BYTES * 8;
+                    (0..8)
+                        .filter(move |bit| (before ^ after) & (0x80 >> bit) != 0)
+                        .map(move |bit| Point::new((x + bit) as i32, y as i32))
+                });
+            for point in changed {
+                prop_assert!(
+                    response.dirty.is_some_and(|dirty| dirty.contains(point)),
+                    "{point:?} changed outside of {:?} after {button:?}",
+                    response.dirty
+                );
+            }
+            before = after;
+        }
+    }
+}
@@ -3,44 +3,104 @@
 // Re-export core driver crate
 pub use bma423;
 
-use crate::{
-    hal::{delay, gpio},
-    pins,
-    sys::EspError,
-};
-use embedded_hal::i2c;
+#[cfg(not(feature = "sim"))]
+use crate::pins;
+#[cfg(feature = "sim")]
+use crate::sim;
+use crate::traits::MotionSensor;
+#[cfg(feature = "esp-idf")]
+use crate::{hal::gpio, sys::EspError};
+use embedded_hal::{delay::DelayNs, i2c};
+use enumset::{EnumSet, EnumSetType};
+#[cfg(feature = "esp-hal")]
+use esp_hal::gpio;
+use rounded_div::RoundedDiv;
 use thiserror::Error;
 
-use bma423::{Bma423, ChipId, Config, Error, FullPower};
+use bma423::Error;
+#[cfg(not(feature = "sim"))]
+use bma423::{Bma423, ChipId, Config, FullPower};
 
 /// Error for display setup problems.
 #[derive(Error, Debug)]
-pub enum AccelerometerError<E: std::fmt::Debug> {
+pub enum AccelerometerError<E: core::fmt::Debug> {
     /// The chip returned an invalid chip ID.
     #[error("Bad chip ID")]
     BadId,
     /// An ESP peripheral error.
+    #[cfg(feature = "esp-idf")]
     #[error("Esp error: {0}")]
-    Esp(#[from] EspError),
+    Esp(#[cfg_attr(feature = "std", source)] EspError),
     /// An error with the accelerometer driver.
     #[error("Accelerometer driver error: {0:?}")]
-    Driver(#[from] Error<E>),
+    Driver(#[cfg_attr(feature = "std", source)] Error<E>),
+    /// An error on the I2C bus when accessing the chip registers directly.
+    #[error("I2C bus error: {0:?}")]
+    Bus(E),
+    /// The built-in self-test failed for the contained axes.
+    #[error("Self-test failed for axes: {0:?}")]
+    SelfTestFailed(EnumSet<Axis>),
+    /// The watch was not lying flat and motionless during offset calibration.
+    #[error("Watch is not lying flat")]
+    NotFlat,
+}
+
+// NOTE: These are implemented manually because the source errors only implement
+// the error trait when using `std`.
+#[cfg(feature = "esp-idf")]
+impl<E: core::fmt::Debug> From<EspError> for AccelerometerError<E> {
+    fn from(value: EspError) -> Self {
+        Self::Esp(value)
+    }
+}
+impl<E: core::fmt::Debug> From<Error<E>> for AccelerometerError<E> {
+    fn from(value: Error<E>) -> Self {
+        Self::Driver(value)
+    }
+}
+
+/// An axis of the accelerometer.
+#[derive(EnumSetType, Debug)]
+pub enum Axis {
+    /// The X axis.
+    X,
+    /// The Y axis.
+    Y,
+    /// The Z axis.
+    Z,
 }
 
 /// Breakout of the accelerometer driver and its interrupt pin drivers.
 ///
 /// The primary interface to the BMA423 accelerometer chip is via an [I2C bus](https://en.wikipedia.org/wiki/I%C2%B2C).
+/// Features not provided by the core driver crate, such as the step counter, are
+/// accessed through [`AccelerometerRegisters`], which also implements [`MotionSensor`],
+/// sharing the bus with the driver.
+#[cfg(not(feature = "sim"))]
 pub struct AccelerometerDriver<'d, I2C> {
     /// The accelerometer driver.
     pub driver: Bma423<I2C, FullPower>,
     /// Pin driver for the interrupt 1 line.
+    #[cfg(feature = "esp-idf")]
     pub pin_driver_int1: gpio::PinDriver<'d, gpio::Gpio14, gpio::Input>,
     /// Pin driver for the interrupt 2 line.
-    pub pin_driver_int2: gpio::PinDriver<'d, gpio::Gpio12, gpio::Input>,
+    #[cfg(feature = "esp-idf")]
+    pub pin_driver_int2: gpio::PinDriver<'d, pins::AccelerometerInt2, gpio::Input>,
+    /// Pin driver for the interrupt 1 line.
+    #[cfg(feature = "esp-hal")]
+    pub pin_driver_int1: gpio::Input<'d>,
+    /// Pin driver for the interrupt 2 line.
+    #[cfg(feature = "esp-hal")]
+    pub pin_driver_int2: gpio::Input<'d>,
 }
+#[cfg(not(feature = "sim"))]
 impl<I2C: i2c::I2c> AccelerometerDriver<'_, I2C> {
     /// Sets up the accelerometer driver and interrupt pin drivers.
     ///
+    /// This always initializes the chip with the `config`, which also resets the step
+    /// counter, so once the chip is running it can instead be
+    /// [resumed](AccelerometerRegisters::resume), such as after deep sleep.
+    ///
     /// It is recommended to setup the `i2c_driver` using the [`i2c_driver`](crate::i2c_driver) function
     /// as this will configure the I2C with the correct settings for the chip.
     ///
@@ -65,16 +125,646 @@ impl<I2C: i2c::I2c> AccelerometerDriver<'_, I2C> {
         config: Config,
     ) -> Result<Self, AccelerometerError<I2C::Error>> {
         // Setup and initialize accelerometer driver
-        let mut driver = Bma423::new(i2c_driver, config).init(&mut delay::Delay::new_default())?;
+        let mut driver = Bma423::new(i2c_driver, config).init(&mut crate::delay())?;
 
         // Verify that the device was found
         match driver.read_chip_id()? {
             ChipId::Unknown => Err(AccelerometerError::BadId),
+            #[cfg(feature = "esp-idf")]
             ChipId::Bma423 => Ok(AccelerometerDriver {
                 driver,
                 pin_driver_int1: gpio::PinDriver::input(accelerometer_pins.int_1)?,
                 pin_driver_int2: gpio::PinDriver::input(accelerometer_pins.int_2)?,
             }),
+            #[cfg(feature = "esp-hal")]
+            ChipId::Bma423 => Ok(AccelerometerDriver {
+                driver,
+                pin_driver_int1: gpio::Input::new(
+                    accelerometer_pins.int_1,
+                    gpio::InputConfig::default(),
+                ),
+                pin_driver_int2: gpio::Input::new(
+                    accelerometer_pins.int_2,
+                    gpio::InputConfig::default(),
+                ),
+            }),
+        }
+    }
+}
+
+/// Driver for the step counter of the accelerometer of a simulated watch.
+#[cfg(feature = "sim")]
+#[derive(Debug, Clone)]
+pub struct AccelerometerDriver {
+    /// The simulated watch.
+    watch: sim::Watch,
+}
+#[cfg(feature = "sim")]
+impl AccelerometerDriver {
+    /// Sets up the accelerometer driver of a simulated watch.
+    ///
+    /// # Example
+    /// ```
+    /// let watch = watchy::sim::Watch::new();
+    /// let accelerometer_driver = watchy::accelerometer::AccelerometerDriver::new(&watch);
+    /// watch.set_steps(1234);
+    /// assert_eq!(accelerometer_driver.steps(), 1234);
+    /// ```
+    pub fn new(watch: &sim::Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+        }
+    }
+
+    /// Returns the acceleration along each axis in mg.
+    pub fn acceleration(&self) -> [i32; 3] {
+        self.watch.acceleration()
+    }
+
+    /// Returns the number of steps counted.
+    pub fn steps(&self) -> u32 {
+        self.watch.steps()
+    }
+
+    /// Resets the step counter to zero.
+    pub fn reset_steps(&mut self) {
+        self.watch.set_steps(0);
+    }
+}
+#[cfg(feature = "sim")]
+impl MotionSensor for AccelerometerDriver {
+    type Error = core::convert::Infallible;
+
+    fn acceleration(&mut self) -> Result<[i32; 3], Self::Error> {
+        Ok(AccelerometerDriver::acceleration(self))
+    }
+
+    fn steps(&mut self) -> Result<Option<u32>, Self::Error> {
+        Ok(Some(AccelerometerDriver::steps(self)))
+    }
+}
+
+/// Raw BMA423 register addresses and values not covered by the core driver crate.
+mod reg {
+    /// Chip identification register.
+    pub const CHIP_ID: u8 = 0x00;
+    /// Start of the six acceleration data registers (X, Y, Z; LSB first).
+    pub const ACC_DATA: u8 = 0x12;
+    /// Start of the four step counter output registers (LSB first).
+    pub const STEP_COUNTER: u8 = 0x1E;
+    /// Temperature register, in K relative to [`TEMPERATURE_ZERO`].
+    pub const TEMPERATURE: u8 = 0x22;
+    /// Internal status register.
+    pub const INTERNAL_STATUS: u8 = 0x2A;
+    /// Accelerometer configuration register.
+    pub const ACC_CONF: u8 = 0x40;
+    /// Feature configuration register, through which the feature configuration area
+    /// is read and written in bursts.
+    pub const FEATURES_IN: u8 = 0x5E;
+    /// Accelerometer range register.
+    pub const ACC_RANGE: u8 = 0x41;
+    /// Self-test control register.
+    pub const SELF_TEST: u8 = 0x6D;
+    /// Non-volatile configuration register.
+    pub const NV_CONF: u8 = 0x70;
+    /// Start of the three offset compensation registers (X, Y, Z).
+    pub const OFFSET: u8 = 0x71;
+    /// Power configuration register.
+    pub const PWR_CONF: u8 = 0x7C;
+    /// Power control register.
+    pub const PWR_CTRL: u8 = 0x7D;
+    /// Command register.
+    pub const CMD: u8 = 0x7E;
+
+    /// [`CHIP_ID`] value of the BMA423.
+    pub const CHIP_ID_BMA423: u8 = 0x13;
+    /// [`INTERNAL_STATUS`] bits that hold the initialization message.
+    pub const INTERNAL_STATUS_MESSAGE: u8 = 0x1F;
+    /// [`INTERNAL_STATUS_MESSAGE`] value once the chip has been initialized.
+    pub const INTERNAL_STATUS_INIT_OK: u8 = 0x01;
+    /// [`ACC_CONF`] value for 1600 Hz, normal averaging, and performance mode.
+    pub const ACC_CONF_SELF_TEST: u8 = 0xAC;
+    /// [`ACC_CONF`] value for 100 Hz, normal averaging, and performance mode.
+    pub const ACC_CONF_CALIBRATION: u8 = 0xA8;
+    /// [`ACC_RANGE`] value for ±2g.
+    pub const ACC_RANGE_2G: u8 = 0x00;
+    /// [`ACC_RANGE`] value for ±8g.
+    pub const ACC_RANGE_8G: u8 = 0x02;
+    /// [`ACC_RANGE`] bits that select the range, with each step doubling it from ±2g.
+    pub const ACC_RANGE_MASK: u8 = 0x03;
+    /// [`SELF_TEST`] value to enable the positive high amplitude excitation.
+    pub const SELF_TEST_POSITIVE: u8 = 0x0D;
+    /// [`SELF_TEST`] value to enable the negative high amplitude excitation.
+    pub const SELF_TEST_NEGATIVE: u8 = 0x09;
+    /// [`SELF_TEST`] value to disable the self-test.
+    pub const SELF_TEST_OFF: u8 = 0x00;
+    /// [`NV_CONF`] bit that enables offset compensation.
+    pub const NV_CONF_ACC_OFF_EN: u8 = 0x08;
+    /// [`PWR_CTRL`] bit that enables the accelerometer.
+    pub const PWR_CTRL_ACC_EN: u8 = 0x04;
+    /// [`CMD`] value to perform a soft reset.
+    pub const CMD_SOFT_RESET: u8 = 0xB6;
+    /// Offset in the feature configuration area of the step counter settings.
+    pub const FEATURE_STEP_COUNTER: usize = 0x3A;
+    /// Bit of the second byte of the step counter settings that enables it.
+    pub const STEP_COUNTER_EN: u8 = 0x10;
+    /// The temperature in °C when the [`TEMPERATURE`] register is zero.
+    pub const TEMPERATURE_ZERO: i8 = 23;
+    /// [`TEMPERATURE`] value when no measurement is available.
+    pub const TEMPERATURE_INVALID: u8 = 0x80;
+}
+
+/// Offset compensation values for each axis of the accelerometer.
+///
+/// These are the raw values of the BMA423 offset registers, which have a
+/// resolution of 3.9 mg per LSB.
+/// These are generally obtained using [`AccelerometerRegisters::calibrate_offsets`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Offsets {
+    /// The X axis offset.
+    pub x: i8,
+    /// The Y axis offset.
+    pub y: i8,
+    /// The Z axis offset.
+    pub z: i8,
+}
+impl Offsets {
+    /// NVS key under which the offsets are stored.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    const NVS_KEY: &'static str = "accel_offsets";
+
+    /// Converts into the raw register bytes.
+    fn to_bytes(self) -> [u8; 3] {
+        [self.x, self.y, self.z].map(|v| v.to_le_bytes()[0])
+    }
+
+    /// Converts from the raw register bytes.
+    fn from_bytes(bytes: [u8; 3]) -> Self {
+        let [x, y, z] = bytes.map(|b| i8::from_le_bytes([b]));
+        Self { x, y, z }
+    }
+
+    /// Persists the offsets in an NVS namespace.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    pub fn store<T: crate::svc::nvs::NvsPartitionId>(
+        &self,
+        nvs: &mut crate::svc::nvs::EspNvs<T>,
+    ) -> crate::EspResult<()> {
+        nvs.set_raw(Self::NVS_KEY, &self.to_bytes()).map(|_| ())
+    }
+
+    /// Loads offsets previously persisted with [`Offsets::store`].
+    ///
+    /// Returns `None` if no offsets have been stored in the namespace.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    pub fn load<T: crate::svc::nvs::NvsPartitionId>(
+        nvs: &crate::svc::nvs::EspNvs<T>,
+    ) -> crate::EspResult<Option<Self>> {
+        let mut buf = [0; 3];
+        Ok(nvs
+            .get_raw(Self::NVS_KEY, &mut buf)?
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(Self::from_bytes))
+    }
+}
+
+/// Direct register access to the BMA423 for features not provided by the core
+/// driver crate, namely the built-in self-test, offset compensation, the step counter,
+/// and the temperature sensor.
+///
+/// Because [`AccelerometerDriver`] takes ownership of its I2C driver, the
+/// `embedded-hal-bus` crate should be used to share the bus if these are needed
+/// alongside it.
+/// Alternatively, since `&mut I2C` also implements the I2C trait, a mutable reference
+/// to the I2C driver can be used before passing it on to [`AccelerometerDriver::new`].
+pub struct AccelerometerRegisters<I2C> {
+    /// The I2C driver.
+    i2c: I2C,
+}
+impl<I2C: i2c::I2c> AccelerometerRegisters<I2C> {
+    /// The I2C address of the chip.
+    const ADDRESS: u8 = 0x18;
+    /// The number of samples averaged during offset calibration.
+    const CALIBRATION_SAMPLES: i32 = 64;
+    /// Minimum difference in mg between the positive and negative self-test
+    /// excitations for each axis to pass.
+    const SELF_TEST_THRESHOLDS: [i32; 3] = [400, 800, 400];
+
+    /// Creates register access over an I2C driver.
+    ///
+    /// It is recommended to setup the `i2c_driver` using the [`i2c_driver`](crate::i2c_driver) function
+    /// as this will configure the I2C with the correct settings for the chip.
+    pub fn new(i2c_driver: I2C) -> Self {
+        Self { i2c: i2c_driver }
+    }
+
+    /// Creates register access to a chip that is still running since it was initialized,
+    /// such as before the ESP32 went into deep sleep, so that the step count is kept.
+    ///
+    /// Returns `None` if the chip has been reset since, in which case it needs to be
+    /// initialized again with [`AccelerometerDriver::new`], which applies its
+    /// configuration.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use core::cell::RefCell;
+    /// use embedded_hal_bus::i2c::RefCellDevice;
+    /// use watchy::accelerometer::{bma423, AccelerometerDriver, AccelerometerRegisters};
+    ///
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let i2c_bus = RefCell::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+    /// let accelerometer = match AccelerometerRegisters::resume(RefCellDevice::new(&i2c_bus)) {
+    ///     Ok(Some(registers)) => registers,
+    ///     _ => {
+    ///         AccelerometerDriver::new(
+    ///             pin_sets.accelerometer,
+    ///             RefCellDevice::new(&i2c_bus),
+    ///             bma423::Config::default(),
+    ///         )
+    ///         .unwrap();
+    ///         let mut registers = AccelerometerRegisters::new(RefCellDevice::new(&i2c_bus));
+    ///         registers.set_step_counter(true).unwrap();
+    ///         registers
+    ///     }
+    /// };
+    /// ```
+    pub fn resume(i2c_driver: I2C) -> Result<Option<Self>, AccelerometerError<I2C::Error>> {
+        let mut registers = Self::new(i2c_driver);
+        Ok(registers.is_initialized()?.then_some(registers))
+    }
+
+    /// Returns the I2C driver.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Reads consecutive registers.
+    fn read(&mut self, register: u8, buf: &mut [u8]) -> Result<(), AccelerometerError<I2C::Error>> {
+        self.i2c
+            .write_read(Self::ADDRESS, &[register], buf)
+            .map_err(AccelerometerError::Bus)
+    }
+
+    /// Writes a single register.
+    fn write(&mut self, register: u8, value: u8) -> Result<(), AccelerometerError<I2C::Error>> {
+        self.i2c
+            .write(Self::ADDRESS, &[register, value])
+            .map_err(AccelerometerError::Bus)
+    }
+
+    /// Returns whether the chip has been initialized since it was last reset, such as by
+    /// [`AccelerometerDriver::new`], so that its features are running.
+    ///
+    /// Returns [`AccelerometerError::BadId`] if the chip is not a BMA423.
+    pub fn is_initialized(&mut self) -> Result<bool, AccelerometerError<I2C::Error>> {
+        let mut chip_id = [0];
+        self.read(reg::CHIP_ID, &mut chip_id)?;
+        if chip_id[0] != reg::CHIP_ID_BMA423 {
+            return Err(AccelerometerError::BadId);
+        }
+
+        let mut status = [0];
+        self.read(reg::INTERNAL_STATUS, &mut status)?;
+        Ok(status[0] & reg::INTERNAL_STATUS_MESSAGE == reg::INTERNAL_STATUS_INIT_OK)
+    }
+
+    /// Reads the acceleration of each axis in raw 12-bit LSBs.
+    fn read_acceleration(&mut self) -> Result<[i32; 3], AccelerometerError<I2C::Error>> {
+        let mut buf = [0; 6];
+        self.read(reg::ACC_DATA, &mut buf)?;
+
+        // NOTE: The 12-bit values are left justified in each 16-bit register pair.
+        Ok([0, 2, 4].map(|i| i32::from(i16::from_le_bytes([buf[i], buf[i + 1]]) >> 4)))
+    }
+
+    /// Reads the acceleration of each axis in mg.
+    ///
+    /// The accelerometer must have been enabled, such as by
+    /// [`AccelerometerDriver::new`], and this uses whichever range it was configured
+    /// with.
+    pub fn acceleration(&mut self) -> Result<[i32; 3], AccelerometerError<I2C::Error>> {
+        let mut range = [0];
+        self.read(reg::ACC_RANGE, &mut range)?;
+        let range = range[0] & reg::ACC_RANGE_MASK;
+
+        // NOTE: At ±2g and 12 bits there are 1024 LSB per g, halving as the range doubles.
+        Ok(self
+            .read_acceleration()?
+            .map(|value| ((value * 1000) << range) / 1024))
+    }
+
+    /// Enables the accelerometer in performance mode with the given range and configuration.
+    fn enable(
+        &mut self,
+        range: u8,
+        config: u8,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), AccelerometerError<I2C::Error>> {
+        // Disable advanced power save so that registers can be freely written
+        self.write(reg::PWR_CONF, 0)?;
+        delay.delay_us(450);
+        self.write(reg::ACC_RANGE, range)?;
+        self.write(reg::ACC_CONF, config)?;
+        self.write(reg::PWR_CTRL, reg::PWR_CTRL_ACC_EN)?;
+        delay.delay_ms(2);
+        Ok(())
+    }
+
+    /// Measures the acceleration with positive and then negative self-test excitation.
+    fn self_test_excitations(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<([i32; 3], [i32; 3]), AccelerometerError<I2C::Error>> {
+        self.write(reg::SELF_TEST, reg::SELF_TEST_POSITIVE)?;
+        delay.delay_ms(50);
+        let positive = self.read_acceleration()?;
+
+        self.write(reg::SELF_TEST, reg::SELF_TEST_NEGATIVE)?;
+        delay.delay_ms(50);
+        let negative = self.read_acceleration()?;
+
+        Ok((positive, negative))
+    }
+
+    /// Runs the built-in self-test of the chip.
+    ///
+    /// The chip is excited electrostatically in both directions along each axis,
+    /// and the difference in measured acceleration is checked against the minimum
+    /// from the datasheet.
+    /// If any axes fail, [`AccelerometerError::SelfTestFailed`] is returned
+    /// with the failing axes.
+    ///
+    /// NOTE: This soft resets the chip when done, so it should be run before
+    /// the chip is initialized with [`AccelerometerDriver::new`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut i2c_driver = watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap();
+    /// watchy::accelerometer::AccelerometerRegisters::new(&mut i2c_driver)
+    ///     .self_test()
+    ///     .unwrap();
+    /// ```
+    pub fn self_test(&mut self) -> Result<(), AccelerometerError<I2C::Error>> {
+        let mut delay = crate::delay();
+        self.enable(reg::ACC_RANGE_8G, reg::ACC_CONF_SELF_TEST, &mut delay)?;
+
+        // Make sure that we try to clean up even if the measurement failed
+        let excitations = self.self_test_excitations(&mut delay);
+        self.write(reg::SELF_TEST, reg::SELF_TEST_OFF)?;
+        self.write(reg::CMD, reg::CMD_SOFT_RESET)?;
+        delay.delay_ms(2);
+        let (positive, negative) = excitations?;
+
+        // NOTE: At ±8g and 12 bits there are 256 LSB per g.
+        let failed: EnumSet<Axis> = [Axis::X, Axis::Y, Axis::Z]
+            .into_iter()
+            .zip(positive.into_iter().zip(negative))
+            .zip(Self::SELF_TEST_THRESHOLDS)
+            .filter(|((_, (p, n)), threshold)| (p - n) * 1000 / 256 < *threshold)
+            .map(|((axis, _), _)| axis)
+            .collect();
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(AccelerometerError::SelfTestFailed(failed))
         }
     }
+
+    /// Reads the die temperature of the chip in °C.
+    ///
+    /// The temperature is only measured while the accelerometer is enabled, such as after
+    /// it is initialized with [`AccelerometerDriver::new`], so `None` is returned if
+    /// no measurement is available yet.
+    /// This is a good proxy for the ambient temperature, which can be used to
+    /// [compensate](crate::display::Panel::set_temperature) the display refresh, as the face
+    /// `Runtime` does before each refresh.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use core::cell::RefCell;
+    /// use embedded_hal_bus::i2c::RefCellDevice;
+    /// use watchy::accelerometer::{bma423, AccelerometerDriver, AccelerometerRegisters};
+    ///
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let i2c_bus = RefCell::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+    /// let accelerometer_driver = AccelerometerDriver::new(
+    ///     pin_sets.accelerometer,
+    ///     RefCellDevice::new(&i2c_bus),
+    ///     bma423::Config::default(),
+    /// )
+    /// .unwrap();
+    /// let mut registers = AccelerometerRegisters::new(RefCellDevice::new(&i2c_bus));
+    /// let temperature = registers.temperature().unwrap();
+    /// ```
+    pub fn temperature(&mut self) -> Result<Option<i8>, AccelerometerError<I2C::Error>> {
+        let mut buf = [0];
+        self.read(reg::TEMPERATURE, &mut buf)?;
+        Ok((buf[0] != reg::TEMPERATURE_INVALID)
+            .then(|| i8::from_le_bytes(buf).saturating_add(reg::TEMPERATURE_ZERO)))
+    }
+
+    /// Reads the number of steps counted since the chip was reset.
+    ///
+    /// The step counter must have been [enabled](Self::set_step_counter), otherwise this
+    /// stays at zero.
+    pub fn steps(&mut self) -> Result<u32, AccelerometerError<I2C::Error>> {
+        let mut buf = [0; 4];
+        self.read(reg::STEP_COUNTER, &mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    /// Enables or disables the step counter feature of the chip.
+    ///
+    /// The chip must have been initialized with [`AccelerometerDriver::new`], which
+    /// loads the feature configuration.
+    pub fn set_step_counter(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), AccelerometerError<I2C::Error>> {
+        // Advanced power save must be disabled while the feature configuration is written
+        let mut power = [0];
+        self.read(reg::PWR_CONF, &mut power)?;
+        self.write(reg::PWR_CONF, 0)?;
+        crate::delay().delay_us(450);
+
+        // NOTE: The configuration area is always read and written from its start.
+        let mut features = [0; reg::FEATURE_STEP_COUNTER + 2];
+        self.read(reg::FEATURES_IN, &mut features)?;
+        let settings = &mut features[reg::FEATURE_STEP_COUNTER + 1];
+        if enabled {
+            *settings |= reg::STEP_COUNTER_EN;
+        } else {
+            *settings &= !reg::STEP_COUNTER_EN;
+        }
+        let mut data = [0; reg::FEATURE_STEP_COUNTER + 3];
+        data[0] = reg::FEATURES_IN;
+        data[1..].copy_from_slice(&features);
+        let result = self
+            .i2c
+            .write(Self::ADDRESS, &data)
+            .map_err(AccelerometerError::Bus);
+
+        self.write(reg::PWR_CONF, power[0])?;
+        result
+    }
+
+    /// Reads the current offset compensation values from the chip.
+    pub fn read_offsets(&mut self) -> Result<Offsets, AccelerometerError<I2C::Error>> {
+        let mut buf = [0; 3];
+        self.read(reg::OFFSET, &mut buf)?;
+        Ok(Offsets::from_bytes(buf))
+    }
+
+    /// Writes offset compensation values to the chip and enables compensation.
+    ///
+    /// NOTE: The offset registers are volatile and are cleared when the chip is reset,
+    /// including when it is initialized with [`AccelerometerDriver::new`], so this
+    /// should be called afterwards on every boot.
+    /// Offsets can be persisted in NVS using [`Offsets::store`].
+    pub fn write_offsets(
+        &mut self,
+        offsets: &Offsets,
+    ) -> Result<(), AccelerometerError<I2C::Error>> {
+        for (register, value) in (reg::OFFSET..).zip(offsets.to_bytes()) {
+            self.write(register, value)?;
+        }
+        self.set_offset_compensation(true)
+    }
+
+    /// Enables or disables offset compensation.
+    fn set_offset_compensation(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), AccelerometerError<I2C::Error>> {
+        let mut nv_conf = [0];
+        self.read(reg::NV_CONF, &mut nv_conf)?;
+        let nv_conf = if enabled {
+            nv_conf[0] | reg::NV_CONF_ACC_OFF_EN
+        } else {
+            nv_conf[0] & !reg::NV_CONF_ACC_OFF_EN
+        };
+        self.write(reg::NV_CONF, nv_conf)
+    }
+
+    /// Runs a guided offset calibration.
+    ///
+    /// The watch must be lying flat and motionless (e.g. on a table) while this runs,
+    /// otherwise [`AccelerometerError::NotFlat`] is returned.
+    /// The measured offsets are written to the chip, and are also returned so that
+    /// they can be persisted using [`Offsets::store`] and restored on subsequent
+    /// boots using [`AccelerometerRegisters::write_offsets`].
+    ///
+    /// NOTE: Initializing the chip with [`AccelerometerDriver::new`] clears the offsets,
+    /// so this should be run afterwards.
+    /// The range, sampling rate, and power configuration of the chip are changed while
+    /// measuring, and are then restored.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use core::cell::RefCell;
+    /// use embedded_hal_bus::i2c::RefCellDevice;
+    /// use watchy::accelerometer::{bma423, AccelerometerDriver, AccelerometerRegisters};
+    ///
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let i2c_bus = RefCell::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+    /// let accelerometer_driver = AccelerometerDriver::new(
+    ///     pin_sets.accelerometer,
+    ///     RefCellDevice::new(&i2c_bus),
+    ///     bma423::Config::default(),
+    /// )
+    /// .unwrap();
+    /// let offsets = AccelerometerRegisters::new(RefCellDevice::new(&i2c_bus))
+    ///     .calibrate_offsets()
+    ///     .unwrap();
+    ///
+    /// let nvs_partition = watchy::svc::nvs::EspDefaultNvsPartition::take().unwrap();
+    /// let mut nvs = watchy::svc::nvs::EspNvs::new(nvs_partition, "watchy", true).unwrap();
+    /// offsets.store(&mut nvs).unwrap();
+    /// ```
+    pub fn calibrate_offsets(&mut self) -> Result<Offsets, AccelerometerError<I2C::Error>> {
+        let mut delay = crate::delay();
+
+        // Save the configuration to restore afterwards, noting that the configuration
+        // and range registers are consecutive, as are the power registers
+        let mut conf = [0; 2];
+        self.read(reg::ACC_CONF, &mut conf)?;
+        let mut power = [0; 2];
+        self.read(reg::PWR_CONF, &mut power)?;
+        let mut nv_conf = [0];
+        self.read(reg::NV_CONF, &mut nv_conf)?;
+
+        // Make sure that we try to clean up even if the measurement failed
+        let result = self.measure_offsets(&mut delay).and_then(|offsets| {
+            self.write_offsets(&offsets)?;
+            Ok(offsets)
+        });
+        if result.is_err() {
+            self.write(reg::NV_CONF, nv_conf[0])?;
+        }
+        self.write(reg::ACC_CONF, conf[0])?;
+        self.write(reg::ACC_RANGE, conf[1])?;
+        self.write(reg::PWR_CTRL, power[1])?;
+        delay.delay_ms(2);
+        // Advanced power save is restored last, since it slows down register writes
+        self.write(reg::PWR_CONF, power[0])?;
+
+        result
+    }
+
+    /// Measures the offsets of the axes while the watch is lying flat.
+    fn measure_offsets(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<Offsets, AccelerometerError<I2C::Error>> {
+        // Measure without any existing compensation
+        self.set_offset_compensation(false)?;
+        self.enable(reg::ACC_RANGE_2G, reg::ACC_CONF_CALIBRATION, delay)?;
+        delay.delay_ms(50);
+
+        let mut sums = [0; 3];
+        for _ in 0..Self::CALIBRATION_SAMPLES {
+            for (sum, value) in sums.iter_mut().zip(self.read_acceleration()?) {
+                *sum += value;
+            }
+            delay.delay_ms(10);
+        }
+
+        // NOTE: At ±2g and 12 bits there are 1024 LSB per g.
+        let [x, y, z] = sums.map(|sum| sum * 1000 / 1024 / Self::CALIBRATION_SAMPLES);
+
+        // The Z axis should see 1g, either up or down, and the others nothing.
+        let z_target = if z < 0 { -1000 } else { 1000 };
+        if x.abs() > 200 || y.abs() > 200 || (z - z_target).abs() > 200 {
+            return Err(AccelerometerError::NotFlat);
+        }
+
+        // NOTE: The offsets have a resolution of 3.9 mg per LSB.
+        let offset = |error: i32| -> i8 { (-error * 10).rounded_div(39).try_into().unwrap() };
+        Ok(Offsets {
+            x: offset(x),
+            y: offset(y),
+            z: offset(z - z_target),
+        })
+    }
+}
+impl<I2C: i2c::I2c> MotionSensor for AccelerometerRegisters<I2C> {
+    type Error = AccelerometerError<I2C::Error>;
+
+    fn acceleration(&mut self) -> Result<[i32; 3], Self::Error> {
+        AccelerometerRegisters::acceleration(self)
+    }
+
+    fn steps(&mut self) -> Result<Option<u32>, Self::Error> {
+        AccelerometerRegisters::steps(self).map(Some)
+    }
+
+    fn temperature(&mut self) -> Result<Option<i8>, Self::Error> {
+        AccelerometerRegisters::temperature(self)
+    }
 }
@@ -0,0 +1,495 @@
+//! Several apps sharing the watch, which are switched between using a menu.
+//!
+//! Each app, such as a watch face, stopwatch, or settings screen, implements [`App`], and
+//! the apps are registered with a [`Launcher`].
+//! The launcher shows the running app, and lists the apps in a menu when an app exits,
+//! which by default is when [`Back`](Button::Back) is pressed.
+//! Pressing back in the menu returns to the first app, which is normally the watch face
+//! and is also the app running after a cold boot.
+//!
+//! The launcher is itself a [`WatchFace`], so it is normally run by the face
+//! [`Runtime`](crate::face::Runtime) with the ESP32 sleeping between button presses and
+//! minutes.
+//! Since everything in RAM is lost while sleeping, which app is running and which is
+//! selected in the menu are kept in RTC memory, so that the same app carries on after
+//! waking.
+//! Each app also has a slot of [`SLOT_SIZE`] bytes of RTC memory in which to
+//! [save](App::save) its state before sleeping, and from which it is
+//! [restored](App::restore) after waking.
+//! With the `esp-idf` and `alloc` features, settings that must also survive a loss of
+//! power can be kept in the [NVS namespace](AppContext::nvs) of the app.
+
+use crate::{
+    button::Button,
+    display::{FrameBuffer, RefreshMode},
+    face::{FaceContext, WatchFace},
+    ui::{Entry, Event, Menu, MenuState, MenuValues},
+};
+#[cfg(all(feature = "esp-idf", feature = "alloc"))]
+use crate::{
+    svc::nvs::{EspDefaultNvsPartition, EspNvs, NvsDefault},
+    EspResult,
+};
+use core::{
+    convert::Infallible,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+/// The maximum number of apps that a [`Launcher`] can have.
+pub const MAX_APPS: usize = 8;
+/// The size in bytes of the RTC memory slot of each app.
+pub const SLOT_SIZE: usize = 64;
+
+/// The maximum length of the name of an app, which is limited by NVS namespaces.
+const MAX_NAME_LEN: usize = 15;
+/// Value marking that the storage is valid.
+///
+/// The RTC memory is not initialized on a cold boot, so it could otherwise contain
+/// anything.
+const MAGIC: u32 = 0x4150_5053;
+/// The value of [`Storage::active`] when the menu is open.
+const MENU: u8 = u8::MAX;
+
+/// The contents of the RTC memory.
+struct Storage {
+    /// Set to [`MAGIC`] only when the rest is valid.
+    magic: u32,
+    /// The index of the running app, or [`MENU`].
+    active: u8,
+    /// The index of the app selected in the menu.
+    selected: u8,
+    /// The slot of each app.
+    slots: [[u8; SLOT_SIZE]; MAX_APPS],
+}
+
+/// The storage in RTC memory, which is neither initialized nor cleared when waking
+/// from deep sleep.
+#[cfg_attr(feature = "esp-idf", link_section = ".rtc_noinit")]
+#[cfg_attr(feature = "esp-hal", link_section = ".rtc_slow.persistent")]
+static mut STORAGE: Storage = Storage {
+    magic: 0,
+    active: 0,
+    selected: 0,
+    slots: [[0; SLOT_SIZE]; MAX_APPS],
+};
+
+/// Whether a [`Launcher`] has already been created.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// What the [`Launcher`] should do after an app handles a button press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppAction {
+    /// Draw the app again.
+    Redraw,
+    /// Nothing changed, so the app need not be drawn again.
+    Ignore,
+    /// Close the app and open the menu.
+    Exit,
+}
+
+/// Everything that an [`App`] needs to know, which is passed to each of its methods.
+pub struct AppContext {
+    /// The time, battery status, step count, and wake reason.
+    pub watch: FaceContext,
+    /// The NVS partition, if any, and the namespace of the app.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    nvs: Option<(EspDefaultNvsPartition, &'static str)>,
+}
+impl AppContext {
+    /// Opens the NVS namespace of the app, which has the same name as the app.
+    ///
+    /// Returns `None` if the launcher was not given an NVS
+    /// [partition](Launcher::with_nvs).
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    pub fn nvs(&self) -> EspResult<Option<EspNvs<NvsDefault>>> {
+        self.nvs
+            .as_ref()
+            .map(|(partition, namespace)| EspNvs::new(partition.clone(), namespace, true))
+            .transpose()
+    }
+}
+
+/// An app run by the [`Launcher`].
+///
+/// Like a watch face, an app is created anew each time the ESP32 wakes from deep sleep,
+/// and then [restored](Self::restore) from its slot of RTC memory.
+///
+/// # Example
+/// ```no_run
+/// use core::convert::Infallible;
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     text::{Alignment, Baseline, Text, TextStyleBuilder},
+/// };
+/// use watchy::{
+///     app::{App, AppAction, AppContext, SLOT_SIZE},
+///     button::Button,
+///     display::FrameBuffer,
+///     text::{FontStyle, SMALL},
+/// };
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     count: u32,
+/// }
+/// impl App for Counter {
+///     fn name(&self) -> &'static str {
+///         "counter"
+///     }
+///
+///     fn start(&mut self, _ctx: &mut AppContext) {
+///         self.count = 0;
+///     }
+///
+///     fn restore(&mut self, slot: &[u8; SLOT_SIZE]) {
+///         self.count = u32::from_le_bytes(slot[..4].try_into().unwrap());
+///     }
+///
+///     fn save(&self, slot: &mut [u8; SLOT_SIZE]) {
+///         slot[..4].copy_from_slice(&self.count.to_le_bytes());
+///     }
+///
+///     fn button(&mut self, button: Button, _ctx: &mut AppContext) -> AppAction {
+///         match button {
+///             Button::Back => AppAction::Exit,
+///             _ => {
+///                 self.count += 1;
+///                 AppAction::Redraw
+///             }
+///         }
+///     }
+///
+///     fn draw(&mut self, _ctx: &AppContext, target: &mut FrameBuffer) -> Result<(), Infallible> {
+///         Text::with_text_style(
+///             &self.count.to_string(),
+///             target.bounding_box().center(),
+///             FontStyle::new(&SMALL, BinaryColor::On),
+///             TextStyleBuilder::new()
+///                 .alignment(Alignment::Center)
+///                 .baseline(Baseline::Middle)
+///                 .build(),
+///         )
+///         .draw(target)?;
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait App {
+    /// Returns the name of the app, which is shown in the menu and names its NVS
+    /// namespace.
+    ///
+    /// This must be unique among the apps and no longer than 15 bytes.
+    fn name(&self) -> &'static str;
+
+    /// Called when the app is opened from the menu, which by default does nothing.
+    fn start(&mut self, ctx: &mut AppContext) {
+        let _ = ctx;
+    }
+
+    /// Restores the state of the app from its slot of RTC memory after waking, which by
+    /// default does nothing.
+    ///
+    /// The slot is all zeros after a cold boot.
+    fn restore(&mut self, slot: &[u8; SLOT_SIZE]) {
+        let _ = slot;
+    }
+
+    /// Saves the state of the app into its slot of RTC memory, which by default does
+    /// nothing.
+    ///
+    /// This is called after the app is started and after every button press that it
+    /// handles.
+    fn save(&self, slot: &mut [u8; SLOT_SIZE]) {
+        let _ = slot;
+    }
+
+    /// Handles a button press.
+    ///
+    /// By default, [`Back`](Button::Back) exits the app and the other buttons are
+    /// ignored.
+    fn button(&mut self, button: Button, ctx: &mut AppContext) -> AppAction {
+        let _ = ctx;
+        match button {
+            Button::Back => AppAction::Exit,
+            _ => AppAction::Ignore,
+        }
+    }
+
+    /// Draws the app onto a framebuffer that has been cleared to white.
+    fn draw(&mut self, ctx: &AppContext, target: &mut FrameBuffer) -> Result<(), Infallible>;
+
+    /// Returns how the display should be refreshed after drawing, as for
+    /// [`WatchFace::refresh_mode`].
+    fn refresh_mode(&self, ctx: &FaceContext) -> Option<RefreshMode> {
+        let _ = ctx;
+        None
+    }
+}
+
+/// The values of the menu, which only has actions.
+struct NoValues;
+impl MenuValues for NoValues {
+    fn toggle(&self, _id: u16) -> bool {
+        false
+    }
+
+    fn number(&self, _id: u16) -> i32 {
+        0
+    }
+}
+
+/// Registry of the [`App`]s, which runs one at a time and lists them in a menu.
+///
+/// The launcher keeps its state in RTC memory, so only one instance can exist.
+///
+/// # Example
+/// ```no_run
+/// use core::cell::RefCell;
+/// use embedded_hal_bus::i2c::RefCellDevice;
+/// use watchy::{
+///     accelerometer::{bma423, AccelerometerDriver, AccelerometerRegisters},
+///     app::{App, Launcher},
+///     display::{self, RetainedFrame},
+///     face::Runtime,
+/// };
+///
+/// # struct Face;
+/// # impl App for Face {
+/// #     fn name(&self) -> &'static str { "face" }
+/// #     fn draw(
+/// #         &mut self,
+/// #         _: &watchy::app::AppContext,
+/// #         _: &mut display::FrameBuffer,
+/// #     ) -> Result<(), core::convert::Infallible> { Ok(()) }
+/// # }
+/// # type Stopwatch = Face;
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let nvs = watchy::svc::nvs::EspDefaultNvsPartition::take().unwrap();
+///
+/// let (mut face, mut stopwatch) = (Face, Stopwatch {});
+/// let mut apps: [&mut dyn App; 2] = [&mut face, &mut stopwatch];
+/// let mut launcher = Launcher::new(&mut apps).unwrap().with_nvs(nvs);
+///
+/// let i2c_bus = RefCell::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+///
+/// // The accelerometer keeps counting steps in deep sleep, so it is only set up on a
+/// // cold boot
+/// let accelerometer = match AccelerometerRegisters::resume(RefCellDevice::new(&i2c_bus)) {
+///     Ok(Some(registers)) => registers,
+///     _ => {
+///         AccelerometerDriver::new(
+///             pin_sets.accelerometer,
+///             RefCellDevice::new(&i2c_bus),
+///             bma423::Config::default(),
+///         )
+///         .unwrap();
+///         let mut registers = AccelerometerRegisters::new(RefCellDevice::new(&i2c_bus));
+///         registers.set_step_counter(true).unwrap();
+///         registers
+///     }
+/// };
+/// let runtime = Runtime::new(
+///     display::panel_driver(pin_sets.display, peripherals.spi2).unwrap(),
+///     watchy::rtc::RtcDriver::new(RefCellDevice::new(&i2c_bus)),
+///     watchy::battery::BatteryStatusDriver::new(pin_sets.battery, peripherals.adc1).unwrap(),
+///     accelerometer,
+///     pin_sets.buttons,
+///     RetainedFrame::take().unwrap(),
+/// );
+/// if let Err(error) = runtime.run(&mut launcher) {
+///     panic!("Launcher failed: {error}");
+/// }
+/// ```
+pub struct Launcher<'a> {
+    /// The apps.
+    apps: &'a mut [&'a mut dyn App],
+    /// The state in RTC memory.
+    storage: &'static mut Storage,
+    /// The framebuffer into which the running app is drawn.
+    canvas: FrameBuffer,
+    /// The NVS partition in which the apps have their namespaces.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    nvs: Option<EspDefaultNvsPartition>,
+}
+impl<'a> Launcher<'a> {
+    /// The title of the menu.
+    const TITLE: &'static str = "Apps";
+
+    /// Creates the launcher, restoring the running app and the state of every app from
+    /// RTC memory, which returns [`None`] if a launcher was already created.
+    ///
+    /// # Panics
+    /// This panics if there are more than [`MAX_APPS`] apps, or if the names of the apps
+    /// are not unique or are too long.
+    pub fn new(apps: &'a mut [&'a mut dyn App]) -> Option<Self> {
+        assert!(apps.len() <= MAX_APPS, "too many apps");
+        for (index, app) in apps.iter().enumerate() {
+            let name = app.name();
+            assert!(name.len() <= MAX_NAME_LEN, "app name `{name}` is too long");
+            assert!(
+                apps[..index].iter().all(|other| other.name() != name),
+                "app name `{name}` is not unique"
+            );
+        }
+        if TAKEN.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
+        // SAFETY: This is only reached once, so this is the only reference to the
+        // storage, and every bit pattern is valid for it.
+        let storage = unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) };
+        if storage.magic != MAGIC {
+            // The first app runs after a cold boot
+            storage.active = 0;
+            storage.selected = 0;
+            storage.slots = [[0; SLOT_SIZE]; MAX_APPS];
+            storage.magic = MAGIC;
+        }
+        // NOTE: The apps may have changed since the storage was written.
+        if usize::from(storage.active) >= apps.len() {
+            storage.active = MENU;
+        }
+        for (app, slot) in apps.iter_mut().zip(&storage.slots) {
+            app.restore(slot);
+        }
+
+        Some(Self {
+            apps,
+            storage,
+            canvas: FrameBuffer::new(),
+            #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+            nvs: None,
+        })
+    }
+
+    /// Sets the NVS partition in which each app has a namespace.
+    #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+    pub fn with_nvs(mut self, partition: EspDefaultNvsPartition) -> Self {
+        self.nvs = Some(partition);
+        self
+    }
+
+    /// Returns the index of the running app, or `None` if the menu is open.
+    pub fn active(&self) -> Option<usize> {
+        (self.storage.active != MENU).then_some(self.storage.active.into())
+    }
+
+    /// Opens an app by its index, closing the menu or any other running app.
+    ///
+    /// # Panics
+    /// This panics if there is no app with the index.
+    pub fn open(&mut self, index: usize, watch: &FaceContext) {
+        assert!(index < self.apps.len(), "no app with index {index}");
+        // NOTE: There are at most `MAX_APPS` apps, so the index fits.
+        self.storage.active = index as u8;
+        self.storage.selected = index as u8;
+        let mut ctx = self.context(index, watch);
+        self.apps[index].start(&mut ctx);
+        self.save(index);
+    }
+
+    /// Creates the context for an app.
+    #[cfg_attr(
+        not(all(feature = "esp-idf", feature = "alloc")),
+        allow(unused_variables)
+    )]
+    fn context(&self, index: usize, watch: &FaceContext) -> AppContext {
+        AppContext {
+            watch: *watch,
+            #[cfg(all(feature = "esp-idf", feature = "alloc"))]
+            nvs: self
+                .nvs
+                .clone()
+                .map(|partition| (partition, self.apps[index].name())),
+        }
+    }
+
+    /// Saves the state of an app into its slot.
+    fn save(&mut self, index: usize) {
+        self.apps[index].save(&mut self.storage.slots[index]);
+    }
+
+    /// Returns the entries of the menu, of which only those of the apps are used.
+    fn entries(&self) -> [Entry<'static>; MAX_APPS] {
+        let mut entries = [Entry::action(0, ""); MAX_APPS];
+        for ((index, app), entry) in self.apps.iter().enumerate().zip(&mut entries) {
+            *entry = Entry::action(index as u16, app.name());
+        }
+        entries
+    }
+
+    /// Handles a button press in the menu, returning whether it changed.
+    fn menu_button(&mut self, button: Button, watch: &FaceContext) -> bool {
+        let entries = self.entries();
+        let menu = Menu::new(Self::TITLE, &entries[..self.apps.len()]);
+        let mut state = MenuState::new(&menu);
+        state.select(self.storage.selected.into());
+
+        let response = state.press(button, &NoValues);
+        self.storage.selected = state.selected_index() as u8;
+        match response.event {
+            Some(Event::Selected(index)) => {
+                self.open(index.into(), watch);
+                true
+            }
+            Some(Event::Closed) if !self.apps.is_empty() => {
+                self.open(0, watch);
+                true
+            }
+            _ => response.dirty.is_some(),
+        }
+    }
+}
+impl WatchFace for Launcher<'_> {
+    fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let Some(index) = self.active() else {
+            let entries = self.entries();
+            let menu = Menu::new(Self::TITLE, &entries[..self.apps.len()]);
+            let mut state = MenuState::new(&menu);
+            state.select(self.storage.selected.into());
+            return state.draw(&NoValues, target);
+        };
+
+        // NOTE: Apps draw into a framebuffer since generic targets are not object safe.
+        let app_ctx = self.context(index, ctx);
+        self.canvas = FrameBuffer::new();
+        self.apps[index]
+            .draw(&app_ctx, &mut self.canvas)
+            .unwrap_or_else(|never| match never {});
+        let area = self.canvas.bounding_box();
+        target.fill_contiguous(
+            &area,
+            area.points()
+                .map(|point| self.canvas.pixel(point).unwrap_or(BinaryColor::Off)),
+        )
+    }
+
+    fn button(&mut self, button: Button, ctx: &FaceContext) -> bool {
+        let Some(index) = self.active() else {
+            return self.menu_button(button, ctx);
+        };
+
+        let mut app_ctx = self.context(index, ctx);
+        let action = self.apps[index].button(button, &mut app_ctx);
+        self.save(index);
+        match action {
+            AppAction::Redraw => true,
+            AppAction::Ignore => false,
+            AppAction::Exit => {
+                self.storage.active = MENU;
+                true
+            }
+        }
+    }
+
+    fn refresh_mode(&self, ctx: &FaceContext) -> Option<RefreshMode> {
+        self.active()
+            .and_then(|index| self.apps[index].refresh_mode(ctx))
+    }
+}
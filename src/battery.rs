@@ -1,31 +1,55 @@
 //! Battery status using the ADC.
 
-use crate::hal::{
-    adc::{attenuation, oneshot, ADC1},
-    gpio,
+mod status;
+
+pub use status::BatteryStatus;
+
+#[cfg(feature = "board-auto")]
+use crate::hal::gpio;
+#[cfg(not(feature = "sim"))]
+use crate::pins;
+#[cfg(feature = "esp-idf")]
+use crate::{
+    hal::adc::{attenuation, oneshot, ADC1},
+    EspResult,
 };
-use crate::{pins, EspResult};
+#[cfg(feature = "esp-hal")]
+use esp_hal::{analog::adc, peripherals::ADC1};
+#[cfg(feature = "sim")]
+use {crate::sim, core::marker::PhantomData};
 
+use crate::traits::BatteryMonitor;
 use rounded_div::RoundedDiv;
 
-/// Represents a battery status.
-pub struct BatteryStatus(u32);
-impl BatteryStatus {
-    /// Returns the battery voltage in mV.
-    pub fn voltage(&self) -> u32 {
-        self.0
+/// The ADC channel configuration used to sample the battery voltage.
+#[cfg(feature = "esp-idf")]
+pub(crate) fn channel_config() -> oneshot::config::AdcChannelConfig {
+    oneshot::config::AdcChannelConfig {
+        attenuation: attenuation::DB_11,
+        resolution: oneshot::config::Resolution::Resolution12Bit,
+        calibration: true,
     }
+}
 
-    /// Returns the charge percentage of the battery.
-    pub fn percentage(&self) -> u8 {
-        // NOTE: The percentage calculation is linear from 3400 mV to 4200 mV
-        self.0
-            .saturating_sub(3400)
-            .saturating_mul(100)
-            .rounded_div(800)
-            .min(100)
-            .try_into()
-            .unwrap()
+/// The ADC channel driver for whichever pin monitors the battery voltage.
+#[cfg(feature = "board-auto")]
+enum ChannelDriver<'d> {
+    /// The channel driver for v1.0 boards.
+    Gpio33(oneshot::AdcChannelDriver<'d, gpio::Gpio33, oneshot::AdcDriver<'d, ADC1>>),
+    /// The channel driver for v2.0 boards.
+    Gpio34(oneshot::AdcChannelDriver<'d, gpio::Gpio34, oneshot::AdcDriver<'d, ADC1>>),
+    /// The channel driver for v1.5 boards.
+    Gpio35(oneshot::AdcChannelDriver<'d, gpio::Gpio35, oneshot::AdcDriver<'d, ADC1>>),
+}
+#[cfg(feature = "board-auto")]
+impl ChannelDriver<'_> {
+    /// Samples the ADC, returning the voltage in mV.
+    fn read(&mut self) -> EspResult<u16> {
+        match self {
+            Self::Gpio33(driver) => driver.read(),
+            Self::Gpio34(driver) => driver.read(),
+            Self::Gpio35(driver) => driver.read(),
+        }
     }
 }
 
@@ -36,9 +60,41 @@ impl BatteryStatus {
 /// peripheral on the ESP32.
 pub struct BatteryStatusDriver<'d> {
     /// The ADC channel driver struct, which owns the [`AdcDriver`].
-    channel_driver: oneshot::AdcChannelDriver<'d, gpio::Gpio34, oneshot::AdcDriver<'d, ADC1>>,
+    #[cfg(all(feature = "esp-idf", not(feature = "board-auto")))]
+    channel_driver: oneshot::AdcChannelDriver<'d, pins::BatteryAdc, oneshot::AdcDriver<'d, ADC1>>,
+    /// The ADC channel driver for the pin of the detected board revision.
+    #[cfg(feature = "board-auto")]
+    channel_driver: ChannelDriver<'d>,
+    /// The ADC driver.
+    #[cfg(feature = "esp-hal")]
+    adc: adc::Adc<'d, ADC1<'d>, esp_hal::Blocking>,
+    /// The ADC pin, calibrated so that samples are in mV.
+    #[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+    adc_pin: adc::AdcPin<pins::BatteryAdc, ADC1<'d>, adc::AdcCalCurve<ADC1<'d>>>,
+    /// The uncalibrated ADC pin.
+    #[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+    adc_pin: adc::AdcPin<pins::BatteryAdc, ADC1<'d>>,
+    /// The simulated watch.
+    #[cfg(feature = "sim")]
+    watch: sim::Watch,
+    /// The lifetime of the peripherals, which are not used by the simulation.
+    #[cfg(feature = "sim")]
+    _peripherals: PhantomData<&'d ()>,
 }
 impl<'d> BatteryStatusDriver<'d> {
+    /// Inverse of the voltage divider ratio between the battery and the ADC pin,
+    /// as a fraction.
+    #[cfg(not(feature = "board-v3"))]
+    const VOLTAGE_DIVIDER: (u32, u32) = (2, 1);
+    /// Inverse of the voltage divider ratio between the battery and the ADC pin,
+    /// as a fraction.
+    #[cfg(feature = "board-v3")]
+    const VOLTAGE_DIVIDER: (u32, u32) = (460, 360);
+    /// Nominal voltage in mV on the ADC pin at the full scale of the 12-bit ADC with
+    /// 11 dB attenuation.
+    #[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+    const ADC_FULL_SCALE: u32 = 3900;
+
     /// Setup a new battery status driver.
     ///
     /// # Example
@@ -48,27 +104,125 @@ impl<'d> BatteryStatusDriver<'d> {
     /// let mut battery_staus_driver =
     ///     watchy::battery::BatteryStatusDriver::new(pin_sets.battery, peripherals.adc1).unwrap();
     /// ```
+    #[cfg(feature = "esp-idf")]
     pub fn new<P: crate::hal::peripheral::Peripheral<P = ADC1> + 'd>(
         battery_pins: pins::Battery,
         adc: P,
     ) -> EspResult<Self> {
         let driver = oneshot::AdcDriver::new(adc)?;
 
-        let channel_driver = oneshot::AdcChannelDriver::new(
-            driver,
-            battery_pins.adc,
-            &oneshot::config::AdcChannelConfig {
-                attenuation: attenuation::DB_11,
-                resolution: oneshot::config::Resolution::Resolution12Bit,
-                calibration: true,
-            },
-        )?;
+        #[cfg(not(feature = "board-auto"))]
+        let channel_driver =
+            oneshot::AdcChannelDriver::new(driver, battery_pins.adc, &channel_config())?;
+        #[cfg(feature = "board-auto")]
+        let channel_driver =
+            match battery_pins.adc {
+                pins::BatteryAdc::Gpio33(pin) => ChannelDriver::Gpio33(
+                    oneshot::AdcChannelDriver::new(driver, pin, &channel_config())?,
+                ),
+                pins::BatteryAdc::Gpio34(pin) => ChannelDriver::Gpio34(
+                    oneshot::AdcChannelDriver::new(driver, pin, &channel_config())?,
+                ),
+                pins::BatteryAdc::Gpio35(pin) => ChannelDriver::Gpio35(
+                    oneshot::AdcChannelDriver::new(driver, pin, &channel_config())?,
+                ),
+            };
 
         Ok(Self { channel_driver })
     }
 
+    /// Setup a new battery status driver.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+    /// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+    /// let mut battery_staus_driver =
+    ///     watchy::battery::BatteryStatusDriver::new(pin_sets.battery, peripherals.ADC1);
+    /// ```
+    #[cfg(feature = "esp-hal")]
+    pub fn new(battery_pins: pins::Battery, adc: ADC1<'d>) -> Self {
+        let mut config = adc::AdcConfig::new();
+        #[cfg(feature = "board-v3")]
+        let adc_pin = config.enable_pin_with_cal(battery_pins.adc, adc::Attenuation::_11dB);
+        // NOTE: `esp-hal` does not support calibrating the ADC of the ESP32.
+        #[cfg(not(feature = "board-v3"))]
+        let adc_pin = config.enable_pin(battery_pins.adc, adc::Attenuation::_11dB);
+
+        Self {
+            adc: adc::Adc::new(adc, config),
+            adc_pin,
+        }
+    }
+
+    /// Setup a new battery status driver for a simulated watch.
+    ///
+    /// # Example
+    /// ```
+    /// let watch = watchy::sim::Watch::new();
+    /// watch.set_battery_voltage(3800);
+    /// let mut battery_status_driver = watchy::battery::BatteryStatusDriver::new(&watch);
+    /// assert_eq!(battery_status_driver.status().percentage(), 50);
+    /// ```
+    #[cfg(feature = "sim")]
+    pub fn new(watch: &sim::Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+            _peripherals: PhantomData,
+        }
+    }
+
     /// Retrieve the battery status by sampling the ADC.
+    #[cfg(feature = "esp-idf")]
     pub fn status(&mut self) -> EspResult<BatteryStatus> {
-        Ok(BatteryStatus(u32::from(self.channel_driver.read()? * 2)))
+        Ok(Self::from_adc_voltage(self.channel_driver.read()?))
+    }
+
+    /// Retrieve the battery status by sampling the ADC.
+    ///
+    /// NOTE: Except on v3.0 boards, the ADC is not calibrated, so the voltage is only
+    /// approximate.
+    #[cfg(feature = "esp-hal")]
+    pub fn status(&mut self) -> BatteryStatus {
+        let voltage = self.adc.read_blocking(&mut self.adc_pin);
+        #[cfg(not(feature = "board-v3"))]
+        let voltage = (u32::from(voltage) * Self::ADC_FULL_SCALE)
+            .rounded_div(4095)
+            .try_into()
+            .unwrap_or(u16::MAX);
+        Self::from_adc_voltage(voltage)
+    }
+
+    /// Retrieve the battery status by sampling the simulated ADC.
+    ///
+    /// NOTE: The voltage goes through the divider and ADC so that it is rounded as on
+    /// the hardware.
+    #[cfg(feature = "sim")]
+    pub fn status(&mut self) -> BatteryStatus {
+        let (numerator, denominator) = Self::VOLTAGE_DIVIDER;
+        let voltage = (self.watch.battery_voltage() * denominator).rounded_div(numerator);
+        Self::from_adc_voltage(voltage.try_into().unwrap_or(u16::MAX))
+    }
+
+    /// Converts the voltage on the ADC pin in mV into the battery status.
+    fn from_adc_voltage(voltage: u16) -> BatteryStatus {
+        let (numerator, denominator) = Self::VOLTAGE_DIVIDER;
+        BatteryStatus::from_voltage((u32::from(voltage) * numerator).rounded_div(denominator))
+    }
+}
+#[cfg(feature = "esp-idf")]
+impl BatteryMonitor for BatteryStatusDriver<'_> {
+    type Error = crate::sys::EspError;
+
+    fn status(&mut self) -> Result<BatteryStatus, Self::Error> {
+        BatteryStatusDriver::status(self)
+    }
+}
+#[cfg(not(feature = "esp-idf"))]
+impl BatteryMonitor for BatteryStatusDriver<'_> {
+    type Error = core::convert::Infallible;
+
+    fn status(&mut self) -> Result<BatteryStatus, Self::Error> {
+        Ok(BatteryStatusDriver::status(self))
     }
 }
@@ -0,0 +1,24 @@
+//! Battery status, which does not depend on the hardware.
+
+/// Represents a battery status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatteryStatus(u32);
+impl BatteryStatus {
+    /// Creates the status for a battery voltage in mV.
+    pub fn from_voltage(voltage: u32) -> Self {
+        Self(voltage)
+    }
+
+    /// Returns the battery voltage in mV.
+    pub fn voltage(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns the charge percentage of the battery.
+    pub fn percentage(&self) -> u8 {
+        // NOTE: The percentage calculation is linear from 3400 mV to 4200 mV, rounded to
+        // the nearest percent.
+        let above_empty = self.0.saturating_sub(3400).min(800);
+        ((above_empty * 100 + 400) / 800).try_into().unwrap()
+    }
+}
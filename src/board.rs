@@ -0,0 +1,126 @@
+//! Identification of the Watchy board revision.
+//!
+//! The board revision is normally selected at compile time using one of the
+//! `board-*` features.
+//! However, the v1.0, v1.5, and v2.0 revisions all use the ESP32, so a single firmware
+//! can support all of them by instead enabling the `board-auto` feature and identifying
+//! the revision at boot using [`BoardRevision::detect`].
+//! In this case, the revision is then used to create the pin sets with
+//! [`Sets::for_revision`](crate::pins::Sets::for_revision), and the
+//! [`RtcDriver`](crate::rtc::RtcDriver) selects the correct RTC chip on its own.
+
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
+use crate::{
+    hal::{
+        adc::{oneshot, ADC1},
+        gpio, i2c, peripheral,
+    },
+    rtc,
+    sys::EspError,
+};
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
+use thiserror::Error;
+
+/// A revision of the Watchy board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardRevision {
+    /// Revision v1.0, which has a DS3231 RTC.
+    V1_0,
+    /// Revision v1.5, which has a PCF8563 RTC and the battery monitored on GPIO35.
+    V1_5,
+    /// Revision v2.0, which has a PCF8563 RTC and the battery monitored on GPIO34.
+    V2_0,
+    /// Revision v3.0, which uses the ESP32-S3 and its internal RTC.
+    V3_0,
+}
+
+/// Error for board revision detection problems.
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
+#[derive(Error, Debug)]
+pub enum DetectionError {
+    /// An ESP peripheral error.
+    #[error("Esp error: {0}")]
+    Esp(#[cfg_attr(feature = "std", source)] EspError),
+    /// Neither of the known RTC chips responded on the I2C bus.
+    #[error("No RTC chip found")]
+    NoRtc,
+}
+// NOTE: This is implemented manually because the source error only implements
+// the error trait when using `std`.
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
+impl From<EspError> for DetectionError {
+    fn from(value: EspError) -> Self {
+        Self::Esp(value)
+    }
+}
+
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
+impl BoardRevision {
+    /// Range of voltages in mV on GPIO35 that indicate that it is monitoring the
+    /// battery, as on v1.5 boards.
+    ///
+    /// On v2.0 boards this is instead button 3, which reads close to 0 V when released
+    /// and saturates the ADC when pressed.
+    const BATTERY_ADC_RANGE: core::ops::RangeInclusive<u16> = 1400..=2600;
+
+    /// Identifies the board revision by probing the hardware.
+    ///
+    /// The RTC chips are first probed on the I2C bus, since only v1.0 boards have a DS3231.
+    /// The v1.5 and v2.0 boards both have a PCF8563, and are then distinguished by sampling
+    /// GPIO35, which monitors the battery voltage on v1.5 boards.
+    ///
+    /// The peripherals are only borrowed, and are free to be used normally afterwards.
+    /// This can only distinguish between revisions that use the ESP32, since v3.0
+    /// requires different firmware.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use watchy::board::BoardRevision;
+    /// let mut peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let revision = BoardRevision::detect(
+    ///     &mut peripherals.pins,
+    ///     &mut peripherals.i2c0,
+    ///     &mut peripherals.adc1,
+    /// )
+    /// .unwrap();
+    /// let pin_sets = watchy::pins::Sets::for_revision(peripherals.pins, revision);
+    /// ```
+    pub fn detect<I2C: i2c::I2c>(
+        pins: &mut gpio::Pins,
+        i2c_periph: impl peripheral::Peripheral<P = I2C>,
+        adc: impl peripheral::Peripheral<P = ADC1>,
+    ) -> Result<Self, DetectionError> {
+        // Probe for the RTC chips
+        {
+            let mut i2c_driver = i2c::I2cDriver::new(
+                i2c_periph,
+                &mut pins.gpio21,
+                &mut pins.gpio22,
+                &crate::i2c_config(),
+            )?;
+
+            if rtc::is_present(&mut i2c_driver, rtc::Ds3231::<i2c::I2cDriver>::ADDRESS) {
+                return Ok(Self::V1_0);
+            }
+            if !rtc::is_present(&mut i2c_driver, rtc::Pcf8563::<i2c::I2cDriver>::ADDRESS) {
+                return Err(DetectionError::NoRtc);
+            }
+        }
+
+        // Sample GPIO35 to distinguish between v1.5 and v2.0
+        let adc_driver = oneshot::AdcDriver::new(adc)?;
+        let mut channel_driver = oneshot::AdcChannelDriver::new(
+            &adc_driver,
+            &mut pins.gpio35,
+            &crate::battery::channel_config(),
+        )?;
+
+        Ok(
+            if Self::BATTERY_ADC_RANGE.contains(&channel_driver.read()?) {
+                Self::V1_5
+            } else {
+                Self::V2_0
+            },
+        )
+    }
+}
@@ -1,19 +1,73 @@
 //! Driver for capturing Watchy button presses.
 
-use crate::{hal::gpio, EspResult};
+#[cfg(feature = "sim")]
+use crate::sim;
+use crate::traits;
+#[cfg(feature = "esp-idf")]
+use crate::{hal::gpio, sys, EspResult};
+#[cfg(feature = "esp-hal")]
+use {core::marker::PhantomData, esp_hal::gpio};
+
+/// Whether the buttons pull their pins low when pressed, which depends on the board revision.
+#[cfg(not(feature = "sim"))]
+const ACTIVE_LOW: bool = cfg!(feature = "board-v3");
+
+/// One of the four buttons, named for its conventional use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Button {
+    /// Button 1 at the bottom left, which conventionally opens the menu or selects.
+    Menu,
+    /// Button 2 at the top left, which conventionally goes back.
+    Back,
+    /// Button 3 at the top right, which conventionally moves up.
+    Up,
+    /// Button 4 at the bottom right, which conventionally moves down.
+    Down,
+}
 
 /// Trait denoting GPIO pins connected to buttons.
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
 pub trait ButtonPin: gpio::InputPin {}
+/// Trait denoting GPIO pins connected to buttons.
+#[cfg(all(feature = "esp-idf", feature = "board-v3"))]
+pub trait ButtonPin: gpio::InputPin + gpio::OutputPin {}
+/// Trait denoting GPIO pins connected to buttons.
+#[cfg(feature = "esp-hal")]
+pub trait ButtonPin: gpio::InputPin {}
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
 impl ButtonPin for gpio::Gpio26 {}
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
 impl ButtonPin for gpio::Gpio25 {}
-impl ButtonPin for gpio::Gpio35 {}
+#[cfg(all(feature = "esp-idf", not(feature = "board-v3")))]
 impl ButtonPin for gpio::Gpio4 {}
+#[cfg(all(feature = "esp-idf", feature = "board-v3"))]
+impl ButtonPin for gpio::Gpio7 {}
+#[cfg(all(feature = "esp-idf", feature = "board-v3"))]
+impl ButtonPin for gpio::Gpio6 {}
+#[cfg(all(feature = "esp-idf", feature = "board-v3"))]
+impl ButtonPin for gpio::Gpio8 {}
+#[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+impl ButtonPin for esp_hal::peripherals::GPIO26<'static> {}
+#[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+impl ButtonPin for esp_hal::peripherals::GPIO25<'static> {}
+#[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+impl ButtonPin for esp_hal::peripherals::GPIO4<'static> {}
+#[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+impl ButtonPin for esp_hal::peripherals::GPIO7<'static> {}
+#[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+impl ButtonPin for esp_hal::peripherals::GPIO6<'static> {}
+#[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+impl ButtonPin for esp_hal::peripherals::GPIO8<'static> {}
+#[cfg(not(feature = "sim"))]
+impl ButtonPin for crate::pins::Button3 {}
 
 /// Driver for capturing button presses.
+#[cfg(feature = "esp-idf")]
 pub struct ButtonDriver<'d, P: ButtonPin> {
     /// The driver for the button pin.
     pin_driver: gpio::PinDriver<'d, P, gpio::Input>,
 }
+#[cfg(feature = "esp-idf")]
 impl<'d, P: ButtonPin> ButtonDriver<'d, P> {
     /// Creates a new button driver for a particular button.
     ///
@@ -25,11 +79,15 @@ impl<'d, P: ButtonPin> ButtonDriver<'d, P> {
     /// ```
     pub fn new(pin: P) -> EspResult<Self> {
         // NOTE: Pins should default to floating pull-up, since the Watchy provides external
-        // pulldown resistors. This cannot even be set for GPIO 35.
+        // pulldown resistors. This cannot even be set for GPIO 35 on v2.0 boards.
+        #[allow(unused_mut)]
+        let mut pin_driver = gpio::PinDriver::input(pin)?;
+
+        // The v3 buttons instead connect to ground when pressed.
+        #[cfg(feature = "board-v3")]
+        pin_driver.set_pull(gpio::Pull::Up)?;
 
-        Ok(Self {
-            pin_driver: gpio::PinDriver::input(pin)?,
-        })
+        Ok(Self { pin_driver })
     }
 
     /// Converts this into a regular [`PinDriver`](gpio::PinDriver).
@@ -39,34 +97,237 @@ impl<'d, P: ButtonPin> ButtonDriver<'d, P> {
 
     /// Returns whether the button is currently pressed.
     pub fn is_pressed(&self) -> bool {
-        self.pin_driver.is_high()
+        self.pin_driver.is_high() != ACTIVE_LOW
     }
 
     /// Asynchronously waits for the button to be pressed.
     ///
     /// If the button is already pressed, it waits for the next press.
     pub async fn wait_for_press(&mut self) -> EspResult<()> {
-        self.pin_driver.wait_for_rising_edge().await
+        if ACTIVE_LOW {
+            self.pin_driver.wait_for_falling_edge().await
+        } else {
+            self.pin_driver.wait_for_rising_edge().await
+        }
     }
 
     /// Asynchronously waits for the button to be in the pressed state.
     ///
     /// If the button is already pressed, this will instantly return.
     pub async fn wait_for_pressed(&mut self) -> EspResult<()> {
-        self.pin_driver.wait_for_high().await
+        if ACTIVE_LOW {
+            self.pin_driver.wait_for_low().await
+        } else {
+            self.pin_driver.wait_for_high().await
+        }
     }
 
     /// Asynchronously waits for the button to be released.
     ///
     /// If the button is already released, it waits for the next release.
     pub async fn wait_for_release(&mut self) -> EspResult<()> {
-        self.pin_driver.wait_for_falling_edge().await
+        if ACTIVE_LOW {
+            self.pin_driver.wait_for_rising_edge().await
+        } else {
+            self.pin_driver.wait_for_falling_edge().await
+        }
     }
 
     /// Asynchronously waits for the button to be in the released state.
     ///
     /// If the button is already released, this will instantly return.
     pub async fn wait_for_released(&mut self) -> EspResult<()> {
-        self.pin_driver.wait_for_low().await
+        if ACTIVE_LOW {
+            self.pin_driver.wait_for_high().await
+        } else {
+            self.pin_driver.wait_for_low().await
+        }
+    }
+}
+
+#[cfg(feature = "esp-idf")]
+impl<P: ButtonPin> traits::Button for ButtonDriver<'_, P> {
+    fn is_pressed(&self) -> bool {
+        ButtonDriver::is_pressed(self)
+    }
+}
+
+/// Enables waking the ESP32 from deep sleep when any of the buttons is pressed.
+///
+/// The buttons are given by the GPIO numbers of their pins, which are all RTC GPIOs.
+#[cfg(feature = "esp-idf")]
+pub(crate) fn enable_wakeup(button_pins: [i32; 4]) -> EspResult<()> {
+    let mask = button_pins.iter().fold(0, |mask, pin| mask | 1 << pin);
+    #[cfg(not(feature = "board-v3"))]
+    {
+        // SAFETY: The pins are all RTC GPIOs.
+        sys::esp!(unsafe {
+            sys::esp_sleep_enable_ext1_wakeup(
+                mask,
+                sys::esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_HIGH,
+            )
+        })?;
+    }
+    // The v3 buttons instead connect to ground when pressed, so the pull-ups must
+    // stay powered while sleeping.
+    #[cfg(feature = "board-v3")]
+    {
+        // SAFETY: The pins are all RTC GPIOs.
+        sys::esp!(unsafe {
+            sys::esp_sleep_enable_ext1_wakeup(
+                mask,
+                sys::esp_sleep_ext1_wakeup_mode_t_ESP_EXT1_WAKEUP_ANY_LOW,
+            )
+        })?;
+        // SAFETY: This has no preconditions.
+        sys::esp!(unsafe {
+            sys::esp_sleep_pd_config(
+                sys::esp_sleep_pd_domain_t_ESP_PD_DOMAIN_RTC_PERIPH,
+                sys::esp_sleep_pd_option_t_ESP_PD_OPTION_ON,
+            )
+        })?;
+        for pin in button_pins {
+            // SAFETY: The pins are all RTC GPIOs.
+            sys::esp!(unsafe { sys::rtc_gpio_pullup_en(pin) })?;
+            // SAFETY: The pins are all RTC GPIOs.
+            sys::esp!(unsafe { sys::rtc_gpio_pulldown_dis(pin) })?;
+        }
+    }
+    Ok(())
+}
+
+/// Driver for capturing button presses.
+#[cfg(feature = "esp-hal")]
+pub struct ButtonDriver<'d, P: ButtonPin> {
+    /// The driver for the button pin.
+    input: gpio::Input<'d>,
+    /// The button pin type, which the driver no longer tracks.
+    pin: PhantomData<P>,
+}
+#[cfg(feature = "esp-hal")]
+impl<'d, P: ButtonPin + 'd> ButtonDriver<'d, P> {
+    /// Creates a new button driver for a particular button.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+    /// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+    /// let button_driver = watchy::button::ButtonDriver::new(pin_sets.buttons.btn_1);
+    /// ```
+    pub fn new(pin: P) -> Self {
+        // NOTE: The Watchy provides external pulldown resistors, except on v3.0 boards,
+        // whose buttons instead connect to ground when pressed.
+        let pull = if ACTIVE_LOW {
+            gpio::Pull::Up
+        } else {
+            gpio::Pull::None
+        };
+
+        Self {
+            input: gpio::Input::new(pin, gpio::InputConfig::default().with_pull(pull)),
+            pin: PhantomData,
+        }
+    }
+
+    /// Converts this into a regular [`Input`](gpio::Input).
+    pub fn into_input(self) -> gpio::Input<'d> {
+        self.input
+    }
+
+    /// Returns whether the button is currently pressed.
+    pub fn is_pressed(&self) -> bool {
+        self.input.is_high() != ACTIVE_LOW
+    }
+
+    /// Asynchronously waits for the button to be pressed.
+    ///
+    /// If the button is already pressed, it waits for the next press.
+    pub async fn wait_for_press(&mut self) {
+        if ACTIVE_LOW {
+            self.input.wait_for_falling_edge().await
+        } else {
+            self.input.wait_for_rising_edge().await
+        }
+    }
+
+    /// Asynchronously waits for the button to be in the pressed state.
+    ///
+    /// If the button is already pressed, this will instantly return.
+    pub async fn wait_for_pressed(&mut self) {
+        if ACTIVE_LOW {
+            self.input.wait_for_low().await
+        } else {
+            self.input.wait_for_high().await
+        }
+    }
+
+    /// Asynchronously waits for the button to be released.
+    ///
+    /// If the button is already released, it waits for the next release.
+    pub async fn wait_for_release(&mut self) {
+        if ACTIVE_LOW {
+            self.input.wait_for_rising_edge().await
+        } else {
+            self.input.wait_for_falling_edge().await
+        }
+    }
+
+    /// Asynchronously waits for the button to be in the released state.
+    ///
+    /// If the button is already released, this will instantly return.
+    pub async fn wait_for_released(&mut self) {
+        if ACTIVE_LOW {
+            self.input.wait_for_high().await
+        } else {
+            self.input.wait_for_low().await
+        }
+    }
+}
+
+#[cfg(feature = "esp-hal")]
+impl<'d, P: ButtonPin + 'd> traits::Button for ButtonDriver<'d, P> {
+    fn is_pressed(&self) -> bool {
+        ButtonDriver::is_pressed(self)
+    }
+}
+
+/// Driver for capturing presses of a button of a simulated watch.
+#[cfg(feature = "sim")]
+#[derive(Debug, Clone)]
+pub struct ButtonDriver {
+    /// The simulated watch.
+    watch: sim::Watch,
+    /// The button.
+    button: Button,
+}
+#[cfg(feature = "sim")]
+impl ButtonDriver {
+    /// Creates a new button driver for a particular button of a simulated watch.
+    ///
+    /// # Example
+    /// ```
+    /// use watchy::button::{Button, ButtonDriver};
+    ///
+    /// let watch = watchy::sim::Watch::new();
+    /// let button_driver = ButtonDriver::new(&watch, Button::Menu);
+    /// watch.set_pressed(Some(Button::Menu));
+    /// assert!(button_driver.is_pressed());
+    /// ```
+    pub fn new(watch: &sim::Watch, button: Button) -> Self {
+        Self {
+            watch: watch.clone(),
+            button,
+        }
+    }
+
+    /// Returns whether the button is currently pressed.
+    pub fn is_pressed(&self) -> bool {
+        self.watch.pressed() == Some(self.button)
+    }
+}
+#[cfg(feature = "sim")]
+impl traits::Button for ButtonDriver {
+    fn is_pressed(&self) -> bool {
+        ButtonDriver::is_pressed(self)
     }
 }
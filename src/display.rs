@@ -2,11 +2,48 @@
 
 // Re-export core display driver crate.
 pub use gdeh0154d67;
+// Re-export the graphics crate used to draw on the framebuffer.
+pub use embedded_graphics;
 
-use crate::hal::{delay, gpio, peripheral, spi, units::FromValueType};
+mod bitmap;
+mod decode;
+mod diff;
+mod framebuffer;
+mod lut;
+mod orientation;
+mod panel;
+mod retained;
+
+pub use bitmap::Bitmap;
+pub use decode::{DecodeError, DecodedImage, ImageFormat};
+pub use diff::Diff;
+pub use framebuffer::{DoubleBuffer, FrameBuffer, GrayFrameBuffer, HEIGHT, WIDTH};
+pub use lut::{Lut, LutSet};
+pub use orientation::{Orientation, Oriented, Rotation};
+pub use panel::{Panel, PanelError, RefreshMode};
+#[cfg(all(feature = "esp-idf", feature = "std"))]
+pub(crate) use retained::discard as discard_retained;
+pub use retained::RetainedFrame;
+
+#[cfg(feature = "esp-idf")]
+use crate::hal::{
+    delay, gpio, peripheral, spi,
+    units::{FromValueType, Hertz},
+};
+#[cfg(not(feature = "sim"))]
 use crate::pins;
+#[cfg(feature = "sim")]
+use crate::sim;
+#[cfg(feature = "esp-idf")]
 use crate::sys::EspError;
+#[cfg(feature = "esp-idf")]
+use core::borrow::Borrow;
+#[cfg(feature = "esp-hal")]
+use core::cell::RefCell;
+#[cfg(feature = "esp-hal")]
+use esp_hal::{delay, gpio, spi};
 
+#[cfg(not(feature = "sim"))]
 use gdeh0154d67::{NotInitialized, GDEH0154D67};
 use thiserror::Error;
 
@@ -14,19 +51,64 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum DisplayError {
     /// A SPI bus error.
+    #[cfg(feature = "esp-idf")]
+    #[error("Error setting up the SPI driver: {0}")]
+    Spi(#[cfg_attr(feature = "std", source)] EspError),
+    /// A SPI bus configuration error.
+    #[cfg(feature = "esp-hal")]
     #[error("Error setting up the SPI driver: {0}")]
-    Spi(#[from] EspError),
+    Spi(#[from] spi::master::ConfigError),
     /// A display driver error.
     #[error("Display driver error: {0}")]
-    Driver(#[from] gdeh0154d67::error::Error),
+    Driver(#[cfg_attr(feature = "std", source)] gdeh0154d67::error::Error),
+}
+// NOTE: These are implemented manually because the source errors only implement
+// the error trait when using `std`.
+#[cfg(feature = "esp-idf")]
+impl From<EspError> for DisplayError {
+    fn from(value: EspError) -> Self {
+        Self::Spi(value)
+    }
+}
+impl From<gdeh0154d67::error::Error> for DisplayError {
+    fn from(value: gdeh0154d67::error::Error) -> Self {
+        Self::Driver(value)
+    }
+}
+
+/// Configuration of the SPI bus and device used for the display.
+#[cfg(feature = "esp-idf")]
+#[derive(Debug, Clone)]
+pub struct DisplayConfig {
+    /// The SPI clock frequency, which must not exceed 20 MHz for the display.
+    pub baudrate: Hertz,
+    /// The DMA setting of the SPI bus, which only applies when the bus is created using
+    /// [`spi_bus_driver`].
+    pub dma: spi::Dma,
+    /// The number of transactions that can be queued for the display.
+    pub queue_size: usize,
+}
+#[cfg(feature = "esp-idf")]
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            baudrate: 20.MHz().into(),
+            dma: spi::Dma::Disabled,
+            queue_size: 20,
+        }
+    }
 }
 
 /// The concrete type for the display driver, either uninitialized or initialized.
-pub type DisplayDriver<'d, INIT> = GDEH0154D67<
-    spi::SpiDeviceDriver<'d, spi::SpiDriver<'d>>,
-    gpio::PinDriver<'d, gpio::Gpio10, gpio::Output>,
-    gpio::PinDriver<'d, gpio::Gpio9, gpio::Output>,
-    gpio::PinDriver<'d, gpio::Gpio19, gpio::Input>,
+///
+/// The SPI bus driver `T` is owned by default, but can be borrowed when sharing the bus
+/// with other devices using [`display_driver_shared`].
+#[cfg(feature = "esp-idf")]
+pub type DisplayDriver<'d, INIT, T = spi::SpiDriver<'d>> = GDEH0154D67<
+    spi::SpiDeviceDriver<'d, T>,
+    gpio::PinDriver<'d, pins::DisplayDc, gpio::Output>,
+    gpio::PinDriver<'d, pins::DisplayReset, gpio::Output>,
+    gpio::PinDriver<'d, pins::DisplayBusy, gpio::Input>,
     delay::Delay,
     INIT,
 >;
@@ -34,6 +116,8 @@ pub type DisplayDriver<'d, INIT> = GDEH0154D67<
 /// Sets up the display driver.
 ///
 /// The GDEH0154D67 e-ink display is connected over a [SPI bus](https://en.wikipedia.org/wiki/Serial_Peripheral_Interface).
+/// This uses the whole SPI peripheral with the [default](DisplayConfig::default)
+/// configuration, see [`display_driver_shared`] to share the bus with other devices.
 ///
 /// NOTE: SPI0 is reserved and SPI1 is restricted, so neither should
 /// be used to drive the display.
@@ -45,20 +129,154 @@ pub type DisplayDriver<'d, INIT> = GDEH0154D67<
 /// let display_driver =
 ///     watchy::display::display_driver(pin_sets.display, peripherals.spi2).unwrap();
 /// ```
+#[cfg(feature = "esp-idf")]
 pub fn display_driver<'d, SPI: spi::SpiAnyPins>(
     display_pins: pins::Display,
     spi: impl peripheral::Peripheral<P = SPI> + 'd,
 ) -> Result<DisplayDriver<'d, NotInitialized>, DisplayError> {
-    // Setup the SPI driver
-    let spi = spi::SpiDeviceDriver::new_single(
+    let config = DisplayConfig::default();
+    let (bus_pins, device_pins) = display_pins.split();
+    display_driver_shared(
+        device_pins,
+        spi_bus_driver(bus_pins, spi, &config)?,
+        &config,
+    )
+}
+
+/// Sets up the display driver on a SPI bus that may be shared with other devices.
+///
+/// The bus driver is typically created using [`spi_bus_driver`] and then borrowed by
+/// each device on the bus, each of which has its own chip select pin.
+///
+/// # Example
+/// ```no_run
+/// use watchy::{
+///     display::{self, DisplayConfig},
+///     hal::spi,
+/// };
+///
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let config = DisplayConfig {
+///     dma: spi::Dma::Auto(4096),
+///     ..Default::default()
+/// };
+/// let (bus_pins, device_pins) = pin_sets.display.split();
+/// let spi_driver = display::spi_bus_driver(bus_pins, peripherals.spi2, &config).unwrap();
+/// let display_driver = display::display_driver_shared(device_pins, &spi_driver, &config).unwrap();
+///
+/// // Another device on the same bus
+/// let other_device = spi::SpiDeviceDriver::new(
+///     &spi_driver,
+///     Some(pin_sets.unused.gpio2),
+///     &spi::config::Config::default(),
+/// )
+/// .unwrap();
+/// ```
+#[cfg(feature = "esp-idf")]
+pub fn display_driver_shared<'d, T: Borrow<spi::SpiDriver<'d>> + 'd>(
+    device_pins: pins::DisplayDevice,
+    spi_driver: T,
+    config: &DisplayConfig,
+) -> Result<DisplayDriver<'d, NotInitialized, T>, DisplayError> {
+    // Setup the display driver
+    Ok(gdeh0154d67::GDEH0154D67::new(
+        spi_device_driver(device_pins.spi_cs, spi_driver, config)?,
+        gpio::PinDriver::output(device_pins.disp_dc)?,
+        gpio::PinDriver::output(device_pins.disp_reset)?,
+        gpio::PinDriver::input(device_pins.disp_busy)?,
+        delay::Delay::new_default(),
+    )?)
+}
+
+/// The concrete type for the direct display panel driver.
+///
+/// The SPI bus driver `T` is owned by default, but can be borrowed when sharing the bus
+/// with other devices using [`panel_driver_shared`].
+#[cfg(feature = "esp-idf")]
+pub type PanelDriver<'d, T = spi::SpiDriver<'d>> = Panel<
+    spi::SpiDeviceDriver<'d, T>,
+    gpio::PinDriver<'d, pins::DisplayDc, gpio::Output>,
+    gpio::PinDriver<'d, pins::DisplayReset, gpio::Output>,
+    gpio::PinDriver<'d, pins::DisplayBusy, gpio::Input>,
+>;
+
+/// Sets up the direct display panel driver.
+///
+/// This is an alternative to the core display driver from [`display_driver`], and uses
+/// the same SPI bus and pins.
+/// With the `embassy` feature, it can refresh the display asynchronously.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let panel = watchy::display::panel_driver(pin_sets.display, peripherals.spi2).unwrap();
+/// ```
+#[cfg(feature = "esp-idf")]
+pub fn panel_driver<'d, SPI: spi::SpiAnyPins>(
+    display_pins: pins::Display,
+    spi: impl peripheral::Peripheral<P = SPI> + 'd,
+) -> Result<PanelDriver<'d>, DisplayError> {
+    let config = DisplayConfig::default();
+    let (bus_pins, device_pins) = display_pins.split();
+    panel_driver_shared(
+        device_pins,
+        spi_bus_driver(bus_pins, spi, &config)?,
+        &config,
+    )
+}
+
+/// Sets up the direct display panel driver on a SPI bus that may be shared with other
+/// devices.
+///
+/// See [`display_driver_shared`] for details.
+#[cfg(feature = "esp-idf")]
+pub fn panel_driver_shared<'d, T: Borrow<spi::SpiDriver<'d>> + 'd>(
+    device_pins: pins::DisplayDevice,
+    spi_driver: T,
+    config: &DisplayConfig,
+) -> Result<PanelDriver<'d, T>, DisplayError> {
+    Ok(Panel::new(
+        spi_device_driver(device_pins.spi_cs, spi_driver, config)?,
+        gpio::PinDriver::output(device_pins.disp_dc)?,
+        gpio::PinDriver::output(device_pins.disp_reset)?,
+        gpio::PinDriver::input(device_pins.disp_busy)?,
+    ))
+}
+
+/// Sets up the driver for the SPI bus used by the display, which can then be shared
+/// with other devices.
+///
+/// NOTE: SPI0 is reserved and SPI1 is restricted, so neither should
+/// be used to drive the display.
+#[cfg(feature = "esp-idf")]
+pub fn spi_bus_driver<'d, SPI: spi::SpiAnyPins>(
+    bus_pins: pins::DisplayBus,
+    spi: impl peripheral::Peripheral<P = SPI> + 'd,
+    config: &DisplayConfig,
+) -> Result<spi::SpiDriver<'d>, EspError> {
+    spi::SpiDriver::new(
         spi,
-        display_pins.spi_sclk,
-        display_pins.spi_sdo,
+        bus_pins.spi_sclk,
+        bus_pins.spi_sdo,
         None::<gpio::AnyIOPin>,
-        Some(display_pins.spi_cs),
-        &spi::config::DriverConfig::new(),
+        &spi::config::DriverConfig::new().dma(config.dma),
+    )
+}
+
+/// Sets up the SPI device driver for the display.
+#[cfg(feature = "esp-idf")]
+fn spi_device_driver<'d, T: Borrow<spi::SpiDriver<'d>> + 'd>(
+    cs: impl peripheral::Peripheral<P = impl gpio::OutputPin> + 'd,
+    spi_driver: T,
+    config: &DisplayConfig,
+) -> Result<spi::SpiDeviceDriver<'d, T>, EspError> {
+    spi::SpiDeviceDriver::new(
+        spi_driver,
+        Some(cs),
         &spi::config::Config {
-            baudrate: 20.MHz().into(),
+            baudrate: config.baudrate,
             data_mode: embedded_hal::spi::Mode {
                 polarity: embedded_hal::spi::Polarity::IdleLow,
                 phase: embedded_hal::spi::Phase::CaptureOnFirstTransition,
@@ -72,16 +290,337 @@ pub fn display_driver<'d, SPI: spi::SpiAnyPins>(
             input_delay_ns: 50,
             polling: false,
             allow_pre_post_delays: true,
-            queue_size: 20,
+            queue_size: config.queue_size,
         },
-    )?;
+    )
+}
+
+/// Configuration of the SPI bus used for the display.
+///
+/// NOTE: DMA is set up separately with esp-hal, see [`panel_driver_dma`].
+#[cfg(feature = "esp-hal")]
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayConfig {
+    /// The SPI clock frequency, which must not exceed 20 MHz for the display.
+    pub baudrate: esp_hal::time::Rate,
+}
+#[cfg(feature = "esp-hal")]
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            baudrate: esp_hal::time::Rate::from_mhz(20),
+        }
+    }
+}
+
+/// The concrete type for the display driver, either uninitialized or initialized.
+///
+/// The SPI device is exclusive by default, but can share the bus with other devices
+/// using [`display_driver_shared`].
+#[cfg(feature = "esp-hal")]
+pub type DisplayDriver<
+    'd,
+    INIT,
+    SPI = embedded_hal_bus::spi::ExclusiveDevice<
+        spi::master::Spi<'d, esp_hal::Blocking>,
+        gpio::Output<'d>,
+        delay::Delay,
+    >,
+> = GDEH0154D67<SPI, gpio::Output<'d>, gpio::Output<'d>, gpio::Input<'d>, delay::Delay, INIT>;
+
+/// The concrete type for the display driver on a shared SPI bus, either uninitialized
+/// or initialized.
+#[cfg(feature = "esp-hal")]
+pub type SharedDisplayDriver<'a, 'd, INIT> = DisplayDriver<
+    'd,
+    INIT,
+    embedded_hal_bus::spi::RefCellDevice<
+        'a,
+        spi::master::Spi<'d, esp_hal::Blocking>,
+        gpio::Output<'d>,
+        delay::Delay,
+    >,
+>;
+
+/// Sets up the display driver.
+///
+/// The GDEH0154D67 e-ink display is connected over a [SPI bus](https://en.wikipedia.org/wiki/Serial_Peripheral_Interface).
+/// This uses the whole SPI peripheral with the [default](DisplayConfig::default)
+/// configuration, see [`display_driver_shared`] to share the bus with other devices.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let display_driver =
+///     watchy::display::display_driver(pin_sets.display, peripherals.SPI2).unwrap();
+/// ```
+#[cfg(feature = "esp-hal")]
+pub fn display_driver<'d>(
+    display_pins: pins::Display,
+    spi: impl spi::master::Instance + 'd,
+) -> Result<DisplayDriver<'d, NotInitialized>, DisplayError> {
+    let (bus_pins, device_pins) = display_pins.split();
+
+    // Setup the SPI driver
+    let spi = embedded_hal_bus::spi::ExclusiveDevice::new(
+        spi_bus_driver(bus_pins, spi, &DisplayConfig::default())?,
+        output(device_pins.spi_cs, gpio::Level::High),
+        delay::Delay::new(),
+    )
+    .unwrap_or_else(|never| match never {});
 
     // Setup the display driver
     Ok(gdeh0154d67::GDEH0154D67::new(
         spi,
-        gpio::PinDriver::output(display_pins.disp_dc)?,
-        gpio::PinDriver::output(display_pins.disp_reset)?,
-        gpio::PinDriver::input(display_pins.disp_busy)?,
-        delay::Delay::new_default(),
+        output(device_pins.disp_dc, gpio::Level::Low),
+        output(device_pins.disp_reset, gpio::Level::High),
+        gpio::Input::new(device_pins.disp_busy, gpio::InputConfig::default()),
+        delay::Delay::new(),
     )?)
 }
+
+/// Sets up the display driver on a SPI bus that may be shared with other devices.
+///
+/// The bus driver is typically created using [`spi_bus_driver`], which is where the
+/// [`DisplayConfig`] applies, and then shared with each device on the bus, each of which
+/// has its own chip select pin.
+///
+/// # Example
+/// ```no_run
+/// use core::cell::RefCell;
+/// use watchy::display::{self, DisplayConfig};
+///
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let (bus_pins, device_pins) = pin_sets.display.split();
+/// let spi_bus = RefCell::new(
+///     display::spi_bus_driver(bus_pins, peripherals.SPI2, &DisplayConfig::default()).unwrap(),
+/// );
+/// let display_driver = display::display_driver_shared(device_pins, &spi_bus).unwrap();
+///
+/// // Other devices on the same bus can use `embedded_hal_bus::spi::RefCellDevice`
+/// ```
+#[cfg(feature = "esp-hal")]
+pub fn display_driver_shared<'a, 'd>(
+    device_pins: pins::DisplayDevice,
+    spi_bus: &'a RefCell<spi::master::Spi<'d, esp_hal::Blocking>>,
+) -> Result<SharedDisplayDriver<'a, 'd, NotInitialized>, DisplayError> {
+    let spi = embedded_hal_bus::spi::RefCellDevice::new(
+        spi_bus,
+        output(device_pins.spi_cs, gpio::Level::High),
+        delay::Delay::new(),
+    )
+    .unwrap_or_else(|never| match never {});
+
+    Ok(gdeh0154d67::GDEH0154D67::new(
+        spi,
+        output(device_pins.disp_dc, gpio::Level::Low),
+        output(device_pins.disp_reset, gpio::Level::High),
+        gpio::Input::new(device_pins.disp_busy, gpio::InputConfig::default()),
+        delay::Delay::new(),
+    )?)
+}
+
+/// The concrete type for the direct display panel driver.
+///
+/// The SPI device is exclusive and asynchronous by default, but can share the bus with
+/// other devices using [`panel_driver_shared`].
+#[cfg(feature = "esp-hal")]
+pub type PanelDriver<
+    'd,
+    SPI = embedded_hal_bus::spi::ExclusiveDevice<
+        spi::master::Spi<'d, esp_hal::Async>,
+        gpio::Output<'d>,
+        embedded_hal_bus::spi::NoDelay,
+    >,
+> = Panel<SPI, gpio::Output<'d>, gpio::Output<'d>, gpio::Input<'d>>;
+
+/// The concrete type for the direct display panel driver on a shared SPI bus.
+///
+/// This can only refresh the display synchronously.
+#[cfg(feature = "esp-hal")]
+pub type SharedPanelDriver<'a, 'd> = PanelDriver<
+    'd,
+    embedded_hal_bus::spi::RefCellDevice<
+        'a,
+        spi::master::Spi<'d, esp_hal::Blocking>,
+        gpio::Output<'d>,
+        delay::Delay,
+    >,
+>;
+
+/// Sets up the direct display panel driver.
+///
+/// This is an alternative to the core display driver from [`display_driver`], and uses
+/// the same SPI bus and pins.
+/// With the `embassy` feature, it can refresh the display asynchronously.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let panel = watchy::display::panel_driver(pin_sets.display, peripherals.SPI2).unwrap();
+/// ```
+#[cfg(feature = "esp-hal")]
+pub fn panel_driver<'d>(
+    display_pins: pins::Display,
+    spi: impl spi::master::Instance + 'd,
+) -> Result<PanelDriver<'d>, DisplayError> {
+    let (bus_pins, device_pins) = display_pins.split();
+    let spi = embedded_hal_bus::spi::ExclusiveDevice::new_no_delay(
+        spi_bus_driver(bus_pins, spi, &DisplayConfig::default())?.into_async(),
+        output(device_pins.spi_cs, gpio::Level::High),
+    )
+    .unwrap_or_else(|never| match never {});
+
+    Ok(Panel::new(
+        spi,
+        output(device_pins.disp_dc, gpio::Level::Low),
+        output(device_pins.disp_reset, gpio::Level::High),
+        gpio::Input::new(device_pins.disp_busy, gpio::InputConfig::default()),
+    ))
+}
+
+/// The concrete type for the direct display panel driver using DMA.
+#[cfg(feature = "esp-hal")]
+pub type DmaPanelDriver<'d> = PanelDriver<
+    'd,
+    embedded_hal_bus::spi::ExclusiveDevice<
+        spi::master::SpiDmaBus<'d, esp_hal::Async>,
+        gpio::Output<'d>,
+        embedded_hal_bus::spi::NoDelay,
+    >,
+>;
+
+/// Sets up the direct display panel driver with the SPI bus using DMA.
+///
+/// This frees up the CPU while frames are sent to the display, so that other tasks can
+/// run while the async methods of [`Panel`] await the transfer.
+/// The transmit buffer should be at least as large as a [`FrameBuffer`] so that each
+/// frame is sent in a single transfer, while the receive buffer is unused.
+/// The DMA channel is `DMA_CH0` on the ESP32-S3 used by v3.0 boards, and `DMA_SPI2`
+/// on the ESP32 used by the other revisions.
+///
+/// # Example
+/// ```no_run
+/// use watchy::esp_hal::{
+///     dma::{DmaRxBuf, DmaTxBuf},
+///     dma_buffers,
+/// };
+///
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let (rx_buffer, rx_descriptors, tx_buffer, tx_descriptors) =
+///     dma_buffers!(4, watchy::display::FrameBuffer::SIZE);
+/// let panel = watchy::display::panel_driver_dma(
+///     pin_sets.display,
+///     peripherals.SPI2,
+///     peripherals.DMA_CH0,
+///     DmaRxBuf::new(rx_descriptors, rx_buffer).unwrap(),
+///     DmaTxBuf::new(tx_descriptors, tx_buffer).unwrap(),
+/// )
+/// .unwrap();
+/// ```
+#[cfg(feature = "esp-hal")]
+pub fn panel_driver_dma<'d>(
+    display_pins: pins::Display,
+    spi: impl spi::master::Instance + 'd,
+    dma_channel: impl esp_hal::dma::DmaChannelFor<spi::master::AnySpi<'d>>,
+    dma_rx_buf: esp_hal::dma::DmaRxBuf,
+    dma_tx_buf: esp_hal::dma::DmaTxBuf,
+) -> Result<DmaPanelDriver<'d>, DisplayError> {
+    let (bus_pins, device_pins) = display_pins.split();
+    let spi = embedded_hal_bus::spi::ExclusiveDevice::new_no_delay(
+        spi_bus_driver(bus_pins, spi, &DisplayConfig::default())?
+            .with_dma(dma_channel)
+            .with_buffers(dma_rx_buf, dma_tx_buf)
+            .into_async(),
+        output(device_pins.spi_cs, gpio::Level::High),
+    )
+    .unwrap_or_else(|never| match never {});
+
+    Ok(Panel::new(
+        spi,
+        output(device_pins.disp_dc, gpio::Level::Low),
+        output(device_pins.disp_reset, gpio::Level::High),
+        gpio::Input::new(device_pins.disp_busy, gpio::InputConfig::default()),
+    ))
+}
+
+/// Sets up the direct display panel driver on a SPI bus that may be shared with other
+/// devices.
+///
+/// See [`display_driver_shared`] for details.
+#[cfg(feature = "esp-hal")]
+pub fn panel_driver_shared<'a, 'd>(
+    device_pins: pins::DisplayDevice,
+    spi_bus: &'a RefCell<spi::master::Spi<'d, esp_hal::Blocking>>,
+) -> SharedPanelDriver<'a, 'd> {
+    let spi = embedded_hal_bus::spi::RefCellDevice::new(
+        spi_bus,
+        output(device_pins.spi_cs, gpio::Level::High),
+        delay::Delay::new(),
+    )
+    .unwrap_or_else(|never| match never {});
+
+    Panel::new(
+        spi,
+        output(device_pins.disp_dc, gpio::Level::Low),
+        output(device_pins.disp_reset, gpio::Level::High),
+        gpio::Input::new(device_pins.disp_busy, gpio::InputConfig::default()),
+    )
+}
+
+/// Sets up the driver for the SPI bus used by the display, which can then be shared
+/// with other devices.
+#[cfg(feature = "esp-hal")]
+pub fn spi_bus_driver<'d>(
+    bus_pins: pins::DisplayBus,
+    spi: impl spi::master::Instance + 'd,
+    config: &DisplayConfig,
+) -> Result<spi::master::Spi<'d, esp_hal::Blocking>, spi::master::ConfigError> {
+    Ok(spi::master::Spi::new(
+        spi,
+        spi::master::Config::default()
+            .with_frequency(config.baudrate)
+            .with_mode(spi::Mode::_0),
+    )?
+    .with_sck(bus_pins.spi_sclk)
+    .with_mosi(bus_pins.spi_sdo))
+}
+
+/// Sets up an output pin driver for the display.
+#[cfg(feature = "esp-hal")]
+fn output<'d>(pin: impl gpio::OutputPin + 'd, level: gpio::Level) -> gpio::Output<'d> {
+    gpio::Output::new(pin, level, gpio::OutputConfig::default())
+}
+
+/// The concrete type for the direct display panel driver of a simulated watch.
+#[cfg(feature = "sim")]
+pub type PanelDriver = Panel<sim::Spi, sim::DataCommand, sim::Reset, sim::Busy>;
+
+/// Sets up the direct display panel driver of a simulated watch.
+///
+/// # Example
+/// ```
+/// use watchy::display::{FrameBuffer, RefreshMode};
+///
+/// let watch = watchy::sim::Watch::new();
+/// let mut panel = watchy::display::panel_driver(&watch);
+/// let mut delay = watchy::sim::Delay;
+/// panel.init(&mut delay).unwrap();
+/// panel
+///     .refresh(&mut delay, &FrameBuffer::new(), RefreshMode::Full)
+///     .unwrap();
+/// assert_eq!(watch.refreshes(RefreshMode::Full), 1);
+/// ```
+#[cfg(feature = "sim")]
+pub fn panel_driver(watch: &sim::Watch) -> PanelDriver {
+    Panel::new(
+        sim::Spi::new(watch),
+        sim::DataCommand::new(watch),
+        sim::Reset::new(watch),
+        sim::Busy::new(),
+    )
+}
@@ -0,0 +1,127 @@
+//! Packed one bit per pixel images.
+
+use embedded_graphics::{image::ImageDrawable, pixelcolor::BinaryColor, prelude::*};
+
+/// An image packed one bit per pixel in the same layout as a [`FrameBuffer`](super::FrameBuffer).
+///
+/// The pixels are packed in rows from the top left, with the most significant bit of each
+/// byte being the leftmost pixel and each row padded to a whole byte.
+/// A set bit is white, which is drawn as [`BinaryColor::Off`].
+///
+/// These are typically converted from image files at build time using the `watchy-assets`
+/// crate and then included using [`include_bitmap`](crate::include_bitmap).
+/// They are drawn using [`Image`](embedded_graphics::image::Image).
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{image::Image, prelude::*};
+/// use watchy::display::{Bitmap, FrameBuffer};
+///
+/// // An 8x2 image with a black top row
+/// const BITMAP: Bitmap = Bitmap::new(8, &[0x00, 0xFF]);
+///
+/// let mut frame = FrameBuffer::new();
+/// Image::new(&BITMAP, Point::new(10, 10))
+///     .draw(&mut frame)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitmap<'a> {
+    /// The width in pixels.
+    width: u32,
+    /// The packed pixel data.
+    data: &'a [u8],
+}
+impl<'a> Bitmap<'a> {
+    /// Creates a bitmap from its width in pixels and packed pixel data, from which the
+    /// height is determined.
+    ///
+    /// # Panics
+    /// This panics if the data is not a whole number of rows.
+    pub const fn new(width: u32, data: &'a [u8]) -> Self {
+        let row_bytes = width.div_ceil(8) as usize;
+        assert!(
+            row_bytes > 0 && data.len().is_multiple_of(row_bytes),
+            "the bitmap data must be a whole number of rows"
+        );
+        Self { width, data }
+    }
+
+    /// Returns the number of bytes in each row of pixels.
+    pub const fn row_bytes(&self) -> usize {
+        self.width.div_ceil(8) as usize
+    }
+
+    /// Returns the packed pixel data.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the color of a pixel, or `None` if it is outside of the bitmap.
+    pub fn pixel(&self, point: Point) -> Option<BinaryColor> {
+        let x = u32::try_from(point.x).ok().filter(|x| *x < self.width)?;
+        let y = u32::try_from(point.y).ok()? as usize;
+        let byte = self.data.get(y * self.row_bytes() + x as usize / 8)?;
+        Some(BinaryColor::from(byte & (0x80 >> (x % 8)) == 0))
+    }
+}
+impl OriginDimensions for Bitmap<'_> {
+    fn size(&self) -> Size {
+        Size::new(self.width, (self.data.len() / self.row_bytes()) as u32)
+    }
+}
+impl ImageDrawable for Bitmap<'_> {
+    type Color = BinaryColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let size = self.size();
+        target.fill_contiguous(
+            &self.bounding_box(),
+            (0..size.height as i32).flat_map(|y| {
+                (0..size.width as i32).map(move |x| self.pixel(Point::new(x, y)).unwrap())
+            }),
+        )
+    }
+
+    fn draw_sub_image<D>(
+        &self,
+        target: &mut D,
+        area: &embedded_graphics::primitives::Rectangle,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        target.draw_iter(area.points().filter_map(|point| {
+            self.pixel(point)
+                .map(|color| Pixel(point - area.top_left, color))
+        }))
+    }
+}
+
+/// Includes a [`Bitmap`](crate::display::Bitmap) that was converted from an image file
+/// by the build script using the `watchy-assets` crate.
+///
+/// The name is that given to the build helper, which writes the bitmap to the `OUT_DIR`
+/// of the crate.
+///
+/// # Example
+/// In `build.rs`:
+/// ```ignore
+/// fn main() {
+///     watchy_assets::include("icon", "assets/icon.png", watchy_assets::Dither::None).unwrap();
+/// }
+/// ```
+/// Then in the crate:
+/// ```ignore
+/// const ICON: watchy::display::Bitmap = watchy::include_bitmap!("icon");
+/// ```
+#[macro_export]
+macro_rules! include_bitmap {
+    ($name:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $name, ".bitmap.rs"))
+    };
+}
@@ -0,0 +1,552 @@
+//! Decoding of monochrome image files received at runtime.
+
+use super::{FrameBuffer, HEIGHT, WIDTH};
+use embedded_graphics::{
+    image::ImageDrawable, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+};
+use thiserror::Error;
+
+/// The largest supported width or height of an image in pixels.
+const MAX_DIMENSION: u32 = u16::MAX as u32;
+
+/// Error for image decoding problems.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The image format could not be determined from the data.
+    #[error("The image format was not recognized")]
+    UnknownFormat,
+    /// The image header is malformed.
+    #[error("The image header is malformed")]
+    InvalidHeader,
+    /// The pixel data is malformed.
+    #[error("The image pixel data is malformed")]
+    InvalidData,
+    /// The image ends before all of the pixel data.
+    #[error("The image data is truncated")]
+    Truncated,
+    /// The image is valid but not monochrome, or uses an unsupported variant of the
+    /// format.
+    #[error("The image is not a supported monochrome image")]
+    Unsupported,
+}
+
+/// A monochrome image file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// [Portable bitmap](https://netpbm.sourceforge.net/doc/pbm.html), in either the
+    /// plain (`P1`) or raw (`P4`) variant.
+    Pbm,
+    /// [X bitmap](https://en.wikipedia.org/wiki/X_BitMap), in the X11 variant with byte
+    /// values.
+    Xbm,
+    /// Windows [bitmap](https://en.wikipedia.org/wiki/BMP_file_format) with one bit per
+    /// pixel and no compression.
+    Bmp,
+}
+impl ImageFormat {
+    /// Determines the format of an image from the start of its data, returning `None`
+    /// if it is not recognized.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [b'P', b'1' | b'4', ..] => Some(Self::Pbm),
+            [b'B', b'M', ..] => Some(Self::Bmp),
+            _ if data.trim_ascii_start().starts_with(b"#define") => Some(Self::Xbm),
+            _ => None,
+        }
+    }
+}
+
+/// Pixel data packed in rows, with each row padded to a whole number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Packed<'a> {
+    /// The packed pixel data for all of the rows.
+    data: &'a [u8],
+    /// The number of bytes in each row, including any padding.
+    row_bytes: usize,
+    /// The number of rows.
+    height: usize,
+    /// Whether the least significant bit of each byte is the leftmost pixel.
+    lsb_first: bool,
+    /// Whether the rows are stored from the bottom of the image.
+    bottom_up: bool,
+    /// The colors of clear and set bits, in that order.
+    colors: [BinaryColor; 2],
+}
+impl Packed<'_> {
+    /// Returns the color of a pixel, which must be inside the image.
+    fn pixel(&self, x: usize, y: usize) -> BinaryColor {
+        let row = if self.bottom_up {
+            self.height - 1 - y
+        } else {
+            y
+        };
+        let byte = self.data[row * self.row_bytes + x / 8];
+        let shift = if self.lsb_first { x % 8 } else { 7 - x % 8 };
+        self.colors[usize::from(byte >> shift & 1)]
+    }
+}
+
+/// How the pixels of an image are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding<'a> {
+    /// Binary packed rows, from which pixels can be read in any order.
+    Packed(Packed<'a>),
+    /// The ASCII digits of a plain PBM image, with 1 being black.
+    ///
+    /// These must be read in order.
+    Plain(&'a [u8]),
+    /// The comma separated byte values of an XBM image, which are packed rows with the
+    /// least significant bit first and 1 being black.
+    ///
+    /// These must be read in order.
+    Xbm(&'a [u8]),
+}
+
+/// A monochrome image decoded from the data of an image file, which is borrowed rather
+/// than copied.
+///
+/// The data is fully validated when decoding, after which the image can be drawn any
+/// number of times.
+/// It can be drawn straight into a [`FrameBuffer`] using [`blit`](Self::blit), or
+/// anywhere else using [`Image`](embedded_graphics::image::Image).
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::prelude::*;
+/// use watchy::display::{DecodedImage, FrameBuffer};
+///
+/// // Received over Wi-Fi or read from flash storage
+/// let data = b"P1\n4 2\n1 0 1 0\n0 1 0 1\n";
+///
+/// let mut frame = FrameBuffer::new();
+/// DecodedImage::decode(data)
+///     .unwrap()
+///     .blit(&mut frame, Point::new(98, 99), false);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodedImage<'a> {
+    /// The format from which the image was decoded.
+    format: ImageFormat,
+    /// The size of the image.
+    size: Size,
+    /// The pixel data.
+    encoding: Encoding<'a>,
+}
+impl<'a> DecodedImage<'a> {
+    /// Decodes an image, the format of which is [detected](ImageFormat::detect) from
+    /// its data.
+    pub fn decode(data: &'a [u8]) -> Result<Self, DecodeError> {
+        Self::decode_as(
+            data,
+            ImageFormat::detect(data).ok_or(DecodeError::UnknownFormat)?,
+        )
+    }
+
+    /// Decodes an image in a known format.
+    pub fn decode_as(data: &'a [u8], format: ImageFormat) -> Result<Self, DecodeError> {
+        match format {
+            ImageFormat::Pbm => Self::decode_pbm(data),
+            ImageFormat::Xbm => Self::decode_xbm(data),
+            ImageFormat::Bmp => Self::decode_bmp(data),
+        }
+    }
+
+    /// Decodes a PBM image.
+    fn decode_pbm(data: &'a [u8]) -> Result<Self, DecodeError> {
+        let (plain, mut rest) = match data {
+            [b'P', b'1', rest @ ..] => (true, rest),
+            [b'P', b'4', rest @ ..] => (false, rest),
+            _ => return Err(DecodeError::InvalidHeader),
+        };
+        if !rest
+            .first()
+            .is_some_and(|b| b.is_ascii_whitespace() || *b == b'#')
+        {
+            return Err(DecodeError::InvalidHeader);
+        }
+        let width = header_number(&mut rest)?;
+        let height = header_number(&mut rest)?;
+        let size = checked_size(width, height)?;
+
+        let encoding = if plain {
+            let mut digits = rest;
+            for _ in 0..pixel_count(size)? {
+                next_plain_pixel(&mut digits)?;
+            }
+            Encoding::Plain(rest)
+        } else {
+            // A single whitespace character separates the header from the pixel data
+            let [separator, rest @ ..] = rest else {
+                return Err(DecodeError::Truncated);
+            };
+            if !separator.is_ascii_whitespace() {
+                return Err(DecodeError::InvalidHeader);
+            }
+            Encoding::Packed(packed(rest, width.div_ceil(8) as usize, size)?)
+        };
+
+        Ok(Self {
+            format: ImageFormat::Pbm,
+            size,
+            encoding,
+        })
+    }
+
+    /// Decodes an XBM image.
+    fn decode_xbm(data: &'a [u8]) -> Result<Self, DecodeError> {
+        let open = data
+            .iter()
+            .position(|b| *b == b'{')
+            .ok_or(DecodeError::InvalidHeader)?;
+        let (header, body) = data.split_at(open);
+
+        let (mut width, mut height) = (None, None);
+        for line in header.split(|b| *b == b'\n') {
+            let mut words = line
+                .split(|b| b.is_ascii_whitespace())
+                .filter(|word| !word.is_empty());
+            if words.next() != Some(&b"#define"[..]) {
+                continue;
+            }
+            let (Some(name), Some(value)) = (words.next(), words.next()) else {
+                return Err(DecodeError::InvalidHeader);
+            };
+            let value = parse_number(value).ok_or(DecodeError::InvalidHeader)?;
+            // NOTE: Any hot spot definitions are ignored.
+            if name.ends_with(b"_width") {
+                width = Some(value);
+            } else if name.ends_with(b"_height") {
+                height = Some(value);
+            }
+        }
+        // The X10 variant has 16-bit values
+        if header.windows(5).any(|word| word == b"short") {
+            return Err(DecodeError::Unsupported);
+        }
+        let width = width.ok_or(DecodeError::InvalidHeader)?;
+        let size = checked_size(width, height.ok_or(DecodeError::InvalidHeader)?)?;
+
+        let body = &body[1..];
+        let body = &body[..body
+            .iter()
+            .position(|b| *b == b'}')
+            .ok_or(DecodeError::Truncated)?];
+        let mut values = body;
+        let count = (width.div_ceil(8) as usize)
+            .checked_mul(size.height as usize)
+            .ok_or(DecodeError::Unsupported)?;
+        for _ in 0..count {
+            next_xbm_value(&mut values)?;
+        }
+
+        Ok(Self {
+            format: ImageFormat::Xbm,
+            size,
+            encoding: Encoding::Xbm(body),
+        })
+    }
+
+    /// Decodes a BMP image.
+    fn decode_bmp(data: &'a [u8]) -> Result<Self, DecodeError> {
+        let read = |offset: usize, len: usize| {
+            offset
+                .checked_add(len)
+                .and_then(|end| data.get(offset..end))
+                .ok_or(DecodeError::Truncated)
+        };
+        let read_u16 = |offset| read(offset, 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+        let read_u32 =
+            |offset| read(offset, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+
+        if !data.starts_with(b"BM") {
+            return Err(DecodeError::InvalidHeader);
+        }
+        let pixel_offset = read_u32(10)? as usize;
+        let header_size = read_u32(14)? as usize;
+        // The original OS/2 header has 16-bit dimensions and 3-byte palette entries
+        let (width, height, planes, bits, compression, entry_size) = match header_size {
+            12 => (
+                i32::from(read_u16(18)?),
+                i32::from(read_u16(20)?),
+                read_u16(22)?,
+                read_u16(24)?,
+                0,
+                3,
+            ),
+            40.. => (
+                read_u32(18)? as i32,
+                read_u32(22)? as i32,
+                read_u16(26)?,
+                read_u16(28)?,
+                read_u32(30)?,
+                4,
+            ),
+            _ => return Err(DecodeError::InvalidHeader),
+        };
+        if planes != 1 || width <= 0 || height == 0 {
+            return Err(DecodeError::InvalidHeader);
+        }
+        if bits != 1 || compression != 0 {
+            return Err(DecodeError::Unsupported);
+        }
+        // A negative height means that the rows are stored from the top
+        let size = checked_size(width as u32, height.unsigned_abs())?;
+
+        // The two palette entries follow the headers and are in BGR order
+        let palette = header_size.saturating_add(14);
+        let color = |index: usize| {
+            read(palette.saturating_add(index * entry_size), 3).map(|entry| {
+                let [blue, green, red] = [entry[0], entry[1], entry[2]].map(u32::from);
+                // Black if darker than mid gray
+                BinaryColor::from(red * 299 + green * 587 + blue * 114 < 128_000)
+            })
+        };
+        let colors = [color(0)?, color(1)?];
+
+        // Rows are padded to a multiple of four bytes
+        let row_bytes = (width as usize).div_ceil(32) * 4;
+        let mut packed = packed(
+            data.get(pixel_offset..).ok_or(DecodeError::Truncated)?,
+            row_bytes,
+            size,
+        )?;
+        packed.bottom_up = height > 0;
+        packed.colors = colors;
+
+        Ok(Self {
+            format: ImageFormat::Bmp,
+            size,
+            encoding: Encoding::Packed(packed),
+        })
+    }
+
+    /// Returns the format from which the image was decoded.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Returns an iterator over the colors of all of the pixels in rows from the top
+    /// left.
+    pub fn pixels(&self) -> impl Iterator<Item = BinaryColor> + 'a {
+        Pixels {
+            encoding: self.encoding,
+            width: self.size.width as usize,
+            height: self.size.height as usize,
+            x: 0,
+            y: 0,
+            byte: 0,
+        }
+    }
+
+    /// Draws the image straight into a framebuffer with its top left corner at a point,
+    /// clipping any part that is outside of the display.
+    ///
+    /// If `invert` is set then black and white are swapped.
+    pub fn blit(&self, frame: &mut FrameBuffer, top_left: Point, invert: bool) {
+        // Also avoids any overflow when offsetting the points
+        if top_left.x >= WIDTH as i32 || top_left.y >= HEIGHT as i32 {
+            return;
+        }
+        let invert = |color: BinaryColor| if invert { color.invert() } else { color };
+
+        match &self.encoding {
+            Encoding::Packed(packed) => {
+                // Only visit the pixels that are on the display
+                let visible =
+                    Rectangle::new(top_left, self.size).intersection(&frame.bounding_box());
+                for point in visible.points() {
+                    let offset = point - top_left;
+                    frame.set_pixel(
+                        point,
+                        invert(packed.pixel(offset.x as usize, offset.y as usize)),
+                    );
+                }
+            }
+            // The text formats can only be read in order
+            Encoding::Plain(_) | Encoding::Xbm(_) => {
+                for (point, color) in self.bounding_box().points().zip(self.pixels()) {
+                    frame.set_pixel(point + top_left, invert(color));
+                }
+            }
+        }
+    }
+}
+impl OriginDimensions for DecodedImage<'_> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+impl ImageDrawable for DecodedImage<'_> {
+    type Color = BinaryColor;
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        target.fill_contiguous(&self.bounding_box(), self.pixels())
+    }
+
+    fn draw_sub_image<D>(&self, target: &mut D, area: &Rectangle) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = area.intersection(&self.bounding_box());
+        target.draw_iter(
+            self.bounding_box()
+                .points()
+                .zip(self.pixels())
+                .filter(|(point, _)| area.contains(*point))
+                .map(|(point, color)| Pixel(point - area.top_left, color)),
+        )
+    }
+}
+
+/// Iterator over the pixels of a [`DecodedImage`] in rows from the top left.
+struct Pixels<'a> {
+    /// The pixel data, the slices of which are advanced for the text formats.
+    encoding: Encoding<'a>,
+    /// The width of the image.
+    width: usize,
+    /// The height of the image.
+    height: usize,
+    /// The column of the next pixel.
+    x: usize,
+    /// The row of the next pixel.
+    y: usize,
+    /// The current byte value of an XBM image.
+    byte: u8,
+}
+impl Iterator for Pixels<'_> {
+    type Item = BinaryColor;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.height {
+            return None;
+        }
+
+        // NOTE: The data was validated when decoding, so it cannot run out early.
+        let color = match &mut self.encoding {
+            Encoding::Packed(packed) => packed.pixel(self.x, self.y),
+            Encoding::Plain(digits) => next_plain_pixel(digits).ok()?,
+            Encoding::Xbm(values) => {
+                if self.x.is_multiple_of(8) {
+                    self.byte = next_xbm_value(values).ok()?;
+                }
+                BinaryColor::from(self.byte >> (self.x % 8) & 1 != 0)
+            }
+        };
+
+        self.x += 1;
+        if self.x == self.width {
+            self.x = 0;
+            self.y += 1;
+        }
+        Some(color)
+    }
+}
+
+/// Checks the dimensions of an image.
+fn checked_size(width: u32, height: u32) -> Result<Size, DecodeError> {
+    if width == 0 || height == 0 {
+        Err(DecodeError::InvalidHeader)
+    } else if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        Err(DecodeError::Unsupported)
+    } else {
+        Ok(Size::new(width, height))
+    }
+}
+
+/// Returns the number of pixels in an image.
+fn pixel_count(size: Size) -> Result<usize, DecodeError> {
+    (size.width as usize)
+        .checked_mul(size.height as usize)
+        .ok_or(DecodeError::Unsupported)
+}
+
+/// Checks that there is enough packed pixel data for an image, which is stored from the
+/// top with the most significant bit first and 1 being black.
+fn packed(data: &[u8], row_bytes: usize, size: Size) -> Result<Packed<'_>, DecodeError> {
+    let height = size.height as usize;
+    let len = row_bytes
+        .checked_mul(height)
+        .ok_or(DecodeError::Unsupported)?;
+    Ok(Packed {
+        data: data.get(..len).ok_or(DecodeError::Truncated)?,
+        row_bytes,
+        height,
+        lsb_first: false,
+        bottom_up: false,
+        colors: [BinaryColor::Off, BinaryColor::On],
+    })
+}
+
+/// Parses a decimal, or hexadecimal with a `0x` prefix, number.
+fn parse_number(word: &[u8]) -> Option<u32> {
+    let (digits, radix) = match word {
+        [b'0', b'x' | b'X', digits @ ..] => (digits, 16),
+        _ => (word, 10),
+    };
+    if !digits.iter().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(core::str::from_utf8(digits).ok()?, radix).ok()
+}
+
+/// Skips whitespace and comments in PBM data.
+fn skip_pbm_space(rest: &mut &[u8]) {
+    loop {
+        match rest {
+            [b'#', ..] => {
+                let end = rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+                *rest = &rest[end..];
+            }
+            [b, tail @ ..] if b.is_ascii_whitespace() => *rest = tail,
+            _ => break,
+        }
+    }
+}
+
+/// Reads a decimal number from a PBM header.
+fn header_number(rest: &mut &[u8]) -> Result<u32, DecodeError> {
+    skip_pbm_space(rest);
+    let len = rest
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (digits, tail) = rest.split_at(len);
+    *rest = tail;
+    match digits {
+        [] if tail.is_empty() => Err(DecodeError::Truncated),
+        [] => Err(DecodeError::InvalidHeader),
+        // Any that are too large would be unsupported anyway
+        _ => Ok(parse_number(digits).unwrap_or(u32::MAX)),
+    }
+}
+
+/// Reads the next pixel of a plain PBM image.
+fn next_plain_pixel(rest: &mut &[u8]) -> Result<BinaryColor, DecodeError> {
+    skip_pbm_space(rest);
+    let (color, tail) = match rest {
+        [b'0', tail @ ..] => (BinaryColor::Off, tail),
+        [b'1', tail @ ..] => (BinaryColor::On, tail),
+        [] => return Err(DecodeError::Truncated),
+        _ => return Err(DecodeError::InvalidData),
+    };
+    *rest = tail;
+    Ok(color)
+}
+
+/// Reads the next byte value of an XBM image.
+fn next_xbm_value(rest: &mut &[u8]) -> Result<u8, DecodeError> {
+    let is_separator = |b: &u8| b.is_ascii_whitespace() || *b == b',';
+    let start = rest
+        .iter()
+        .position(|b| !is_separator(b))
+        .ok_or(DecodeError::Truncated)?;
+    let token = &rest[start..];
+    let (token, tail) = token.split_at(token.iter().position(is_separator).unwrap_or(token.len()));
+    *rest = tail;
+    parse_number(token)
+        .and_then(|value| u8::try_from(value).ok())
+        .ok_or(DecodeError::InvalidData)
+}
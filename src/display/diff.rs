@@ -0,0 +1,47 @@
+//! Comparison of frames against images, such as golden images.
+
+use super::{DecodedImage, FrameBuffer};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+/// The pixels in which a frame differs from an image.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Diff {
+    /// A frame in which the pixels that differ are black.
+    pub frame: FrameBuffer,
+    /// The number of pixels that differ.
+    pub pixels: usize,
+}
+impl Diff {
+    /// Compares a frame against an image drawn from its top left corner.
+    ///
+    /// Any part of the image that is outside of the frame differs, since it cannot be
+    /// shown, but is not in the [frame](Self::frame) of differences.
+    pub fn new(frame: &FrameBuffer, image: &DecodedImage) -> Self {
+        let mut expected = FrameBuffer::new();
+        image.blit(&mut expected, Point::zero(), false);
+        let outside = image.size().width as usize * image.size().height as usize
+            - image
+                .bounding_box()
+                .intersection(&expected.bounding_box())
+                .points()
+                .count();
+
+        let mut differences = FrameBuffer::new();
+        let mut pixels = outside;
+        for point in expected.bounding_box().points() {
+            if expected.pixel(point) != frame.pixel(point) {
+                differences.set_pixel(point, BinaryColor::On);
+                pixels += 1;
+            }
+        }
+        Self {
+            frame: differences,
+            pixels,
+        }
+    }
+
+    /// Returns whether the frame matches the image.
+    pub fn is_empty(&self) -> bool {
+        self.pixels == 0
+    }
+}
@@ -0,0 +1,239 @@
+//! A framebuffer matching the RAM layout of the display controller.
+
+use embedded_graphics::{
+    pixelcolor::{BinaryColor, Gray2},
+    prelude::*,
+};
+
+/// Width of the display in pixels.
+pub const WIDTH: u32 = 200;
+/// Height of the display in pixels.
+pub const HEIGHT: u32 = 200;
+
+/// In-memory image of the entire display, which can be drawn on using
+/// [`embedded-graphics`](embedded_graphics).
+///
+/// The pixels are packed one bit per pixel in rows from the top left, with the most
+/// significant bit of each byte being the leftmost pixel.
+/// This matches the RAM layout of the controller, in which a set bit is white.
+/// When drawing, [`BinaryColor::On`] is black and [`BinaryColor::Off`] is white.
+#[derive(Clone, PartialEq, Eq)]
+pub struct FrameBuffer {
+    /// The packed pixel data.
+    data: [u8; Self::SIZE],
+}
+impl FrameBuffer {
+    /// The number of bytes in each row of pixels.
+    pub const ROW_BYTES: usize = WIDTH as usize / 8;
+    /// The size of the packed pixel data in bytes.
+    pub const SIZE: usize = Self::ROW_BYTES * HEIGHT as usize;
+
+    /// Creates a new framebuffer that is entirely white.
+    pub const fn new() -> Self {
+        Self {
+            data: [0xFF; Self::SIZE],
+        }
+    }
+
+    /// Creates a framebuffer from packed pixel data.
+    pub const fn from_bytes(data: [u8; Self::SIZE]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the packed pixel data.
+    pub fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.data
+    }
+
+    /// Returns the packed pixel data mutably.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8; Self::SIZE] {
+        &mut self.data
+    }
+
+    /// Returns the byte index and bit mask of a pixel, or `None` if it is
+    /// outside of the display.
+    fn locate(point: Point) -> Option<(usize, u8)> {
+        let x = u32::try_from(point.x).ok().filter(|x| *x < WIDTH)? as usize;
+        let y = u32::try_from(point.y).ok().filter(|y| *y < HEIGHT)? as usize;
+        Some((y * Self::ROW_BYTES + x / 8, 0x80 >> (x % 8)))
+    }
+
+    /// Returns the color of a pixel, or `None` if it is outside of the display.
+    pub fn pixel(&self, point: Point) -> Option<BinaryColor> {
+        Self::locate(point).map(|(index, mask)| BinaryColor::from(self.data[index] & mask == 0))
+    }
+
+    /// Sets the color of a pixel, which is ignored if it is outside of the display.
+    pub fn set_pixel(&mut self, point: Point, color: BinaryColor) {
+        if let Some((index, mask)) = Self::locate(point) {
+            match color {
+                BinaryColor::On => self.data[index] &= !mask,
+                BinaryColor::Off => self.data[index] |= mask,
+            }
+        }
+    }
+}
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl core::fmt::Debug for FrameBuffer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FrameBuffer").finish_non_exhaustive()
+    }
+}
+impl OriginDimensions for FrameBuffer {
+    fn size(&self) -> Size {
+        Size::new(WIDTH, HEIGHT)
+    }
+}
+impl DrawTarget for FrameBuffer {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set_pixel(point, color);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.data.fill(match color {
+            BinaryColor::On => 0x00,
+            BinaryColor::Off => 0xFF,
+        });
+        Ok(())
+    }
+}
+
+/// A pair of framebuffers, so that the next frame can be drawn into the back buffer
+/// while the front buffer is shown on the display.
+///
+/// With the `embassy` feature, the buffers are typically presented using
+/// [`Panel::present_async`](super::Panel::present_async),
+/// which overlaps the drawing of the next frame with the refresh of the current one.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct DoubleBuffer {
+    /// The two framebuffers.
+    buffers: [FrameBuffer; 2],
+    /// The index of the front buffer.
+    front: usize,
+}
+impl DoubleBuffer {
+    /// Creates a new pair of framebuffers that are both entirely white.
+    pub const fn new() -> Self {
+        Self {
+            buffers: [FrameBuffer::new(), FrameBuffer::new()],
+            front: 0,
+        }
+    }
+
+    /// Returns the front buffer, which was most recently presented.
+    pub fn front(&self) -> &FrameBuffer {
+        &self.buffers[self.front]
+    }
+
+    /// Returns the back buffer, into which the next frame is drawn.
+    pub fn back(&self) -> &FrameBuffer {
+        &self.buffers[1 - self.front]
+    }
+
+    /// Returns the back buffer mutably, into which the next frame is drawn.
+    pub fn back_mut(&mut self) -> &mut FrameBuffer {
+        &mut self.buffers[1 - self.front]
+    }
+
+    /// Returns the front buffer and the back buffer mutably, in that order.
+    pub fn split_mut(&mut self) -> (&FrameBuffer, &mut FrameBuffer) {
+        let [first, second] = &mut self.buffers;
+        if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+
+    /// Swaps the front and back buffers.
+    ///
+    /// NOTE: The new back buffer still holds the frame before last, so it should be
+    /// cleared or entirely redrawn.
+    pub fn swap(&mut self) {
+        self.front = 1 - self.front;
+    }
+}
+
+/// In-memory image of the entire display with four gray levels, which can be drawn on
+/// using [`embedded-graphics`](embedded_graphics).
+///
+/// The two bits of each pixel are split between two one bit per pixel planes, which are
+/// loaded into the two controller RAMs to select between the four waveforms of a
+/// [grayscale LUT](super::Lut::GRAYSCALE_4).
+/// A set bit is lighter, so that [`Gray2::BLACK`] is zero in both planes.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct GrayFrameBuffer {
+    /// The plane with the most significant bit of each pixel.
+    high: FrameBuffer,
+    /// The plane with the least significant bit of each pixel.
+    low: FrameBuffer,
+}
+impl GrayFrameBuffer {
+    /// Creates a new framebuffer that is entirely white.
+    pub const fn new() -> Self {
+        Self {
+            high: FrameBuffer::new(),
+            low: FrameBuffer::new(),
+        }
+    }
+
+    /// Returns the planes with the most and least significant bits of each pixel,
+    /// in that order.
+    pub fn planes(&self) -> (&FrameBuffer, &FrameBuffer) {
+        (&self.high, &self.low)
+    }
+
+    /// Returns the gray level of a pixel, or `None` if it is outside of the display.
+    pub fn pixel(&self, point: Point) -> Option<Gray2> {
+        let high = self.high.pixel(point)? == BinaryColor::Off;
+        let low = self.low.pixel(point)? == BinaryColor::Off;
+        Some(Gray2::new(u8::from(high) << 1 | u8::from(low)))
+    }
+
+    /// Sets the gray level of a pixel, which is ignored if it is outside of the display.
+    pub fn set_pixel(&mut self, point: Point, color: Gray2) {
+        let luma = color.luma();
+        self.high
+            .set_pixel(point, BinaryColor::from(luma & 0b10 == 0));
+        self.low
+            .set_pixel(point, BinaryColor::from(luma & 0b01 == 0));
+    }
+}
+impl OriginDimensions for GrayFrameBuffer {
+    fn size(&self) -> Size {
+        Size::new(WIDTH, HEIGHT)
+    }
+}
+impl DrawTarget for GrayFrameBuffer {
+    type Color = Gray2;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set_pixel(point, color);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let luma = color.luma();
+        self.high.clear(BinaryColor::from(luma & 0b10 == 0))?;
+        self.low.clear(BinaryColor::from(luma & 0b01 == 0))
+    }
+}
@@ -0,0 +1,238 @@
+//! Custom waveform lookup tables (LUTs) for the display controller.
+
+/// A waveform lookup table (LUT) along with the voltages it is driven with.
+///
+/// The controller normally loads a LUT from its OTP memory for each refresh, which is
+/// selected using its temperature sensor.
+/// Uploading a custom LUT to the [`Panel`](super::Panel) instead allows for faster
+/// refreshes or for [grayscale](super::GrayFrameBuffer) rendering.
+///
+/// The LUT consists of 159 bytes in the same layout as used by the panel vendor:
+/// - 153 bytes for the waveform itself (command `0x32`), with the voltage selection
+///   for each of the four pixel transitions and VCOM in each of the 12 groups, followed
+///   by the phase timing of each group, the frame rates, and the gate scan selection.
+/// - The end option (command `0x3F`).
+/// - The gate voltage (command `0x03`).
+/// - The source voltages VSH1, VSH2, and VSL (command `0x04`).
+/// - The VCOM voltage (command `0x2C`).
+///
+/// The four pixel transitions are selected by the bit of each pixel in the previous and
+/// new frames, in that order, where zero is black.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Lut {
+    /// The raw bytes.
+    data: [u8; Self::SIZE],
+}
+impl Lut {
+    /// The size of the raw LUT in bytes.
+    pub const SIZE: usize = 159;
+    /// The size of just the waveform in bytes.
+    const WAVEFORM_SIZE: usize = 153;
+
+    /// Refreshes every pixel over around two seconds.
+    ///
+    /// This is the full update waveform from the reference driver of the panel vendor, and
+    /// is intended for use at room temperature.
+    pub const FULL: Self = Self::new([
+        // Voltage selection
+        0x80, 0x48, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x40, 0x48, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x80, 0x48, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x40, 0x48, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Phase timing
+        0x0A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x08, 0x01, 0x00, 0x08, 0x01, 0x00, 0x02, //
+        0x0A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Frame rates and gate scan selection
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00, 0x00, //
+        // End option, gate voltage, source voltages, and VCOM
+        0x22, 0x17, 0x41, 0x00, 0x32, 0x20,
+    ]);
+
+    /// Only drives the pixels that change, taking around a third of a second.
+    ///
+    /// This is the partial update waveform from the reference driver of the panel vendor,
+    /// and is intended for use at room temperature.
+    /// It leaves more ghosting than the built-in partial waveform, so a full refresh
+    /// should be done regularly.
+    pub const FAST_PARTIAL: Self = Self::new([
+        // Voltage selection
+        0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x80, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x40, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Phase timing
+        0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Frame rates and gate scan selection
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00, 0x00, //
+        // End option, gate voltage, source voltages, and VCOM
+        0x02, 0x17, 0x41, 0xB0, 0x32, 0x28,
+    ]);
+
+    /// The same as [`FAST_PARTIAL`](Self::FAST_PARTIAL) but with the phases twice as
+    /// long, since the particles move more slowly in the cold.
+    ///
+    /// This is experimental, since it is not from the panel vendor but only derived from
+    /// the room temperature waveform, and has not been characterized below 10 °C.
+    pub const FAST_PARTIAL_COLD: Self = Self::new([
+        // Voltage selection
+        0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x80, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x40, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Phase timing
+        0x1E, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x02, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Frame rates and gate scan selection
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00, 0x00, //
+        // End option, gate voltage, source voltages, and VCOM
+        0x02, 0x17, 0x41, 0xB0, 0x32, 0x28,
+    ]);
+
+    /// Renders four gray levels from a [`GrayFrameBuffer`](super::GrayFrameBuffer).
+    ///
+    /// Every pixel is first cleared by driving it black and then white, after which it
+    /// is driven black for a duration depending on its gray level.
+    /// The exact shades vary between panels and with temperature, and the grayscale
+    /// image will also fade more quickly than a black and white one.
+    ///
+    /// This is experimental, since the panel vendor provides no grayscale waveform for
+    /// this panel, so the phase timing was tuned by eye on a single panel at room
+    /// temperature.
+    pub const GRAYSCALE_4: Self = Self::new([
+        // Voltage selection
+        0x60, 0x54, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x60, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x60, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x60, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Phase timing
+        0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x01, //
+        0x10, 0x08, 0x00, 0x04, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // Frame rates and gate scan selection
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00, 0x00, //
+        // End option, gate voltage, source voltages, and VCOM
+        0x22, 0x17, 0x41, 0x00, 0x32, 0x20,
+    ]);
+
+    /// Creates a LUT from its raw bytes.
+    pub const fn new(data: [u8; Self::SIZE]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the raw bytes.
+    pub fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.data
+    }
+
+    /// Returns the waveform.
+    pub(super) fn waveform(&self) -> &[u8] {
+        &self.data[..Self::WAVEFORM_SIZE]
+    }
+
+    /// Returns the end option.
+    pub(super) fn end_option(&self) -> u8 {
+        self.data[Self::WAVEFORM_SIZE]
+    }
+
+    /// Returns the gate voltage.
+    pub(super) fn gate_voltage(&self) -> u8 {
+        self.data[Self::WAVEFORM_SIZE + 1]
+    }
+
+    /// Returns the source voltages.
+    pub(super) fn source_voltages(&self) -> &[u8] {
+        &self.data[Self::WAVEFORM_SIZE + 2..Self::WAVEFORM_SIZE + 5]
+    }
+
+    /// Returns the VCOM voltage.
+    pub(super) fn vcom(&self) -> u8 {
+        self.data[Self::WAVEFORM_SIZE + 5]
+    }
+}
+impl core::fmt::Debug for Lut {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Lut").finish_non_exhaustive()
+    }
+}
+
+/// A set of LUTs for different temperature ranges, from which the LUT to use is
+/// selected according to the ambient temperature.
+///
+/// # Example
+/// ```no_run
+/// use watchy::display::LutSet;
+///
+/// let lut = LutSet::FAST_PARTIAL.select(5).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LutSet<'a> {
+    /// The LUTs along with the minimum temperature in °C at which each applies, in
+    /// ascending order of temperature.
+    luts: &'a [(i8, Lut)],
+}
+impl<'a> LutSet<'a> {
+    /// The [fast partial](Lut::FAST_PARTIAL) LUTs, using the experimental
+    /// [cold](Lut::FAST_PARTIAL_COLD) variant below 10 °C.
+    pub const FAST_PARTIAL: LutSet<'static> =
+        LutSet::new(&[(0, Lut::FAST_PARTIAL_COLD), (10, Lut::FAST_PARTIAL)]);
+
+    /// Creates a set from LUTs along with the minimum temperature in °C at which each
+    /// applies, which must be in ascending order of temperature.
+    pub const fn new(luts: &'a [(i8, Lut)]) -> Self {
+        Self { luts }
+    }
+
+    /// Selects the LUT for a temperature in °C, or returns `None` if the temperature is
+    /// below that of every LUT.
+    pub fn select(&self, temperature: i8) -> Option<&'a Lut> {
+        self.luts
+            .iter()
+            .rev()
+            .find(|(min, _)| temperature >= *min)
+            .map(|(_, lut)| lut)
+    }
+}
@@ -0,0 +1,215 @@
+//! Rotation and mirroring of anything drawn on the display.
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// Clockwise rotation of the displayed image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// Not rotated.
+    #[default]
+    Deg0,
+    /// Rotated by 90°.
+    Deg90,
+    /// Rotated by 180°, such as for wearing the watch on the other wrist.
+    Deg180,
+    /// Rotated by 270°.
+    Deg270,
+}
+
+/// Orientation of the displayed image, which is mirrored before it is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Orientation {
+    /// The rotation.
+    pub rotation: Rotation,
+    /// Whether the image is mirrored horizontally, left to right.
+    pub mirror_horizontal: bool,
+    /// Whether the image is mirrored vertically, top to bottom.
+    pub mirror_vertical: bool,
+}
+impl Orientation {
+    /// Creates an orientation with only a rotation.
+    pub const fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            mirror_horizontal: false,
+            mirror_vertical: false,
+        }
+    }
+
+    /// Returns the size of the image as drawn for a display of the given size, which
+    /// swaps the width and height when rotated by 90° or 270°.
+    pub fn size(&self, display_size: Size) -> Size {
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => display_size,
+            Rotation::Deg90 | Rotation::Deg270 => {
+                Size::new(display_size.height, display_size.width)
+            }
+        }
+    }
+
+    /// Transforms a point as drawn into the corresponding point on a display of the given
+    /// size.
+    ///
+    /// # Example
+    /// ```
+    /// use embedded_graphics::prelude::*;
+    /// use watchy::display::{Orientation, Rotation};
+    ///
+    /// let size = Size::new(200, 100);
+    /// let transform = |orientation: Orientation, x, y| orientation.transform(Point::new(x, y), size);
+    ///
+    /// // The drawn top left corner moves clockwise around the display
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg0), 0, 0),
+    ///     Point::new(0, 0)
+    /// );
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg90), 0, 0),
+    ///     Point::new(199, 0)
+    /// );
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg180), 0, 0),
+    ///     Point::new(199, 99)
+    /// );
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg270), 0, 0),
+    ///     Point::new(0, 99)
+    /// );
+    ///
+    /// // The drawn image is 100 pixels wide when rotated by 90°
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg90), 99, 0),
+    ///     Point::new(199, 99)
+    /// );
+    /// assert_eq!(
+    ///     transform(Orientation::new(Rotation::Deg90), 10, 20),
+    ///     Point::new(179, 10)
+    /// );
+    ///
+    /// // Mirroring happens before rotation
+    /// let orientation = Orientation {
+    ///     rotation: Rotation::Deg90,
+    ///     mirror_horizontal: true,
+    ///     mirror_vertical: false,
+    /// };
+    /// assert_eq!(transform(orientation, 0, 0), Point::new(199, 99));
+    /// let orientation = Orientation {
+    ///     rotation: Rotation::Deg0,
+    ///     mirror_horizontal: true,
+    ///     mirror_vertical: true,
+    /// };
+    /// assert_eq!(transform(orientation, 10, 20), Point::new(189, 79));
+    /// ```
+    pub fn transform(&self, point: Point, display_size: Size) -> Point {
+        let size = self.size(display_size);
+        let (width, height) = (size.width as i32, size.height as i32);
+
+        let x = if self.mirror_horizontal {
+            width - 1 - point.x
+        } else {
+            point.x
+        };
+        let y = if self.mirror_vertical {
+            height - 1 - point.y
+        } else {
+            point.y
+        };
+
+        match self.rotation {
+            Rotation::Deg0 => Point::new(x, y),
+            Rotation::Deg90 => Point::new(height - 1 - y, x),
+            Rotation::Deg180 => Point::new(width - 1 - x, height - 1 - y),
+            Rotation::Deg270 => Point::new(y, width - 1 - x),
+        }
+    }
+}
+
+/// Wrapper around a display or [`FrameBuffer`](super::FrameBuffer) that rotates and
+/// mirrors everything drawn on it.
+///
+/// Drawing code is unaffected, since the wrapper reports the size of the image as drawn.
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::*};
+/// use watchy::display::{FrameBuffer, Orientation, Oriented, Rotation};
+///
+/// // Upside down for wearing on the other wrist
+/// let mut display = Oriented::new(FrameBuffer::new(), Orientation::new(Rotation::Deg180));
+/// Rectangle::new(Point::zero(), Size::new(50, 20))
+///     .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+///     .draw(&mut display)
+///     .unwrap();
+/// let frame = display.into_inner();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Oriented<D> {
+    /// The wrapped display.
+    target: D,
+    /// The orientation of everything drawn.
+    orientation: Orientation,
+}
+impl<D> Oriented<D> {
+    /// Wraps a display with an orientation.
+    pub fn new(target: D, orientation: Orientation) -> Self {
+        Self {
+            target,
+            orientation,
+        }
+    }
+
+    /// Returns the orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Changes the orientation, which only affects what is drawn afterwards.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Returns the wrapped display.
+    pub fn inner(&self) -> &D {
+        &self.target
+    }
+
+    /// Returns the wrapped display mutably, which is drawn on without any orientation.
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    /// Unwraps the display.
+    pub fn into_inner(self) -> D {
+        self.target
+    }
+}
+impl<D: Dimensions> OriginDimensions for Oriented<D> {
+    fn size(&self) -> Size {
+        self.orientation.size(self.target.bounding_box().size)
+    }
+}
+impl<D: DrawTarget> DrawTarget for Oriented<D> {
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let display_size = self.target.bounding_box().size;
+        let bounds = Rectangle::new(Point::zero(), self.size());
+        let orientation = self.orientation;
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| bounds.contains(*point))
+                .map(|Pixel(point, color)| {
+                    Pixel(orientation.transform(point, display_size), color)
+                }),
+        )
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
@@ -0,0 +1,743 @@
+//! Direct driver for the SSD1681 controller of the GDEH0154D67 e-Ink display.
+
+#[cfg(feature = "embassy")]
+use super::DoubleBuffer;
+use super::{FrameBuffer, GrayFrameBuffer, Lut};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+};
+use thiserror::Error;
+
+/// Raw SSD1681 commands and values not covered by the core driver crate.
+mod cmd {
+    /// Driver output control command.
+    pub const DRIVER_OUTPUT: u8 = 0x01;
+    /// Gate driving voltage control command.
+    pub const GATE_VOLTAGE: u8 = 0x03;
+    /// Source driving voltage control command.
+    pub const SOURCE_VOLTAGE: u8 = 0x04;
+    /// Deep sleep mode command.
+    pub const DEEP_SLEEP: u8 = 0x10;
+    /// Data entry mode command.
+    pub const DATA_ENTRY_MODE: u8 = 0x11;
+    /// Software reset command.
+    pub const SW_RESET: u8 = 0x12;
+    /// Temperature sensor selection command.
+    pub const TEMPERATURE_SENSOR: u8 = 0x18;
+    /// Write temperature register command.
+    pub const WRITE_TEMPERATURE: u8 = 0x1A;
+    /// Master activation command, which runs the display update sequence.
+    pub const MASTER_ACTIVATION: u8 = 0x20;
+    /// Display update sequence option command.
+    pub const UPDATE_CONTROL_2: u8 = 0x22;
+    /// Command to write to the RAM of the new frame.
+    pub const WRITE_RAM_NEW: u8 = 0x24;
+    /// Command to write to the RAM of the previous frame, against which the new frame
+    /// is compared during a partial update.
+    pub const WRITE_RAM_OLD: u8 = 0x26;
+    /// Write VCOM register command.
+    pub const WRITE_VCOM: u8 = 0x2C;
+    /// Write LUT register command.
+    pub const WRITE_LUT: u8 = 0x32;
+    /// Border waveform control command.
+    pub const BORDER_WAVEFORM: u8 = 0x3C;
+    /// End option command, which sets the voltages after the LUT.
+    pub const END_OPTION: u8 = 0x3F;
+    /// RAM X address start and end position command.
+    pub const RAM_X_RANGE: u8 = 0x44;
+    /// RAM Y address start and end position command.
+    pub const RAM_Y_RANGE: u8 = 0x45;
+    /// RAM X address counter command.
+    pub const RAM_X_COUNTER: u8 = 0x4E;
+    /// RAM Y address counter command.
+    pub const RAM_Y_COUNTER: u8 = 0x4F;
+
+    /// [`DEEP_SLEEP`] value for mode 1, which retains the RAM.
+    pub const DEEP_SLEEP_MODE_1: u8 = 0x01;
+    /// [`UPDATE_CONTROL_2`] value for a full update using display mode 1, after
+    /// which the analog circuits and oscillator are turned off.
+    pub const UPDATE_FULL: u8 = 0xF7;
+    /// [`UPDATE_CONTROL_2`] value for a partial update using display mode 2, after
+    /// which the analog circuits and oscillator are turned off.
+    pub const UPDATE_PARTIAL: u8 = 0xFF;
+    /// Bit of the [`UPDATE_CONTROL_2`] values that loads the temperature from the
+    /// sensor, which is cleared to instead use the temperature that was written.
+    pub const UPDATE_LOAD_TEMPERATURE: u8 = 0x20;
+    /// The same as [`UPDATE_FULL`] but using the LUT in the register rather than
+    /// loading it from OTP.
+    pub const UPDATE_FULL_CUSTOM: u8 = 0xC7;
+    /// The same as [`UPDATE_PARTIAL`] but using the LUT in the register rather than
+    /// loading it from OTP.
+    pub const UPDATE_PARTIAL_CUSTOM: u8 = 0xCF;
+
+    /// The sequence of commands and their data to configure the controller for the
+    /// 200x200 panel after a reset.
+    pub const INIT_SEQUENCE: &[(u8, &[u8])] = &[
+        // 200 gate lines, scanning from G0
+        (DRIVER_OUTPUT, &[0xC7, 0x00, 0x00]),
+        // Increment X then Y
+        (DATA_ENTRY_MODE, &[0x03]),
+        // Columns 0 to 24 in bytes
+        (RAM_X_RANGE, &[0x00, 0x18]),
+        // Rows 0 to 199
+        (RAM_Y_RANGE, &[0x00, 0x00, 0xC7, 0x00]),
+        // Follow the LUT
+        (BORDER_WAVEFORM, &[0x05]),
+        // Internal temperature sensor
+        (TEMPERATURE_SENSOR, &[0x80]),
+    ];
+}
+
+/// Error for display panel problems.
+#[derive(Error, Debug)]
+pub enum PanelError<S: core::fmt::Debug, P: core::fmt::Debug> {
+    /// An error on the SPI bus.
+    #[error("SPI bus error: {0:?}")]
+    Spi(S),
+    /// An error with one of the control pins.
+    #[error("Control pin error: {0:?}")]
+    Pin(P),
+    /// The refresh was refused because the temperature in °C is outside of the
+    /// [rated range](Panel::TEMPERATURE_RANGE) of the panel.
+    #[error("Temperature of {0} °C is outside of the rated range of the display")]
+    TemperatureOutOfRange(i8),
+    /// A grayscale refresh was attempted without a [custom LUT](Panel::load_lut) loaded.
+    #[error("No custom LUT is loaded for the grayscale refresh")]
+    NoLutLoaded,
+}
+
+/// How the display is refreshed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshMode {
+    /// Refreshes every pixel, which flashes the screen but clears any ghosting.
+    Full,
+    /// Only drives the pixels that differ from the previous frame, which does not
+    /// flash the screen but may leave some ghosting after many updates.
+    Partial,
+}
+
+/// Direct driver for the SSD1681 display controller.
+///
+/// This drives the controller itself rather than going through the core driver crate,
+/// which gives finer control over how the display is refreshed.
+/// The frame to display is drawn into a separate [`FrameBuffer`].
+///
+/// The controller keeps a copy of the previous frame for partial refreshes, which is
+/// kept up to date by every refresh done with this driver.
+/// Custom [LUTs](Lut) can be loaded for faster refreshes or for grayscale.
+/// After a deep sleep of the ESP32, the controller can be [resumed](Self::resume)
+/// rather than initialized so that the first refresh can still be partial, see
+/// [`RetainedFrame`](super::RetainedFrame).
+pub struct Panel<SPI, DC, RST, BUSY> {
+    /// The SPI device.
+    spi: SPI,
+    /// The data/command pin, which is high for data.
+    dc: DC,
+    /// The reset pin, which is active low.
+    reset: RST,
+    /// The busy pin, which is high while the controller is busy.
+    busy: BUSY,
+    /// Whether a custom LUT has been loaded, which is then used instead of the built-in
+    /// one.
+    lut_loaded: bool,
+    /// The ambient temperature in °C, if supplied rather than using the internal sensor.
+    temperature: Option<i8>,
+}
+impl<SPI, DC, RST, BUSY> Panel<SPI, DC, RST, BUSY> {
+    /// The range of temperatures in °C at which the panel is rated to refresh.
+    pub const TEMPERATURE_RANGE: core::ops::RangeInclusive<i8> = 0..=50;
+
+    /// Creates a new driver from its parts.
+    ///
+    /// It is recommended to use [`panel_driver`](super::panel_driver) instead, which
+    /// sets up the SPI bus and pins correctly.
+    pub fn new(spi: SPI, dc: DC, reset: RST, busy: BUSY) -> Self {
+        Self {
+            spi,
+            dc,
+            reset,
+            busy,
+            lut_loaded: false,
+            temperature: None,
+        }
+    }
+
+    /// Supplies the ambient temperature in °C, which is then written to the controller
+    /// before each refresh to select the built-in LUT, or `None` to go back to using
+    /// the internal temperature sensor of the controller.
+    ///
+    /// The die temperature of the [accelerometer](crate::accelerometer::AccelerometerRegisters::temperature)
+    /// is typically used, which the face `Runtime` supplies before each refresh.
+    /// Refreshes are refused with [`PanelError::TemperatureOutOfRange`] when the
+    /// temperature is outside of the [rated range](Self::TEMPERATURE_RANGE) of the panel,
+    /// since the display may otherwise be left in a poor state.
+    pub fn set_temperature(&mut self, temperature: Option<i8>) {
+        self.temperature = temperature;
+    }
+
+    /// Checks the supplied temperature and returns the data to write to the temperature
+    /// register, if any.
+    fn temperature_data<S: core::fmt::Debug, P: core::fmt::Debug>(
+        &self,
+    ) -> Result<Option<[u8; 2]>, PanelError<S, P>> {
+        match self.temperature {
+            Some(temperature) if !Self::TEMPERATURE_RANGE.contains(&temperature) => {
+                Err(PanelError::TemperatureOutOfRange(temperature))
+            }
+            // NOTE: The register is 12 bits in units of 1/16 °C, left justified.
+            temperature => Ok(temperature.map(|t| [t.to_be_bytes()[0], 0x00])),
+        }
+    }
+
+    /// Switches back to the LUT built into the controller, after a custom one was
+    /// [loaded](Self::load_lut).
+    pub fn use_builtin_lut(&mut self) {
+        self.lut_loaded = false;
+    }
+
+    /// Returns the display update sequence option for a refresh mode.
+    fn update_option(&self, mode: RefreshMode) -> u8 {
+        let option = match (mode, self.lut_loaded) {
+            (RefreshMode::Full, false) => cmd::UPDATE_FULL,
+            (RefreshMode::Partial, false) => cmd::UPDATE_PARTIAL,
+            (RefreshMode::Full, true) => cmd::UPDATE_FULL_CUSTOM,
+            (RefreshMode::Partial, true) => cmd::UPDATE_PARTIAL_CUSTOM,
+        };
+        if self.temperature.is_some() {
+            option & !cmd::UPDATE_LOAD_TEMPERATURE
+        } else {
+            option
+        }
+    }
+
+    /// Returns the SPI device and the data/command, reset, and busy pins.
+    pub fn release(self) -> (SPI, DC, RST, BUSY) {
+        (self.spi, self.dc, self.reset, self.busy)
+    }
+}
+
+impl<SPI, DC, RST, BUSY, E> Panel<SPI, DC, RST, BUSY>
+where
+    SPI: embedded_hal::spi::SpiDevice,
+    DC: OutputPin<Error = E>,
+    RST: OutputPin<Error = E>,
+    BUSY: InputPin<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Sends a command followed by its data.
+    fn command(&mut self, command: u8, data: &[u8]) -> Result<(), PanelError<SPI::Error, E>> {
+        self.dc.set_low().map_err(PanelError::Pin)?;
+        self.spi.write(&[command]).map_err(PanelError::Spi)?;
+        if !data.is_empty() {
+            self.dc.set_high().map_err(PanelError::Pin)?;
+            self.spi.write(data).map_err(PanelError::Spi)?;
+        }
+        Ok(())
+    }
+
+    /// Waits for the controller to no longer be busy by polling the busy pin.
+    fn wait_until_idle(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        while self.busy.is_high().map_err(PanelError::Pin)? {
+            delay.delay_ms(1);
+        }
+        Ok(())
+    }
+
+    /// Writes a frame to one of the controller RAMs.
+    fn write_ram(
+        &mut self,
+        command: u8,
+        frame: &FrameBuffer,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command(cmd::RAM_X_COUNTER, &[0x00])?;
+        self.command(cmd::RAM_Y_COUNTER, &[0x00, 0x00])?;
+        self.command(command, frame.as_bytes())
+    }
+
+    /// Writes the supplied temperature, if any, which first checks that it is within the
+    /// rated range.
+    fn write_temperature(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        match self.temperature_data()? {
+            Some(data) => self.command(cmd::WRITE_TEMPERATURE, &data),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the display update sequence and waits for it to complete.
+    fn update(
+        &mut self,
+        delay: &mut impl DelayNs,
+        option: u8,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command(cmd::UPDATE_CONTROL_2, &[option])?;
+        self.command(cmd::MASTER_ACTIVATION, &[])?;
+        self.wait_until_idle(delay)
+    }
+
+    /// Configures the controller after a reset.
+    fn configure(&mut self, delay: &mut impl DelayNs) -> Result<(), PanelError<SPI::Error, E>> {
+        self.lut_loaded = false;
+        self.command(cmd::SW_RESET, &[])?;
+        self.wait_until_idle(delay)?;
+        for (command, data) in cmd::INIT_SEQUENCE {
+            self.command(*command, data)?;
+        }
+        self.wait_until_idle(delay)
+    }
+
+    /// Pulses the reset pin and waits for the controller to start.
+    fn hardware_reset(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.reset.set_low().map_err(PanelError::Pin)?;
+        delay.delay_ms(10);
+        self.reset.set_high().map_err(PanelError::Pin)?;
+        delay.delay_ms(10);
+        self.wait_until_idle(delay)
+    }
+
+    /// Resets and configures the controller, which is required before the first full
+    /// refresh and to wake it from [sleep](Self::sleep).
+    pub fn init(&mut self, delay: &mut impl DelayNs) -> Result<(), PanelError<SPI::Error, E>> {
+        self.hardware_reset(delay)?;
+        self.configure(delay)
+    }
+
+    /// Configures the controller so that the first refresh can be partial, for example
+    /// after waking the ESP32 from deep sleep, in which case there is no need to
+    /// [initialize](Self::init) it first.
+    ///
+    /// If the `previous` frame is known, typically from a [`RetainedFrame`](super::RetainedFrame),
+    /// the controller is reset, which also wakes it from [sleep](Self::sleep), and the
+    /// frame is then written to the controller RAM against which the next partial
+    /// refresh is compared.
+    /// Otherwise, the frame still in the controller RAM is used without a hardware
+    /// reset, which requires that the controller remained powered and was not put to
+    /// sleep, and that the reset pin was held high while the ESP32 slept.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use watchy::display::{RefreshMode, RetainedFrame};
+    ///
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut panel = watchy::display::panel_driver(pin_sets.display, peripherals.spi2).unwrap();
+    /// let mut delay = watchy::hal::delay::Delay::new_default();
+    ///
+    /// let mut retained = RetainedFrame::take().unwrap();
+    /// let mut frame = match retained.previous() {
+    ///     Some(previous) => {
+    ///         panel.resume(&mut delay, Some(previous)).unwrap();
+    ///         previous.clone()
+    ///     }
+    ///     None => {
+    ///         panel.init(&mut delay).unwrap();
+    ///         Default::default()
+    ///     }
+    /// };
+    ///
+    /// // Draw on the frame...
+    ///
+    /// panel
+    ///     .refresh(&mut delay, &frame, RefreshMode::Partial)
+    ///     .unwrap();
+    /// retained.store(&frame);
+    /// ```
+    pub fn resume(
+        &mut self,
+        delay: &mut impl DelayNs,
+        previous: Option<&FrameBuffer>,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        match previous {
+            Some(frame) => {
+                self.hardware_reset(delay)?;
+                self.configure(delay)?;
+                self.write_ram(cmd::WRITE_RAM_OLD, frame)
+            }
+            None => {
+                self.wait_until_idle(delay)?;
+                // A software reset does not affect the RAM
+                self.configure(delay)
+            }
+        }
+    }
+
+    /// Displays a frame, blocking until the refresh is complete.
+    pub fn refresh(
+        &mut self,
+        delay: &mut impl DelayNs,
+        frame: &FrameBuffer,
+        mode: RefreshMode,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.write_temperature()?;
+        match mode {
+            RefreshMode::Full => {
+                self.write_ram(cmd::WRITE_RAM_OLD, frame)?;
+                self.write_ram(cmd::WRITE_RAM_NEW, frame)?;
+                self.update(delay, self.update_option(mode))
+            }
+            RefreshMode::Partial => {
+                self.write_ram(cmd::WRITE_RAM_NEW, frame)?;
+                self.update(delay, self.update_option(mode))?;
+                // The controller does not copy the new frame itself
+                self.write_ram(cmd::WRITE_RAM_OLD, frame)
+            }
+        }
+    }
+
+    /// Loads a custom LUT, which is then used for every refresh until the controller is
+    /// initialized or resumed, or [`use_builtin_lut`](Self::use_builtin_lut) is called.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use watchy::display::{FrameBuffer, LutSet, RefreshMode};
+    ///
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut panel = watchy::display::panel_driver(pin_sets.display, peripherals.spi2).unwrap();
+    /// let mut delay = watchy::hal::delay::Delay::new_default();
+    /// panel.init(&mut delay).unwrap();
+    ///
+    /// // The ambient temperature in °C
+    /// let temperature = 20;
+    /// panel
+    ///     .load_lut(
+    ///         &mut delay,
+    ///         LutSet::FAST_PARTIAL.select(temperature).unwrap(),
+    ///     )
+    ///     .unwrap();
+    /// panel
+    ///     .refresh(&mut delay, &FrameBuffer::new(), RefreshMode::Partial)
+    ///     .unwrap();
+    /// ```
+    pub fn load_lut(
+        &mut self,
+        delay: &mut impl DelayNs,
+        lut: &Lut,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command(cmd::WRITE_LUT, lut.waveform())?;
+        self.wait_until_idle(delay)?;
+        self.command(cmd::END_OPTION, &[lut.end_option()])?;
+        self.command(cmd::GATE_VOLTAGE, &[lut.gate_voltage()])?;
+        self.command(cmd::SOURCE_VOLTAGE, lut.source_voltages())?;
+        self.command(cmd::WRITE_VCOM, &[lut.vcom()])?;
+        self.lut_loaded = true;
+        Ok(())
+    }
+
+    /// Displays a grayscale frame, blocking until the refresh is complete.
+    ///
+    /// A grayscale LUT such as [`Lut::GRAYSCALE_4`] must have been
+    /// [loaded](Self::load_lut) first, otherwise [`PanelError::NoLutLoaded`] is returned.
+    /// Since the controller RAMs then no longer hold a black and white frame, the next
+    /// black and white refresh should be full.
+    pub fn refresh_grayscale(
+        &mut self,
+        delay: &mut impl DelayNs,
+        frame: &GrayFrameBuffer,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        if !self.lut_loaded {
+            return Err(PanelError::NoLutLoaded);
+        }
+        self.write_temperature()?;
+        let (high, low) = frame.planes();
+        self.write_ram(cmd::WRITE_RAM_OLD, high)?;
+        self.write_ram(cmd::WRITE_RAM_NEW, low)?;
+        self.update(delay, cmd::UPDATE_FULL_CUSTOM)
+    }
+
+    /// Puts the controller into deep sleep, retaining its RAM.
+    ///
+    /// The controller must then be initialized again before the next refresh.
+    pub fn sleep(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command(cmd::DEEP_SLEEP, &[cmd::DEEP_SLEEP_MODE_1])
+    }
+}
+
+#[cfg(feature = "embassy")]
+impl<SPI, DC, RST, BUSY, E> Panel<SPI, DC, RST, BUSY>
+where
+    SPI: embedded_hal_async::spi::SpiDevice,
+    DC: OutputPin<Error = E>,
+    RST: OutputPin<Error = E>,
+    BUSY: embedded_hal_async::digital::Wait<Error = E>,
+    E: core::fmt::Debug,
+{
+    /// Sends a command followed by its data.
+    async fn command_async(
+        &mut self,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.dc.set_low().map_err(PanelError::Pin)?;
+        self.spi.write(&[command]).await.map_err(PanelError::Spi)?;
+        if !data.is_empty() {
+            self.dc.set_high().map_err(PanelError::Pin)?;
+            self.spi.write(data).await.map_err(PanelError::Spi)?;
+        }
+        Ok(())
+    }
+
+    /// Asynchronously waits for the controller to no longer be busy.
+    async fn wait_until_idle_async(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        self.busy.wait_for_low().await.map_err(PanelError::Pin)
+    }
+
+    /// Writes a frame to one of the controller RAMs.
+    async fn write_ram_async(
+        &mut self,
+        command: u8,
+        frame: &FrameBuffer,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command_async(cmd::RAM_X_COUNTER, &[0x00]).await?;
+        self.command_async(cmd::RAM_Y_COUNTER, &[0x00, 0x00])
+            .await?;
+        self.command_async(command, frame.as_bytes()).await
+    }
+
+    /// Writes the supplied temperature, if any, which first checks that it is within the
+    /// rated range.
+    async fn write_temperature_async(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        match self.temperature_data()? {
+            Some(data) => self.command_async(cmd::WRITE_TEMPERATURE, &data).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the display update sequence and waits for it to complete.
+    async fn update_async(&mut self, option: u8) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command_async(cmd::UPDATE_CONTROL_2, &[option]).await?;
+        self.command_async(cmd::MASTER_ACTIVATION, &[]).await?;
+        self.wait_until_idle_async().await
+    }
+
+    /// Configures the controller after a reset.
+    async fn configure_async(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        self.lut_loaded = false;
+        self.command_async(cmd::SW_RESET, &[]).await?;
+        self.wait_until_idle_async().await?;
+        for (command, data) in cmd::INIT_SEQUENCE {
+            self.command_async(*command, data).await?;
+        }
+        self.wait_until_idle_async().await
+    }
+
+    /// Pulses the reset pin and waits for the controller to start.
+    async fn hardware_reset_async(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.reset.set_low().map_err(PanelError::Pin)?;
+        delay.delay_ms(10);
+        self.reset.set_high().map_err(PanelError::Pin)?;
+        delay.delay_ms(10);
+        self.wait_until_idle_async().await
+    }
+
+    /// Resets and configures the controller, which is required before the first full
+    /// refresh and to wake it from [sleep](Self::sleep_async).
+    ///
+    /// The short reset pulse uses the blocking `delay`, but the controller is awaited
+    /// while it is busy.
+    pub async fn init_async(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.hardware_reset_async(delay).await?;
+        self.configure_async().await
+    }
+
+    /// Configures the controller so that the first refresh can be partial, resetting it
+    /// first if the `previous` frame is known.
+    ///
+    /// See [`resume`](Self::resume) for details.
+    /// The short reset pulse uses the blocking `delay`, but the controller is awaited
+    /// while it is busy.
+    pub async fn resume_async(
+        &mut self,
+        delay: &mut impl DelayNs,
+        previous: Option<&FrameBuffer>,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        match previous {
+            Some(frame) => {
+                self.hardware_reset_async(delay).await?;
+                self.configure_async().await?;
+                self.write_ram_async(cmd::WRITE_RAM_OLD, frame).await
+            }
+            None => {
+                self.wait_until_idle_async().await?;
+                // A software reset does not affect the RAM
+                self.configure_async().await
+            }
+        }
+    }
+
+    /// Displays a frame, waiting for the refresh to complete without blocking.
+    ///
+    /// A full refresh takes around two seconds, during which other tasks can continue
+    /// to run while this awaits the busy pin.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::*};
+    /// use watchy::display::{FrameBuffer, RefreshMode};
+    ///
+    /// # async fn example() {
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut panel = watchy::display::panel_driver(pin_sets.display, peripherals.spi2).unwrap();
+    /// panel
+    ///     .init_async(&mut watchy::hal::delay::Delay::new_default())
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// let mut frame = FrameBuffer::new();
+    /// Circle::new(Point::new(50, 50), 100)
+    ///     .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+    ///     .draw(&mut frame)
+    ///     .unwrap();
+    /// panel
+    ///     .refresh_async(&frame, RefreshMode::Full)
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub async fn refresh_async(
+        &mut self,
+        frame: &FrameBuffer,
+        mode: RefreshMode,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.write_temperature_async().await?;
+        match mode {
+            RefreshMode::Full => {
+                self.write_ram_async(cmd::WRITE_RAM_OLD, frame).await?;
+                self.write_ram_async(cmd::WRITE_RAM_NEW, frame).await?;
+                self.update_async(self.update_option(mode)).await
+            }
+            RefreshMode::Partial => {
+                self.write_ram_async(cmd::WRITE_RAM_NEW, frame).await?;
+                self.update_async(self.update_option(mode)).await?;
+                // The controller does not copy the new frame itself
+                self.write_ram_async(cmd::WRITE_RAM_OLD, frame).await
+            }
+        }
+    }
+
+    /// Presents the frame drawn into the back buffer, while drawing the next frame.
+    ///
+    /// The buffers are first swapped, after which the new front buffer is sent to the
+    /// display and its refresh is started.
+    /// `draw` then draws the next frame into the new back buffer while the controller
+    /// refreshes the display on its own, so the drawing always overlaps the refresh
+    /// rather than depending on how the SPI bus transfers the frame.
+    /// A frame is sent in around 2 ms at 20 MHz, whereas a partial refresh takes a few
+    /// hundred milliseconds and a full refresh around two seconds, so drawing that takes
+    /// less than the refresh adds nothing to the time between frames.
+    ///
+    /// Since `draw` is not async, other tasks do not run while it draws.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::*};
+    /// use watchy::display::{DoubleBuffer, RefreshMode};
+    ///
+    /// # async fn example() {
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut panel = watchy::display::panel_driver(pin_sets.display, peripherals.spi2).unwrap();
+    /// panel
+    ///     .init_async(&mut watchy::hal::delay::Delay::new_default())
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// let mut buffers = DoubleBuffer::new();
+    /// for size in 1..10 {
+    ///     panel
+    ///         .present_async(&mut buffers, RefreshMode::Partial, |frame| {
+    ///             frame.clear(BinaryColor::Off).unwrap();
+    ///             Circle::new(Point::new(0, 0), size * 20)
+    ///                 .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+    ///                 .draw(frame)
+    ///                 .unwrap();
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// # }
+    /// ```
+    pub async fn present_async(
+        &mut self,
+        buffers: &mut DoubleBuffer,
+        mode: RefreshMode,
+        draw: impl FnOnce(&mut FrameBuffer),
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        self.write_temperature_async().await?;
+        buffers.swap();
+        let (front, back) = buffers.split_mut();
+
+        self.write_ram_async(cmd::WRITE_RAM_NEW, front).await?;
+        if mode == RefreshMode::Full {
+            self.write_ram_async(cmd::WRITE_RAM_OLD, front).await?;
+        }
+        self.command_async(cmd::UPDATE_CONTROL_2, &[self.update_option(mode)])
+            .await?;
+        self.command_async(cmd::MASTER_ACTIVATION, &[]).await?;
+        // NOTE: The controller only reports when the refresh is complete on the busy
+        // pin, which is checked once the next frame is drawn.
+        draw(back);
+        self.wait_until_idle_async().await?;
+        if mode == RefreshMode::Partial {
+            // The controller does not copy the new frame itself
+            self.write_ram_async(cmd::WRITE_RAM_OLD, front).await?;
+        }
+        Ok(())
+    }
+
+    /// Loads a custom LUT, which is then used for every refresh until the controller is
+    /// initialized or resumed, or [`use_builtin_lut`](Self::use_builtin_lut) is called.
+    ///
+    /// See [`load_lut`](Self::load_lut) for details.
+    pub async fn load_lut_async(&mut self, lut: &Lut) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command_async(cmd::WRITE_LUT, lut.waveform()).await?;
+        self.wait_until_idle_async().await?;
+        self.command_async(cmd::END_OPTION, &[lut.end_option()])
+            .await?;
+        self.command_async(cmd::GATE_VOLTAGE, &[lut.gate_voltage()])
+            .await?;
+        self.command_async(cmd::SOURCE_VOLTAGE, lut.source_voltages())
+            .await?;
+        self.command_async(cmd::WRITE_VCOM, &[lut.vcom()]).await?;
+        self.lut_loaded = true;
+        Ok(())
+    }
+
+    /// Displays a grayscale frame, waiting for the refresh to complete without blocking.
+    ///
+    /// See [`refresh_grayscale`](Self::refresh_grayscale) for details.
+    pub async fn refresh_grayscale_async(
+        &mut self,
+        frame: &GrayFrameBuffer,
+    ) -> Result<(), PanelError<SPI::Error, E>> {
+        if !self.lut_loaded {
+            return Err(PanelError::NoLutLoaded);
+        }
+        self.write_temperature_async().await?;
+        let (high, low) = frame.planes();
+        self.write_ram_async(cmd::WRITE_RAM_OLD, high).await?;
+        self.write_ram_async(cmd::WRITE_RAM_NEW, low).await?;
+        self.update_async(cmd::UPDATE_FULL_CUSTOM).await
+    }
+
+    /// Puts the controller into deep sleep, retaining its RAM.
+    ///
+    /// The controller must then be initialized again before the next refresh.
+    pub async fn sleep_async(&mut self) -> Result<(), PanelError<SPI::Error, E>> {
+        self.command_async(cmd::DEEP_SLEEP, &[cmd::DEEP_SLEEP_MODE_1])
+            .await
+    }
+}
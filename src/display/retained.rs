@@ -0,0 +1,88 @@
+//! Retention of the displayed frame in RTC memory across deep sleep.
+
+use super::FrameBuffer;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Value marking that the retained frame is valid.
+///
+/// The RTC memory is not initialized on a cold boot, so it could otherwise contain
+/// anything.
+const MAGIC: u32 = 0x5741_5443;
+
+/// The contents of the RTC memory.
+struct Storage {
+    /// Set to [`MAGIC`] only when the frame is valid.
+    magic: u32,
+    /// The last frame displayed before deep sleep.
+    frame: FrameBuffer,
+}
+
+/// The storage in RTC memory, which is neither initialized nor cleared when waking
+/// from deep sleep.
+#[cfg_attr(feature = "esp-idf", link_section = ".rtc_noinit")]
+#[cfg_attr(feature = "esp-hal", link_section = ".rtc_slow.persistent")]
+static mut STORAGE: Storage = Storage {
+    magic: 0,
+    frame: FrameBuffer::new(),
+};
+
+/// Whether the [`RetainedFrame`] has already been taken.
+static TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// The last displayed frame, retained in RTC memory while the ESP32 is in deep sleep.
+///
+/// After waking, the previous frame is used to [resume](super::Panel::resume) the
+/// display without a hardware reset, so that the first refresh can be partial rather
+/// than flashing the whole screen.
+/// The frame should be [stored](Self::store) after every refresh.
+///
+/// This occupies around 5 kB of the 8 kB of RTC slow memory.
+/// Only one instance can exist, which is obtained using [`take`](Self::take).
+pub struct RetainedFrame {
+    /// Prevents construction outside of [`take`](Self::take).
+    _private: (),
+}
+impl RetainedFrame {
+    /// Takes the retained frame, which returns [`None`] if it was already taken.
+    pub fn take() -> Option<Self> {
+        (!TAKEN.swap(true, Ordering::AcqRel)).then_some(Self { _private: () })
+    }
+
+    /// Returns the retained frame, or [`None`] if there is no valid frame, such as after
+    /// a cold boot.
+    pub fn previous(&self) -> Option<&FrameBuffer> {
+        // SAFETY: Only the single instance accesses the storage, and every bit pattern
+        // is valid for it.
+        let storage = unsafe { &*core::ptr::addr_of!(STORAGE) };
+        (storage.magic == MAGIC).then_some(&storage.frame)
+    }
+
+    /// Stores the frame that is now displayed.
+    pub fn store(&mut self, frame: &FrameBuffer) {
+        // SAFETY: Only the single instance accesses the storage, and it is borrowed
+        // mutably here.
+        let storage = unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) };
+        // Invalidate first so that a reset part way through leaves no partial frame
+        storage.magic = 0;
+        storage.frame.clone_from(frame);
+        storage.magic = MAGIC;
+    }
+
+    /// Discards the stored frame, so that the display will be fully initialized after
+    /// the next wake.
+    pub fn invalidate(&mut self) {
+        // SAFETY: Only the single instance accesses the storage, and it is borrowed
+        // mutably here.
+        unsafe { (*core::ptr::addr_of_mut!(STORAGE)).magic = 0 };
+    }
+}
+
+/// Discards the stored frame even if the [`RetainedFrame`] has been taken, for when the
+/// display shows something else before deep sleep and nothing else will run, such as
+/// after a panic.
+#[cfg(all(feature = "esp-idf", feature = "std"))]
+pub(crate) fn discard() {
+    // SAFETY: Any instance is never used again, and any bit pattern of the magic value
+    // is valid.
+    unsafe { (*core::ptr::addr_of_mut!(STORAGE)).magic = 0 };
+}
@@ -0,0 +1,257 @@
+//! Structure for writing watch faces.
+//!
+//! A watch face implements [`WatchFace`], which draws it for a [`FaceContext`] holding
+//! the time, battery status, step count, next alarm, and why the watch woke up.
+//! The face can also react to button presses.
+//!
+//! With the `esp-idf` feature, the [`Runtime`] owns the drivers and does everything
+//! else.
+//! Each time the ESP32 wakes from deep sleep, on the minute or when a button is pressed,
+//! it reads the context, has the face draw into a framebuffer, refreshes the display
+//! partially or fully as needed, and then returns to deep sleep until the next minute.
+//! With the `sim` feature, the `Simulator` does the same on the host for a simulated
+//! watch, fast-forwarding through deep sleep, and [`Golden`] compares the images that
+//! it displays against golden images to catch regressions.
+//!
+//! Since the ESP32 restarts after deep sleep, the face is created anew on every wake.
+//! Any state that must survive between wakes needs to be kept elsewhere, such as in RTC
+//! memory.
+//!
+//! Common information, such as the battery level, date, step count, and next alarm, can
+//! be drawn by the built-in [`Complication`]s, each in a [`Slot`] with an area of the
+//! face.
+//! The step count is read from the accelerometer, and the next alarm from the daily
+//! alarm of the RTC chip.
+//! Since the face is drawn whole on every wake, the [`Runtime`] compares the frame with
+//! that last displayed, so that the display is only refreshed when a slot or anything
+//! else changed, and a partial refresh only changes the pixels that differ.
+
+mod complication;
+#[cfg(feature = "sim")]
+mod golden;
+mod layout;
+#[cfg(feature = "esp-idf")]
+mod runtime;
+#[cfg(feature = "sim")]
+mod simulator;
+
+pub use complication::{Battery, Complication, Date, NextAlarm, Slot, Steps};
+#[cfg(feature = "sim")]
+pub use golden::{Golden, GoldenError};
+pub use layout::{
+    ComplicationKind, Element, Elements, Layout, LayoutError, LayoutFont, TextPlacement,
+};
+#[cfg(feature = "esp-idf")]
+pub use runtime::{Runtime, RuntimeError};
+#[cfg(feature = "sim")]
+pub use simulator::{Simulator, SimulatorError};
+
+use crate::{battery::BatteryStatus, button::Button, display::RefreshMode, rtc::DateTime};
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+use crate::{
+    display::{FrameBuffer, Orientation},
+    rtc::RtcError,
+    traits::{BatteryMonitor, Clock, MotionSensor},
+};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+/// Why the watch woke up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeReason {
+    /// The ESP32 was powered on or reset rather than waking from deep sleep.
+    Boot,
+    /// The minute ticked over.
+    Tick,
+    /// A button was pressed.
+    Button(Button),
+    /// Something else woke the ESP32.
+    Other,
+}
+
+/// Everything that a [`WatchFace`] needs to know to draw itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceContext {
+    /// The current date and time, or `None` if the clock has not been set since it last
+    /// lost power.
+    pub now: Option<DateTime>,
+    /// The battery status.
+    pub battery: BatteryStatus,
+    /// The number of steps taken, or `None` if no step count is available.
+    pub steps: Option<u32>,
+    /// When the daily alarm of the RTC chip next goes off, or `None` if no alarm is set
+    /// or the clock has not been set.
+    pub next_alarm: Option<DateTime>,
+    /// Why the watch woke up.
+    pub wake_reason: WakeReason,
+}
+
+/// A watch face, which is drawn every minute and whenever a button is pressed.
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     text::{Alignment, Baseline, Text, TextStyleBuilder},
+/// };
+/// use watchy::{
+///     face::{FaceContext, WatchFace},
+///     text::{FontStyle, LARGE_DIGITS},
+/// };
+///
+/// struct Digital;
+/// impl WatchFace for Digital {
+///     fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+///     where
+///         D: DrawTarget<Color = BinaryColor>,
+///     {
+///         let time = match ctx.now {
+///             Some(now) => format!("{:02}:{:02}", now.hour, now.minute),
+///             None => "--:--".into(),
+///         };
+///         Text::with_text_style(
+///             &time,
+///             target.bounding_box().center(),
+///             FontStyle::new(&LARGE_DIGITS, BinaryColor::On),
+///             TextStyleBuilder::new()
+///                 .alignment(Alignment::Center)
+///                 .baseline(Baseline::Middle)
+///                 .build(),
+///         )
+///         .draw(target)?;
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait WatchFace {
+    /// Draws the face onto a target that has been cleared to white.
+    fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>;
+
+    /// Handles a button press, returning whether the face needs to be drawn again.
+    ///
+    /// By default, the face is always drawn again.
+    fn button(&mut self, button: Button, ctx: &FaceContext) -> bool {
+        let _ = (button, ctx);
+        true
+    }
+
+    /// Returns how the display should be refreshed after drawing, or `None` to leave
+    /// this up to the runtime.
+    ///
+    /// By default, the runtime decides, which refreshes partially unless the display
+    /// needs to be cleared of ghosting.
+    fn refresh_mode(&self, ctx: &FaceContext) -> Option<RefreshMode> {
+        let _ = ctx;
+        None
+    }
+}
+
+/// Configuration of the watch face runtime and simulator.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// The number of partial refreshes after which the next refresh is full to clear
+    /// any ghosting, unless the face decides otherwise.
+    pub full_refresh_interval: u32,
+    /// The orientation in which the face is drawn.
+    pub orientation: Orientation,
+}
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            // About once an hour
+            full_refresh_interval: 60,
+            orientation: Orientation::default(),
+        }
+    }
+}
+
+/// An error reading the [`FaceContext`] from one of the drivers.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+enum ContextError<R: core::fmt::Debug, B, M> {
+    /// An error reading the RTC, other than it not having been set.
+    Rtc(RtcError<R>),
+    /// An error sampling the battery voltage.
+    Battery(B),
+    /// An error reading the accelerometer.
+    Motion(M),
+}
+
+/// Reads the context for a face from the drivers, so that the runtime and the simulator
+/// build it in the same way.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+fn read_context<C: Clock, B: BatteryMonitor, M: MotionSensor>(
+    clock: &mut C,
+    battery: &mut B,
+    motion: &mut M,
+    wake_reason: WakeReason,
+) -> Result<FaceContext, ContextError<C::BusError, B::Error, M::Error>> {
+    let now = match clock.now() {
+        Ok(now) => Some(now),
+        Err(RtcError::ClockStopped) => None,
+        Err(error) => return Err(ContextError::Rtc(error)),
+    };
+    let alarm = clock.alarm().map_err(ContextError::Rtc)?;
+
+    Ok(FaceContext {
+        now,
+        battery: battery.status().map_err(ContextError::Battery)?,
+        steps: motion.steps().map_err(ContextError::Motion)?,
+        next_alarm: now.zip(alarm).map(|(now, alarm)| alarm.next(&now)),
+        wake_reason,
+    })
+}
+
+/// The second of the minute before which a timer wake is treated as the minute ticking
+/// over.
+///
+/// NOTE: The timer runs from the RTC slow clock, which can be several percent out, so a
+/// later wake is taken to be early for the next minute.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+const LATE_TICK_SECOND: u8 = 30;
+
+/// Passes any button press to a face, returning whether it needs to be drawn after
+/// waking.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+fn needs_drawing(face: &mut impl WatchFace, ctx: &FaceContext) -> bool {
+    match ctx.wake_reason {
+        WakeReason::Button(button) => face.button(button, ctx),
+        WakeReason::Tick => ctx.now.is_none_or(|now| now.second < LATE_TICK_SECOND),
+        WakeReason::Boot | WakeReason::Other => true,
+    }
+}
+
+/// Returns how to refresh the display with a newly drawn frame, or `None` if it does not
+/// need to be refreshed.
+///
+/// The `requested` mode from the face is used if the `previous` frame on the display is
+/// known, and otherwise the refresh is partial unless the frame is unchanged or there
+/// have been enough `partial_refreshes` in a row.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+fn choose_refresh_mode(
+    requested: Option<RefreshMode>,
+    previous: Option<&FrameBuffer>,
+    frame: &FrameBuffer,
+    partial_refreshes: u32,
+    config: &RuntimeConfig,
+) -> Option<RefreshMode> {
+    Some(match (requested, previous) {
+        // The display must be fully refreshed if its contents are not known
+        (_, None) => RefreshMode::Full,
+        (Some(mode), Some(_)) => mode,
+        (None, Some(previous)) if previous == frame => return None,
+        (None, Some(_)) if partial_refreshes >= config.full_refresh_interval => RefreshMode::Full,
+        (None, Some(_)) => RefreshMode::Partial,
+    })
+}
+
+/// Returns the number of seconds to sleep in order to wake just after the minute ticks
+/// over.
+#[cfg(any(feature = "esp-idf", feature = "sim"))]
+fn seconds_until_tick(now: Option<DateTime>) -> u64 {
+    let second = now.map_or(0, |now| now.second);
+    u64::from(60 - second.min(59))
+}
@@ -0,0 +1,286 @@
+//! Complications, which are small pieces of information drawn in slots of a face.
+
+use super::FaceContext;
+use crate::{
+    rtc::DateTime,
+    text::{FontStyle, Formatted, SMALL},
+};
+use embedded_graphics::{
+    geometry::AnchorX,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyleBuilder, Rectangle, StrokeAlignment},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// The abbreviated names of the days of the week, starting with Sunday.
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+/// The abbreviated names of the months.
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Formats a date such as "Mon 19 Oct".
+pub(super) fn format_date(date: &DateTime) -> Formatted<16> {
+    let weekday = date.weekday().map_or("---", |weekday| {
+        WEEKDAYS[usize::from(weekday.number_from_sunday())]
+    });
+    let month = MONTHS[usize::from(date.month.clamp(1, 12) - 1)];
+    Formatted::new(format_args!("{weekday} {} {month}", date.day))
+}
+
+/// A piece of information, such as the battery level or date, drawn in a [`Slot`] of a
+/// watch face.
+///
+/// The information shown is its [`Value`](Self::Value), which is read from the context
+/// separately from drawing it.
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+/// use watchy::face::{Complication, FaceContext};
+///
+/// /// Shows a dot when the battery is low.
+/// struct LowBattery;
+/// impl Complication for LowBattery {
+///     type Value = bool;
+///
+///     fn value(&self, ctx: &FaceContext) -> bool {
+///         ctx.battery.percentage() < 10
+///     }
+///
+///     fn draw<D>(&self, low: &bool, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+///     where
+///         D: DrawTarget<Color = BinaryColor>,
+///     {
+///         if *low {
+///             target.fill_solid(&area, BinaryColor::On)?;
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Complication {
+    /// The information shown.
+    type Value;
+
+    /// Returns the information to show.
+    fn value(&self, ctx: &FaceContext) -> Self::Value;
+
+    /// Draws the information within an area that has been cleared to white.
+    fn draw<D>(&self, value: &Self::Value, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>;
+}
+
+/// Draws text in the middle of an area in the small font.
+fn draw_text<D>(text: &str, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    Text::with_text_style(
+        text,
+        area.center(),
+        FontStyle::new(&SMALL, BinaryColor::On),
+        TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build(),
+    )
+    .draw(&mut target.clipped(&area))?;
+    Ok(())
+}
+
+/// A battery icon filled according to the charge percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Battery;
+impl Complication for Battery {
+    type Value = u8;
+
+    fn value(&self, ctx: &FaceContext) -> u8 {
+        ctx.battery.percentage()
+    }
+
+    fn draw<D>(&self, percentage: &u8, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        // The terminal sticks out of the middle of the right side
+        let terminal = Size::new((area.size.width / 12).max(2), area.size.height / 2);
+        let body = area.resized_width(
+            area.size.width.saturating_sub(terminal.width),
+            AnchorX::Left,
+        );
+        body.into_styled(
+            PrimitiveStyleBuilder::new()
+                .stroke_color(BinaryColor::On)
+                .stroke_width(2)
+                .stroke_alignment(StrokeAlignment::Inside)
+                .build(),
+        )
+        .draw(target)?;
+        target.fill_solid(
+            &Rectangle::new(
+                Point::new(
+                    body.top_left.x + body.size.width as i32,
+                    area.center().y - terminal.height as i32 / 2,
+                ),
+                terminal,
+            ),
+            BinaryColor::On,
+        )?;
+
+        let inside = body.offset(-4);
+        let width = inside.size.width * u32::from(*percentage).min(100) / 100;
+        target.fill_solid(&inside.resized_width(width, AnchorX::Left), BinaryColor::On)
+    }
+}
+
+/// The number of steps taken, which is blank if there is no step count.
+///
+/// The step count is read from the step counter of the accelerometer, and passed to the
+/// face in the [`FaceContext`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Steps;
+impl Complication for Steps {
+    type Value = Option<u32>;
+
+    fn value(&self, ctx: &FaceContext) -> Option<u32> {
+        ctx.steps
+    }
+
+    fn draw<D>(&self, steps: &Option<u32>, area: Rectangle, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        match steps {
+            Some(steps) => draw_text(
+                Formatted::<16>::new(format_args!("{steps} steps")).as_str(),
+                area,
+                target,
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The date, such as "Mon 19 Oct", which is blank if the clock has not been set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Date;
+impl Complication for Date {
+    type Value = Option<DateTime>;
+
+    fn value(&self, ctx: &FaceContext) -> Option<DateTime> {
+        // Only the date is shown
+        ctx.now.map(|now| DateTime {
+            hour: 0,
+            minute: 0,
+            second: 0,
+            ..now
+        })
+    }
+
+    fn draw<D>(
+        &self,
+        date: &Option<DateTime>,
+        area: Rectangle,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let Some(date) = date else {
+            return Ok(());
+        };
+        draw_text(format_date(date).as_str(), area, target)
+    }
+}
+
+/// The time of the next alarm, which is blank if no alarm is set.
+///
+/// This is the daily [alarm](crate::rtc::Alarm) of the RTC chip, which is passed to the
+/// face in the [`FaceContext`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NextAlarm;
+impl Complication for NextAlarm {
+    /// The hour and minute.
+    type Value = Option<(u8, u8)>;
+
+    fn value(&self, ctx: &FaceContext) -> Option<(u8, u8)> {
+        ctx.next_alarm.map(|alarm| (alarm.hour, alarm.minute))
+    }
+
+    fn draw<D>(
+        &self,
+        alarm: &Option<(u8, u8)>,
+        area: Rectangle,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        match alarm {
+            Some((hour, minute)) => draw_text(
+                Formatted::<16>::new(format_args!("Alarm {hour:02}:{minute:02}")).as_str(),
+                area,
+                target,
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An area of a watch face in which a [`Complication`] is drawn.
+#[derive(Debug, Clone)]
+pub struct Slot<C: Complication> {
+    /// The complication.
+    complication: C,
+    /// The area.
+    area: Rectangle,
+}
+impl<C: Complication> Slot<C> {
+    /// Creates a slot.
+    pub const fn new(complication: C, area: Rectangle) -> Self {
+        Self { complication, area }
+    }
+
+    /// Returns the area.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+
+    /// Returns the complication.
+    pub fn complication(&self) -> &C {
+        &self.complication
+    }
+
+    /// Clears the area and draws the complication in it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+    /// use watchy::face::{Battery, Date, FaceContext, Slot, WatchFace};
+    ///
+    /// struct Face {
+    ///     battery: Slot<Battery>,
+    ///     date: Slot<Date>,
+    /// }
+    /// impl WatchFace for Face {
+    ///     fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    ///     where
+    ///         D: DrawTarget<Color = BinaryColor>,
+    ///     {
+    ///         self.battery.draw(ctx, target)?;
+    ///         self.date.draw(ctx, target)
+    ///     }
+    /// }
+    /// ```
+    pub fn draw<D>(&self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        target.fill_solid(&self.area, BinaryColor::Off)?;
+        self.complication
+            .draw(&self.complication.value(ctx), self.area, target)
+    }
+}
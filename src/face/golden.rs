@@ -0,0 +1,264 @@
+//! Regression testing of watch faces against golden images.
+
+use super::{RuntimeConfig, Simulator, SimulatorError, WatchFace};
+use crate::{
+    display::{DecodeError, DecodedImage, Diff, FrameBuffer},
+    sim::{self, Watch},
+};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Error for golden image test problems.
+#[derive(Error, Debug)]
+pub enum GoldenError {
+    /// The image differs from the golden image.
+    #[error("{pixels} pixels differ from {}, see {}", golden.display(), diff.display())]
+    Mismatch {
+        /// The path of the golden image.
+        golden: PathBuf,
+        /// The path of the written image of the pixels that differ.
+        diff: PathBuf,
+        /// The number of pixels that differ.
+        pixels: usize,
+    },
+    /// There is no golden image yet.
+    #[error("{} does not exist, check {} and bless it if it looks correct", golden.display(), actual.display())]
+    Missing {
+        /// The path of the golden image.
+        golden: PathBuf,
+        /// The path of the written image.
+        actual: PathBuf,
+    },
+    /// The golden image is not a valid monochrome image.
+    #[error("Golden image {} is invalid: {source}", golden.display())]
+    Invalid {
+        /// The path of the golden image.
+        golden: PathBuf,
+        /// The problem decoding the image.
+        source: DecodeError,
+    },
+    /// An error simulating the watch face.
+    #[error("Simulator error: {0}")]
+    Simulator(#[from] SimulatorError),
+    /// An error reading or writing an image.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A directory of golden PBM images, against which the displayed images of watch faces
+/// are compared to catch regressions without the hardware.
+///
+/// Each image has a name, such as `morning` for `morning.pbm`.
+/// When an image does not match, the image is written next to the golden image as
+/// `morning.actual.pbm`, along with `morning.diff.pbm`, in which the pixels that differ
+/// are black.
+///
+/// When blessing, the golden images are instead overwritten with the current images,
+/// which should then be checked by eye before being committed.
+/// This is the case if the `WATCHY_BLESS` environment variable is set, as for the tests
+/// of the assets crate, so that all of the goldens can be updated with
+/// `WATCHY_BLESS=1 cargo test`.
+///
+/// # Example
+/// ```no_run
+/// use watchy::{face::Golden, rtc::DateTime, sim::Watch};
+///
+/// # struct Digital;
+/// # impl watchy::face::WatchFace for Digital {
+/// #     fn draw<D>(
+/// #         &mut self,
+/// #         _: &watchy::face::FaceContext,
+/// #         _: &mut D,
+/// #     ) -> Result<(), D::Error>
+/// #     where
+/// #         D: embedded_graphics::prelude::DrawTarget<
+/// #             Color = embedded_graphics::pixelcolor::BinaryColor,
+/// #         >,
+/// #     {
+/// #         Ok(())
+/// #     }
+/// # }
+/// #[test]
+/// fn low_battery_in_the_morning() {
+///     let watch = Watch::new();
+///     watch.set_time(&DateTime {
+///         year: 2024,
+///         month: 10,
+///         day: 19,
+///         hour: 7,
+///         minute: 55,
+///         second: 0,
+///     });
+///     watch.set_battery_voltage(3500);
+///     watch.set_steps(1234);
+///
+///     Golden::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"))
+///         .check_face(&mut Digital, &watch, "low_battery_morning")
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Golden {
+    /// The directory of the golden images.
+    directory: PathBuf,
+    /// Whether to overwrite the golden images rather than compare against them.
+    bless: bool,
+    /// The configuration with which faces are simulated.
+    config: RuntimeConfig,
+}
+impl Golden {
+    /// The environment variable that enables blessing when set.
+    pub const BLESS_VAR: &'static str = "WATCHY_BLESS";
+
+    /// Uses the golden images in a directory, blessing them if the
+    /// [environment variable](Self::BLESS_VAR) is set.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            bless: std::env::var_os(Self::BLESS_VAR).is_some(),
+            config: RuntimeConfig::default(),
+        }
+    }
+
+    /// Sets whether to overwrite the golden images rather than compare against them.
+    pub fn with_bless(mut self, bless: bool) -> Self {
+        self.bless = bless;
+        self
+    }
+
+    /// Replaces the configuration with which faces are simulated, such as to test a
+    /// rotated face.
+    pub fn with_config(mut self, config: RuntimeConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Boots a simulated watch, which draws the face for its time, battery voltage, and
+    /// step count, and then checks the displayed image against the golden image.
+    ///
+    /// To check the face after button presses or as time passes, drive a [`Simulator`]
+    /// instead and [check](Self::check) the image that its watch displays.
+    ///
+    /// # Example
+    /// ```
+    /// use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+    /// use watchy::{
+    ///     display::DecodedImage,
+    ///     face::{FaceContext, Golden, GoldenError, WatchFace},
+    ///     sim::Watch,
+    /// };
+    ///
+    /// /// Shows the battery level as a bar.
+    /// struct Bar;
+    /// impl WatchFace for Bar {
+    ///     fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    ///     where
+    ///         D: DrawTarget<Color = BinaryColor>,
+    ///     {
+    ///         let size = Size::new(ctx.battery.percentage().into(), 10);
+    ///         target.fill_solid(&Rectangle::new(Point::zero(), size), BinaryColor::On)
+    ///     }
+    /// }
+    ///
+    /// let directory = std::env::temp_dir().join(format!("watchy-{}", std::process::id()));
+    /// std::fs::create_dir_all(&directory).unwrap();
+    /// let golden = Golden::new(&directory).with_bless(false);
+    /// let watch = Watch::new();
+    /// watch.set_battery_voltage(3800);
+    ///
+    /// // There is nothing to compare against until the image is blessed
+    /// assert!(matches!(
+    ///     golden.check_face(&mut Bar, &watch, "bar"),
+    ///     Err(GoldenError::Missing { .. })
+    /// ));
+    /// golden
+    ///     .clone()
+    ///     .with_bless(true)
+    ///     .check_face(&mut Bar, &watch, "bar")
+    ///     .unwrap();
+    /// golden.check_face(&mut Bar, &watch, "bar").unwrap();
+    ///
+    /// // Ten more percent is ten more pixels in each of the ten rows of the bar
+    /// watch.set_battery_voltage(3880);
+    /// assert!(matches!(
+    ///     golden.check_face(&mut Bar, &watch, "bar"),
+    ///     Err(GoldenError::Mismatch { pixels: 100, .. })
+    /// ));
+    /// let diff = std::fs::read(directory.join("bar.diff.pbm")).unwrap();
+    /// let diff = DecodedImage::decode(&diff).unwrap();
+    /// assert_eq!(diff.pixels().filter(|color| color.is_on()).count(), 100);
+    /// # std::fs::remove_dir_all(&directory).unwrap();
+    /// ```
+    pub fn check_face(
+        &self,
+        face: &mut impl WatchFace,
+        watch: &Watch,
+        name: &str,
+    ) -> Result<(), GoldenError> {
+        Simulator::new(watch).with_config(self.config).reset(face)?;
+        self.check(&watch.displayed(), name)
+    }
+
+    /// Checks a frame against the golden image with a name, or overwrites the golden
+    /// image when blessing.
+    pub fn check(&self, frame: &FrameBuffer, name: &str) -> Result<(), GoldenError> {
+        let golden = self.path(name, "");
+        let actual = self.path(name, ".actual");
+        let diff = self.path(name, ".diff");
+        remove_if_exists(&actual)?;
+        remove_if_exists(&diff)?;
+
+        let pbm = sim::encode_pbm(frame);
+        if self.bless {
+            std::fs::write(&golden, pbm)?;
+            return Ok(());
+        }
+
+        let data = match std::fs::read(&golden) {
+            Ok(data) => data,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                std::fs::write(&actual, pbm)?;
+                return Err(GoldenError::Missing { golden, actual });
+            }
+            Err(error) => return Err(error.into()),
+        };
+        // NOTE: The images are nearly always encoded identically, so only decode the
+        // golden image when they are not.
+        if data == pbm {
+            return Ok(());
+        }
+
+        let image = DecodedImage::decode(&data).map_err(|source| GoldenError::Invalid {
+            golden: golden.clone(),
+            source,
+        })?;
+        let differences = Diff::new(frame, &image);
+        if differences.is_empty() {
+            return Ok(());
+        }
+
+        std::fs::write(&actual, pbm)?;
+        std::fs::write(&diff, sim::encode_pbm(&differences.frame))?;
+        Err(GoldenError::Mismatch {
+            golden,
+            diff,
+            pixels: differences.pixels,
+        })
+    }
+
+    /// Returns the path of an image with a name and a suffix.
+    fn path(&self, name: &str, suffix: &str) -> PathBuf {
+        self.directory.join(format!("{name}{suffix}.pbm"))
+    }
+}
+
+/// Removes a file that was left by a previous check, if any.
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
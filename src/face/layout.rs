@@ -0,0 +1,496 @@
+//! Watch faces described by layouts, which are interpreted at runtime.
+//!
+//! A layout is a compact binary description, which is normally compiled from a TOML file
+//! at build time by the `watchy-assets` crate.
+//! It starts with the bytes `WFL` and the format version, followed by the elements in
+//! the order that they are drawn.
+//! Each element is a kind byte followed by its fields, with multi-byte numbers being
+//! little endian:
+//!
+//! | Kind | Element        | Fields                                                  |
+//! |------|----------------|---------------------------------------------------------|
+//! | 1    | Time           | text placement, flags (bit 0 for the 12 hour clock)     |
+//! | 2    | Date           | text placement                                          |
+//! | 3    | Text           | text placement, length (`u8`), UTF-8 text               |
+//! | 4    | Complication   | complication, x, y (`i16`), width, height (`u16`)       |
+//! | 5    | Image          | x, y (`i16`), width, height (`u16`), packed pixel data  |
+//!
+//! A text placement is the x and y position (`i16`), the font, and then the alignment
+//! in the low nibble and the baseline in the high nibble of a byte.
+//! The font is 0 for [`SMALL`], 1 for [`LARGE_DIGITS`], or 2 for seven-segment digits
+//! followed by the digit width, digit height, and segment width (`u8`).
+//! The alignment is 0 for left, 1 for center, and 2 for right, and the baseline is 0 for
+//! top, 1 for middle, 2 for bottom, and 3 for the alphabetic baseline.
+//! The complication is 0 for [`Battery`], 1 for [`Steps`], 2 for [`Date`](super::Date),
+//! and 3 for [`NextAlarm`].
+//! The image pixel data is packed in the same way as a [`Bitmap`].
+
+use super::{
+    complication::format_date, Battery, Complication, FaceContext, NextAlarm, Steps, WatchFace,
+};
+use crate::{
+    display::Bitmap,
+    text::{FontStyle, Formatted, SevenSegmentStyle, LARGE_DIGITS, SMALL},
+};
+use embedded_graphics::{
+    image::Image,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{renderer::TextRenderer, Alignment, Baseline, Text, TextStyleBuilder},
+};
+use thiserror::Error;
+
+/// The bytes at the start of every layout.
+const MAGIC: &[u8; 3] = b"WFL";
+/// The version of the format that is supported.
+const VERSION: u8 = 1;
+
+/// The kind bytes of the elements.
+mod kind {
+    /// The time.
+    pub const TIME: u8 = 1;
+    /// The date.
+    pub const DATE: u8 = 2;
+    /// Fixed text.
+    pub const TEXT: u8 = 3;
+    /// A complication.
+    pub const COMPLICATION: u8 = 4;
+    /// An image.
+    pub const IMAGE: u8 = 5;
+}
+
+/// Error for malformed layouts.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The data does not start with the layout magic bytes.
+    #[error("The data is not a layout")]
+    NotLayout,
+    /// The layout is in a newer version of the format.
+    #[error("Unsupported layout version {0}")]
+    UnsupportedVersion(u8),
+    /// The layout ends part way through an element.
+    #[error("The layout is truncated")]
+    Truncated,
+    /// An element has an unknown kind.
+    #[error("Unknown layout element kind {0}")]
+    UnknownElement(u8),
+    /// A font, alignment, baseline, or complication is not known.
+    #[error("Invalid value in a layout element")]
+    InvalidValue,
+    /// The text of an element is not valid UTF-8.
+    #[error("The layout text is not valid UTF-8")]
+    InvalidText,
+}
+
+/// A font in which text is drawn by a [`Layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutFont {
+    /// The [`SMALL`] font.
+    Small,
+    /// The [`LARGE_DIGITS`] font.
+    LargeDigits,
+    /// Seven-segment digits.
+    SevenSegment {
+        /// The size of a full width character.
+        digit_size: Size,
+        /// The thickness of the segments.
+        segment_width: u32,
+    },
+}
+
+/// Where and how text is drawn by a [`Layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextPlacement {
+    /// The position of the text.
+    pub position: Point,
+    /// The font.
+    pub font: LayoutFont,
+    /// The horizontal alignment relative to the position.
+    pub alignment: Alignment,
+    /// The vertical alignment relative to the position.
+    pub baseline: Baseline,
+}
+impl TextPlacement {
+    /// Draws text.
+    fn draw<D>(&self, text: &str, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        match self.font {
+            LayoutFont::Small => {
+                self.draw_styled(text, FontStyle::new(&SMALL, BinaryColor::On), target)
+            }
+            LayoutFont::LargeDigits => {
+                self.draw_styled(text, FontStyle::new(&LARGE_DIGITS, BinaryColor::On), target)
+            }
+            LayoutFont::SevenSegment {
+                digit_size,
+                segment_width,
+            } => self.draw_styled(
+                text,
+                SevenSegmentStyle::new(digit_size, segment_width, BinaryColor::On),
+                target,
+            ),
+        }
+    }
+
+    /// Draws text in a character style.
+    fn draw_styled<S, D>(&self, text: &str, style: S, target: &mut D) -> Result<(), D::Error>
+    where
+        S: TextRenderer<Color = BinaryColor>,
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        Text::with_text_style(
+            text,
+            self.position,
+            style,
+            TextStyleBuilder::new()
+                .alignment(self.alignment)
+                .baseline(self.baseline)
+                .build(),
+        )
+        .draw(target)?;
+        Ok(())
+    }
+}
+
+/// A built-in [`Complication`] drawn by a [`Layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplicationKind {
+    /// The [`Battery`] complication.
+    Battery,
+    /// The [`Steps`] complication.
+    Steps,
+    /// The [`Date`](super::Date) complication.
+    Date,
+    /// The [`NextAlarm`] complication.
+    NextAlarm,
+}
+
+/// Something drawn by a [`Layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Element<'a> {
+    /// The time, which is dashes if the clock has not been set.
+    Time {
+        /// Where the time is drawn.
+        placement: TextPlacement,
+        /// Whether to use the 12 hour clock rather than the 24 hour clock.
+        twelve_hour: bool,
+    },
+    /// The date in the same format as the [`Date`](super::Date) complication, which is
+    /// blank if the clock has not been set.
+    Date {
+        /// Where the date is drawn.
+        placement: TextPlacement,
+    },
+    /// Fixed text.
+    Text {
+        /// Where the text is drawn.
+        placement: TextPlacement,
+        /// The text.
+        text: &'a str,
+    },
+    /// A built-in complication.
+    Complication {
+        /// The complication.
+        kind: ComplicationKind,
+        /// The area in which it is drawn.
+        area: Rectangle,
+    },
+    /// An image.
+    Image {
+        /// The position of the top left corner.
+        position: Point,
+        /// The image.
+        bitmap: Bitmap<'a>,
+    },
+}
+impl Element<'_> {
+    /// Draws the element.
+    fn draw<D>(&self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        match self {
+            Self::Time {
+                placement,
+                twelve_hour,
+            } => {
+                let time = match ctx.now {
+                    Some(now) if *twelve_hour => {
+                        let hour = match now.hour % 12 {
+                            0 => 12,
+                            hour => hour,
+                        };
+                        Formatted::<8>::new(format_args!("{hour}:{:02}", now.minute))
+                    }
+                    Some(now) => Formatted::new(format_args!("{:02}:{:02}", now.hour, now.minute)),
+                    None => Formatted::new(format_args!("--:--")),
+                };
+                placement.draw(time.as_str(), target)
+            }
+            Self::Date { placement } => match ctx.now {
+                Some(now) => placement.draw(format_date(&now).as_str(), target),
+                None => Ok(()),
+            },
+            Self::Text { placement, text } => placement.draw(text, target),
+            Self::Complication { kind, area } => match kind {
+                ComplicationKind::Battery => draw_complication(&Battery, ctx, *area, target),
+                ComplicationKind::Steps => draw_complication(&Steps, ctx, *area, target),
+                ComplicationKind::Date => draw_complication(&super::Date, ctx, *area, target),
+                ComplicationKind::NextAlarm => draw_complication(&NextAlarm, ctx, *area, target),
+            },
+            Self::Image { position, bitmap } => Image::new(bitmap, *position).draw(target),
+        }
+    }
+}
+
+/// Clears an area and draws a complication in it.
+fn draw_complication<C, D>(
+    complication: &C,
+    ctx: &FaceContext,
+    area: Rectangle,
+    target: &mut D,
+) -> Result<(), D::Error>
+where
+    C: Complication,
+    D: DrawTarget<Color = BinaryColor>,
+{
+    target.fill_solid(&area, BinaryColor::Off)?;
+    complication.draw(&complication.value(ctx), area, target)
+}
+
+/// Reads the fields of elements from layout data.
+#[derive(Clone)]
+struct Reader<'a> {
+    /// The data that has not yet been read.
+    data: &'a [u8],
+}
+impl<'a> Reader<'a> {
+    /// Reads a number of bytes.
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], LayoutError> {
+        if len > self.data.len() {
+            return Err(LayoutError::Truncated);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Reads a byte.
+    fn u8(&mut self) -> Result<u8, LayoutError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    /// Reads an unsigned 16-bit number.
+    fn u16(&mut self) -> Result<u16, LayoutError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Reads a position.
+    fn point(&mut self) -> Result<Point, LayoutError> {
+        let x = self.u16()? as i16;
+        let y = self.u16()? as i16;
+        Ok(Point::new(x.into(), y.into()))
+    }
+
+    /// Reads a size.
+    fn size(&mut self) -> Result<Size, LayoutError> {
+        let width = self.u16()?;
+        let height = self.u16()?;
+        Ok(Size::new(width.into(), height.into()))
+    }
+
+    /// Reads a text placement.
+    fn placement(&mut self) -> Result<TextPlacement, LayoutError> {
+        let position = self.point()?;
+        let font = match self.u8()? {
+            0 => LayoutFont::Small,
+            1 => LayoutFont::LargeDigits,
+            2 => {
+                let width = self.u8()?;
+                let height = self.u8()?;
+                LayoutFont::SevenSegment {
+                    digit_size: Size::new(width.into(), height.into()),
+                    segment_width: self.u8()?.into(),
+                }
+            }
+            _ => return Err(LayoutError::InvalidValue),
+        };
+        let alignment = self.u8()?;
+        Ok(TextPlacement {
+            position,
+            font,
+            alignment: match alignment & 0x0F {
+                0 => Alignment::Left,
+                1 => Alignment::Center,
+                2 => Alignment::Right,
+                _ => return Err(LayoutError::InvalidValue),
+            },
+            baseline: match alignment >> 4 {
+                0 => Baseline::Top,
+                1 => Baseline::Middle,
+                2 => Baseline::Bottom,
+                3 => Baseline::Alphabetic,
+                _ => return Err(LayoutError::InvalidValue),
+            },
+        })
+    }
+
+    /// Reads the next element, or returns `None` at the end of the data.
+    fn element(&mut self) -> Result<Option<Element<'a>>, LayoutError> {
+        loop {
+            if self.data.is_empty() {
+                return Ok(None);
+            }
+            let element = match self.u8()? {
+                kind::TIME => Element::Time {
+                    placement: self.placement()?,
+                    twelve_hour: self.u8()? & 1 != 0,
+                },
+                kind::DATE => Element::Date {
+                    placement: self.placement()?,
+                },
+                kind::TEXT => {
+                    let placement = self.placement()?;
+                    let len = self.u8()?;
+                    Element::Text {
+                        placement,
+                        text: core::str::from_utf8(self.bytes(len.into())?)
+                            .map_err(|_| LayoutError::InvalidText)?,
+                    }
+                }
+                kind::COMPLICATION => {
+                    let kind = match self.u8()? {
+                        0 => ComplicationKind::Battery,
+                        1 => ComplicationKind::Steps,
+                        2 => ComplicationKind::Date,
+                        3 => ComplicationKind::NextAlarm,
+                        _ => return Err(LayoutError::InvalidValue),
+                    };
+                    Element::Complication {
+                        kind,
+                        area: Rectangle::new(self.point()?, self.size()?),
+                    }
+                }
+                kind::IMAGE => {
+                    let position = self.point()?;
+                    let size = self.size()?;
+                    let row_bytes = size.width.div_ceil(8) as usize;
+                    // NOTE: An empty image is skipped, since a bitmap cannot be empty, by
+                    // looping rather than recursing so that any number of them in a row
+                    // cannot overflow the stack.
+                    let data = self.bytes(row_bytes * size.height as usize)?;
+                    if data.is_empty() {
+                        continue;
+                    }
+                    Element::Image {
+                        position,
+                        bitmap: Bitmap::new(size.width, data),
+                    }
+                }
+                kind => return Err(LayoutError::UnknownElement(kind)),
+            };
+            return Ok(Some(element));
+        }
+    }
+}
+
+/// Iterator over the [`Element`]s of a [`Layout`].
+#[derive(Clone)]
+pub struct Elements<'a> {
+    /// Reads the remaining elements.
+    reader: Reader<'a>,
+}
+impl<'a> Iterator for Elements<'a> {
+    type Item = Element<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The elements were all checked when the layout was created
+        self.reader.element().ok().flatten()
+    }
+}
+
+/// A watch face described by a layout, which places the time, date, complications,
+/// text, and images with their fonts and alignment.
+///
+/// Since the layout is only data, it can be swapped without flashing new firmware, for
+/// example by storing a new layout in NVS.
+/// The interpreter is pure `no_std` code, so it draws the same on the host.
+///
+/// # Example
+/// In `build.rs`:
+/// ```ignore
+/// fn main() {
+///     watchy_assets::include_layout("digital", "layouts/digital.toml").unwrap();
+/// }
+/// ```
+/// Then in the crate, using the layout in NVS if one has been stored:
+/// ```ignore
+/// use watchy::face::Layout;
+///
+/// const DIGITAL: &[u8] = watchy::include_layout!("digital");
+///
+/// let nvs_partition = watchy::svc::nvs::EspDefaultNvsPartition::take().unwrap();
+/// let nvs = watchy::svc::nvs::EspNvs::new(nvs_partition, "face", true).unwrap();
+/// let mut buf = [0; 4096];
+/// let data = nvs.get_raw("layout", &mut buf).unwrap().unwrap_or(DIGITAL);
+/// let mut face = Layout::new(data).unwrap_or_else(|_| Layout::new(DIGITAL).unwrap());
+/// // Run the face...
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout<'a> {
+    /// The elements, without the header.
+    elements: &'a [u8],
+}
+impl<'a> Layout<'a> {
+    /// Checks the layout data, returning an error if it is malformed.
+    pub fn new(data: &'a [u8]) -> Result<Self, LayoutError> {
+        let mut reader = Reader { data };
+        if reader.bytes(MAGIC.len()).ok() != Some(MAGIC) {
+            return Err(LayoutError::NotLayout);
+        }
+        match reader.u8()? {
+            VERSION => {}
+            version => return Err(LayoutError::UnsupportedVersion(version)),
+        }
+
+        let layout = Self {
+            elements: reader.data,
+        };
+        while reader.element()?.is_some() {}
+        Ok(layout)
+    }
+
+    /// Returns the elements in the order that they are drawn.
+    pub fn elements(&self) -> Elements<'a> {
+        Elements {
+            reader: Reader {
+                data: self.elements,
+            },
+        }
+    }
+}
+impl WatchFace for Layout<'_> {
+    fn draw<D>(&mut self, ctx: &FaceContext, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        self.elements()
+            .try_for_each(|element| element.draw(ctx, target))
+    }
+}
+
+/// Includes a layout that was compiled from a TOML file by the build script using the
+/// `watchy-assets` crate, as a byte slice for [`Layout::new`].
+///
+/// The name is that given to the build helper, which writes the layout to the `OUT_DIR`
+/// of the crate.
+#[macro_export]
+macro_rules! include_layout {
+    ($name:literal) => {
+        include_bytes!(concat!(env!("OUT_DIR"), "/", $name, ".layout"))
+    };
+}
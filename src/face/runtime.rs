@@ -0,0 +1,324 @@
+//! Runtime that draws a watch face each time the ESP32 wakes from deep sleep.
+
+use super::{ContextError, FaceContext, RuntimeConfig, WakeReason, WatchFace};
+use crate::{
+    battery::BatteryStatusDriver,
+    button::Button,
+    display::{FrameBuffer, Oriented, PanelDriver, PanelError, RefreshMode, RetainedFrame},
+    hal::{gpio::Pin, spi},
+    pins,
+    rtc::{RtcDriver, RtcError},
+    sys::{self, EspError},
+    traits::MotionSensor,
+};
+use core::{
+    borrow::Borrow,
+    convert::Infallible,
+    sync::atomic::{AtomicU32, Ordering},
+};
+use embedded_hal::i2c;
+use thiserror::Error;
+
+/// The number of partial refreshes since the last full refresh, retained in RTC memory
+/// while the ESP32 is in deep sleep.
+///
+/// Unlike the [`RetainedFrame`], this is initialized on a cold boot.
+#[link_section = ".rtc.data"]
+static PARTIAL_REFRESHES: AtomicU32 = AtomicU32::new(0);
+
+/// Error for watch face runtime problems.
+#[derive(Error, Debug)]
+pub enum RuntimeError<E: core::fmt::Debug, M: core::fmt::Debug> {
+    /// An error refreshing the display.
+    #[error("Display error: {0}")]
+    Display(#[from] PanelError<EspError, EspError>),
+    /// An error reading the RTC, other than it not having been set.
+    #[error("RTC error: {0}")]
+    Rtc(#[from] RtcError<E>),
+    /// An error reading the accelerometer.
+    #[error("Accelerometer error: {0:?}")]
+    Motion(M),
+    /// An ESP error sampling the battery voltage or setting up the wake sources.
+    #[error("ESP error: {0}")]
+    Esp(#[cfg_attr(feature = "std", source)] EspError),
+}
+// NOTE: This is implemented manually because the source error only implements the
+// error trait when using `std`.
+impl<E: core::fmt::Debug, M: core::fmt::Debug> From<EspError> for RuntimeError<E, M> {
+    fn from(value: EspError) -> Self {
+        Self::Esp(value)
+    }
+}
+
+/// Runtime that owns the drivers and draws a [`WatchFace`] each time the ESP32 wakes
+/// from deep sleep.
+///
+/// Once [run](Self::run), the runtime reads the [`FaceContext`], passes any button press
+/// to the face, and then has it draw into a framebuffer.
+/// If the frame differs from that already displayed, the display is refreshed
+/// partially, or fully on a cold boot and after every
+/// [`full_refresh_interval`](RuntimeConfig::full_refresh_interval) partial refreshes.
+/// The ESP32 then goes into deep sleep until the next minute or until a button is
+/// pressed, after which it restarts.
+/// Holding a button down repeats it, since the ESP32 then wakes again straight away.
+///
+/// The frame is kept in the [`RetainedFrame`] so that the display can be refreshed
+/// partially after waking.
+/// The step count is read from the accelerometer on every wake, usually through its
+/// [`AccelerometerRegisters`](crate::accelerometer::AccelerometerRegisters) sharing the
+/// I2C bus with the RTC.
+/// Its die temperature is also supplied to the panel before each refresh, so that the
+/// built-in LUT suits the ambient temperature, and the refresh is refused with
+/// [`PanelError::TemperatureOutOfRange`] outside of the rated range of the panel.
+/// The next alarm is read from the daily [alarm](crate::rtc::Alarm) of the RTC chip.
+///
+/// # Example
+/// ```no_run
+/// use core::cell::RefCell;
+/// use embedded_hal_bus::i2c::RefCellDevice;
+/// use watchy::{
+///     accelerometer::{bma423, AccelerometerDriver, AccelerometerRegisters},
+///     display::{self, RetainedFrame},
+///     face::{Runtime, WatchFace},
+/// };
+///
+/// # struct Digital;
+/// # impl WatchFace for Digital {
+/// #     fn draw<D>(
+/// #         &mut self,
+/// #         _: &watchy::face::FaceContext,
+/// #         _: &mut D,
+/// #     ) -> Result<(), D::Error>
+/// #     where
+/// #         D: embedded_graphics::prelude::DrawTarget<
+/// #             Color = embedded_graphics::pixelcolor::BinaryColor,
+/// #         >,
+/// #     {
+/// #         Ok(())
+/// #     }
+/// # }
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let i2c_bus = RefCell::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+///
+/// // The accelerometer keeps counting steps in deep sleep, so it is only set up on a
+/// // cold boot
+/// let accelerometer = match AccelerometerRegisters::resume(RefCellDevice::new(&i2c_bus)) {
+///     Ok(Some(registers)) => registers,
+///     _ => {
+///         AccelerometerDriver::new(
+///             pin_sets.accelerometer,
+///             RefCellDevice::new(&i2c_bus),
+///             bma423::Config::default(),
+///         )
+///         .unwrap();
+///         let mut registers = AccelerometerRegisters::new(RefCellDevice::new(&i2c_bus));
+///         registers.set_step_counter(true).unwrap();
+///         registers
+///     }
+/// };
+/// let runtime = Runtime::new(
+///     display::panel_driver(pin_sets.display, peripherals.spi2).unwrap(),
+///     watchy::rtc::RtcDriver::new(RefCellDevice::new(&i2c_bus)),
+///     watchy::battery::BatteryStatusDriver::new(pin_sets.battery, peripherals.adc1).unwrap(),
+///     accelerometer,
+///     pin_sets.buttons,
+///     RetainedFrame::take().unwrap(),
+/// );
+///
+/// // This only returns if there is an error
+/// if let Err(error) = runtime.run(&mut Digital) {
+///     panic!("Watch face failed: {error}");
+/// }
+/// ```
+pub struct Runtime<'d, I2C, M, T = spi::SpiDriver<'d>>
+where
+    T: Borrow<spi::SpiDriver<'d>> + 'd,
+{
+    /// The display panel driver.
+    panel: PanelDriver<'d, T>,
+    /// The RTC driver.
+    rtc: RtcDriver<I2C>,
+    /// The battery status driver.
+    battery: BatteryStatusDriver<'d>,
+    /// The accelerometer, which counts the steps.
+    motion: M,
+    /// The button pins, which are only used to wake the ESP32.
+    _buttons: pins::Buttons,
+    /// The GPIO number of each button.
+    button_pins: [(Button, i32); 4],
+    /// The frame that was last displayed.
+    retained: RetainedFrame,
+    /// The frame being drawn.
+    frame: Oriented<FrameBuffer>,
+    /// The configuration.
+    config: RuntimeConfig,
+    /// Why the ESP32 woke up.
+    wake_reason: WakeReason,
+}
+impl<'d, I2C, M, T> Runtime<'d, I2C, M, T>
+where
+    I2C: i2c::I2c,
+    M: MotionSensor,
+    T: Borrow<spi::SpiDriver<'d>> + 'd,
+{
+    /// Creates the runtime from the drivers, using the default configuration.
+    ///
+    /// The display panel should not have been initialized, since the runtime does this
+    /// only when it needs to refresh the display.
+    pub fn new(
+        panel: PanelDriver<'d, T>,
+        rtc: RtcDriver<I2C>,
+        battery: BatteryStatusDriver<'d>,
+        motion: M,
+        buttons: pins::Buttons,
+        retained: RetainedFrame,
+    ) -> Self {
+        let button_pins = [
+            (Button::Menu, buttons.btn_1.pin()),
+            (Button::Back, buttons.btn_2.pin()),
+            (Button::Up, buttons.btn_3.pin()),
+            (Button::Down, buttons.btn_4.pin()),
+        ];
+        let config = RuntimeConfig::default();
+
+        let mut runtime = Self {
+            panel,
+            rtc,
+            battery,
+            motion,
+            _buttons: buttons,
+            button_pins,
+            retained,
+            frame: Oriented::new(FrameBuffer::new(), config.orientation),
+            config,
+            wake_reason: WakeReason::Other,
+        };
+        runtime.wake_reason = runtime.read_wake_reason();
+        runtime
+    }
+
+    /// Replaces the configuration.
+    pub fn with_config(mut self, config: RuntimeConfig) -> Self {
+        self.frame.set_orientation(config.orientation);
+        self.config = config;
+        self
+    }
+
+    /// Returns why the ESP32 woke up.
+    pub fn wake_reason(&self) -> WakeReason {
+        self.wake_reason
+    }
+
+    /// Determines why the ESP32 woke up from the wake cause.
+    fn read_wake_reason(&self) -> WakeReason {
+        // SAFETY: This has no preconditions.
+        let cause = unsafe { sys::esp_sleep_get_wakeup_cause() };
+        match cause {
+            sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_UNDEFINED => WakeReason::Boot,
+            sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_TIMER => WakeReason::Tick,
+            sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_EXT1 => {
+                // SAFETY: This has no preconditions.
+                let status = unsafe { sys::esp_sleep_get_ext1_wakeup_status() };
+                self.button_pins
+                    .iter()
+                    .find(|(_, pin)| status & (1 << pin) != 0)
+                    .map_or(WakeReason::Other, |(button, _)| WakeReason::Button(*button))
+            }
+            _ => WakeReason::Other,
+        }
+    }
+
+    /// Reads the context for the face.
+    pub fn context(&mut self) -> Result<FaceContext, RuntimeError<I2C::Error, M::Error>> {
+        super::read_context(
+            &mut self.rtc,
+            &mut self.battery,
+            &mut self.motion,
+            self.wake_reason,
+        )
+        .map_err(|error| match error {
+            ContextError::Rtc(error) => RuntimeError::Rtc(error),
+            ContextError::Battery(error) => RuntimeError::Esp(error),
+            ContextError::Motion(error) => RuntimeError::Motion(error),
+        })
+    }
+
+    /// Passes any button press to the face, and then draws it and refreshes the display
+    /// if needed.
+    pub fn update(
+        &mut self,
+        face: &mut impl WatchFace,
+    ) -> Result<(), RuntimeError<I2C::Error, M::Error>> {
+        let context = self.context()?;
+        #[cfg(feature = "std")]
+        crate::panic::set_battery_status(context.battery);
+        if !super::needs_drawing(face, &context) {
+            return Ok(());
+        }
+
+        *self.frame.inner_mut() = FrameBuffer::new();
+        face.draw(&context, &mut self.frame)
+            .unwrap_or_else(|never| match never {});
+        let frame = self.frame.inner();
+
+        let partial_refreshes = PARTIAL_REFRESHES.load(Ordering::Relaxed);
+        let Some(mode) = super::choose_refresh_mode(
+            face.refresh_mode(&context),
+            self.retained.previous(),
+            frame,
+            partial_refreshes,
+            &self.config,
+        ) else {
+            return Ok(());
+        };
+
+        let temperature = self.motion.temperature().map_err(RuntimeError::Motion)?;
+        self.panel.set_temperature(temperature);
+
+        // NOTE: The panel was put to sleep, from which only a hardware reset wakes it,
+        // which resuming with the previous frame also does.
+        let mut delay = crate::delay();
+        match mode {
+            RefreshMode::Full => self.panel.init(&mut delay)?,
+            RefreshMode::Partial => self.panel.resume(&mut delay, self.retained.previous())?,
+        }
+        self.panel.refresh(&mut delay, frame, mode)?;
+        self.retained.store(frame);
+        PARTIAL_REFRESHES.store(
+            match mode {
+                RefreshMode::Full => 0,
+                RefreshMode::Partial => partial_refreshes + 1,
+            },
+            Ordering::Relaxed,
+        );
+        Ok(())
+    }
+
+    /// Puts the display and then the ESP32 into deep sleep until the next minute or until
+    /// a button is pressed.
+    ///
+    /// This only returns if there is an error.
+    pub fn sleep(mut self) -> Result<Infallible, RuntimeError<I2C::Error, M::Error>> {
+        self.panel.sleep()?;
+
+        // Wake just after the minute ticks over
+        let seconds = super::seconds_until_tick(self.rtc.now().ok());
+        // SAFETY: This has no preconditions.
+        sys::esp!(unsafe { sys::esp_sleep_enable_timer_wakeup(seconds * 1_000_000) })?;
+
+        crate::button::enable_wakeup(self.button_pins.map(|(_, pin)| pin))?;
+
+        // SAFETY: This has no preconditions, and does not return.
+        unsafe { sys::esp_deep_sleep_start() }
+    }
+
+    /// Updates the face and then sleeps, so that this only returns if there is an error.
+    pub fn run(
+        mut self,
+        face: &mut impl WatchFace,
+    ) -> Result<Infallible, RuntimeError<I2C::Error, M::Error>> {
+        self.update(face)?;
+        self.sleep()
+    }
+}
@@ -0,0 +1,341 @@
+//! Simulator that runs a watch face on the host, fast-forwarding through deep sleep.
+
+use super::{ContextError, RuntimeConfig, WakeReason, WatchFace};
+use crate::{
+    accelerometer::AccelerometerDriver,
+    battery::BatteryStatusDriver,
+    button::Button,
+    display::{self, FrameBuffer, Oriented, PanelDriver, PanelError, RefreshMode},
+    rtc::{Alarm, RtcDriver, RtcError},
+    sim::{self, Command, ScriptError, Watch},
+    traits::MotionSensor,
+};
+use core::convert::Infallible;
+use embedded_hal::i2c::ErrorKind;
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Error for watch face simulation problems.
+#[derive(Error, Debug)]
+pub enum SimulatorError {
+    /// An error refreshing the simulated display.
+    #[error("Display error: {0}")]
+    Display(#[from] PanelError<Infallible, Infallible>),
+    /// An error reading the simulated RTC, other than it not having been set.
+    #[error("RTC error: {0}")]
+    Rtc(#[from] RtcError<ErrorKind>),
+    /// A line of the script is not a valid command.
+    #[error("Line {line}: {source}")]
+    Script {
+        /// The line number, starting from one.
+        line: usize,
+        /// The problem with the command.
+        source: ScriptError,
+    },
+    /// An error reading the script or writing an image.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Simulator that draws a [`WatchFace`] on a simulated [`Watch`] just as the `Runtime`
+/// does on the hardware.
+///
+/// Each time the simulated ESP32 wakes, the face is passed any button press and drawn,
+/// and the simulated display is refreshed partially or fully as needed by the real panel
+/// driver, with the frame retained as if in RTC memory.
+/// Deep sleep is then fast-forwarded in virtual time, waking on every minute, so that a
+/// day of the face runs in moments.
+///
+/// The face is kept between wakes, whereas on the hardware it is created anew, so it
+/// should not rely on any state that would not survive deep sleep.
+///
+/// # Example
+/// ```
+/// use watchy::{face::Simulator, sim::Watch};
+///
+/// # struct Digital;
+/// # impl watchy::face::WatchFace for Digital {
+/// #     fn draw<D>(
+/// #         &mut self,
+/// #         _: &watchy::face::FaceContext,
+/// #         _: &mut D,
+/// #     ) -> Result<(), D::Error>
+/// #     where
+/// #         D: embedded_graphics::prelude::DrawTarget<
+/// #             Color = embedded_graphics::pixelcolor::BinaryColor,
+/// #         >,
+/// #     {
+/// #         Ok(())
+/// #     }
+/// # }
+/// let script = "
+///     time 2024-10-19 07:55
+///     press menu
+///     wait 1d
+/// ";
+///
+/// let watch = Watch::new();
+/// let mut simulator = Simulator::new(&watch);
+/// simulator.run(&mut Digital, script.as_bytes()).unwrap();
+/// assert_eq!(watch.now().unwrap().day, 20);
+/// ```
+pub struct Simulator {
+    /// The simulated watch.
+    watch: Watch,
+    /// The display panel driver.
+    panel: PanelDriver,
+    /// The RTC driver.
+    rtc: RtcDriver<sim::I2c>,
+    /// The battery status driver.
+    battery: BatteryStatusDriver<'static>,
+    /// The accelerometer driver, which counts the steps.
+    accelerometer: AccelerometerDriver,
+    /// The frame that was last displayed, as retained in RTC memory, which survives a
+    /// reset but not the watch being powered on.
+    retained: Option<FrameBuffer>,
+    /// The number of partial refreshes since the last full refresh.
+    partial_refreshes: u32,
+    /// The frame being drawn.
+    frame: Oriented<FrameBuffer>,
+    /// The configuration.
+    config: RuntimeConfig,
+    /// The directory to which images are written.
+    output: PathBuf,
+    /// Whether to write every refreshed frame to the output directory.
+    record: bool,
+    /// The number of frames written.
+    frames: u32,
+}
+impl Simulator {
+    /// Creates the simulator for a watch, using the default configuration.
+    ///
+    /// The watch does not boot until the first command, so that it can be set up first.
+    pub fn new(watch: &Watch) -> Self {
+        let config = RuntimeConfig::default();
+        Self {
+            watch: watch.clone(),
+            panel: display::panel_driver(watch),
+            rtc: RtcDriver::new(watch.i2c()),
+            battery: BatteryStatusDriver::new(watch),
+            accelerometer: AccelerometerDriver::new(watch),
+            retained: None,
+            partial_refreshes: 0,
+            frame: Oriented::new(FrameBuffer::new(), config.orientation),
+            config,
+            output: PathBuf::from("."),
+            record: false,
+            frames: 0,
+        }
+    }
+
+    /// Replaces the configuration.
+    pub fn with_config(mut self, config: RuntimeConfig) -> Self {
+        self.frame.set_orientation(config.orientation);
+        self.config = config;
+        self
+    }
+
+    /// Sets the directory to which images are written, which is the current directory
+    /// by default.
+    pub fn with_output(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.output = directory.into();
+        self
+    }
+
+    /// Writes the display to the output directory after every refresh, as numbered
+    /// images such as `frame-00001.pbm`.
+    pub fn with_recording(mut self) -> Self {
+        self.record = true;
+        self
+    }
+
+    /// Returns the simulated watch.
+    pub fn watch(&self) -> &Watch {
+        &self.watch
+    }
+
+    /// Sets the time of day of the daily alarm of the simulated RTC chip, or clears it
+    /// if `None`.
+    pub fn set_alarm(&mut self, alarm: Option<Alarm>) -> Result<(), SimulatorError> {
+        self.rtc.set_alarm(alarm)?;
+        Ok(())
+    }
+
+    /// Resets the ESP32, which then boots and draws the face.
+    ///
+    /// The frame retained in RTC memory survives this, as on the hardware.
+    pub fn reset(&mut self, face: &mut impl WatchFace) -> Result<(), SimulatorError> {
+        self.partial_refreshes = 0;
+        self.wake(face, WakeReason::Boot)
+    }
+
+    /// Presses and releases a button, which wakes the ESP32.
+    pub fn press(
+        &mut self,
+        face: &mut impl WatchFace,
+        button: Button,
+    ) -> Result<(), SimulatorError> {
+        self.watch.set_pressed(Some(button));
+        let result = self.wake(face, WakeReason::Button(button));
+        self.watch.set_pressed(None);
+        result
+    }
+
+    /// Fast-forwards through deep sleep by a number of seconds, waking just after each
+    /// minute ticks over.
+    pub fn wait(&mut self, face: &mut impl WatchFace, seconds: u64) -> Result<(), SimulatorError> {
+        let mut remaining = seconds;
+        loop {
+            let tick = super::seconds_until_tick(self.watch.now());
+            if tick > remaining {
+                self.watch.advance(remaining);
+                return Ok(());
+            }
+            self.watch.advance(tick);
+            remaining -= tick;
+            self.wake(face, WakeReason::Tick)?;
+        }
+    }
+
+    /// Writes the image shown on the display to a PBM file with a name in the output
+    /// directory, such as `menu.pbm` for `menu`.
+    pub fn screenshot(&self, name: &str) -> Result<(), SimulatorError> {
+        self.write_image(&self.output.join(format!("{name}.pbm")))
+    }
+
+    /// Carries out a command.
+    ///
+    /// This does not boot the watch, which must first be [reset](Self::reset).
+    pub fn execute(
+        &mut self,
+        face: &mut impl WatchFace,
+        command: &Command,
+    ) -> Result<(), SimulatorError> {
+        match command {
+            Command::SetTime(now) => self.watch.set_time(now),
+            Command::SetBattery(voltage) => self.watch.set_battery_voltage(*voltage),
+            Command::SetSteps(steps) => self.watch.set_steps(*steps),
+            Command::SetAlarm(alarm) => self.set_alarm(*alarm)?,
+            Command::Press(button) => self.press(face, *button)?,
+            Command::Wait(seconds) => self.wait(face, *seconds)?,
+            Command::Reset => self.reset(face)?,
+            Command::Screenshot(name) => self.screenshot(name)?,
+        }
+        Ok(())
+    }
+
+    /// Runs a script of [`Command`]s, one per line.
+    ///
+    /// The watch boots before the first command other than those that set it up, or at
+    /// the end of the script if there are none, unless it was already booted by a
+    /// `reset`.
+    pub fn run(
+        &mut self,
+        face: &mut impl WatchFace,
+        script: impl BufRead,
+    ) -> Result<(), SimulatorError> {
+        let mut booted = false;
+        for (index, line) in script.lines().enumerate() {
+            let command = Command::parse(&line?).map_err(|source| SimulatorError::Script {
+                line: index + 1,
+                source,
+            })?;
+            let Some(command) = command else {
+                continue;
+            };
+
+            let setup = matches!(
+                command,
+                Command::SetTime(_)
+                    | Command::SetBattery(_)
+                    | Command::SetSteps(_)
+                    | Command::SetAlarm(_)
+            );
+            if !booted && !setup {
+                booted = true;
+                self.reset(face)?;
+                if command == Command::Reset {
+                    continue;
+                }
+            }
+            self.execute(face, &command)?;
+        }
+
+        if !booted {
+            self.reset(face)?;
+        }
+        Ok(())
+    }
+
+    /// Wakes the ESP32, drawing the face and refreshing the display if needed.
+    fn wake(
+        &mut self,
+        face: &mut impl WatchFace,
+        reason: WakeReason,
+    ) -> Result<(), SimulatorError> {
+        let context = super::read_context(
+            &mut self.rtc,
+            &mut self.battery,
+            &mut self.accelerometer,
+            reason,
+        )
+        .map_err(|error| match error {
+            ContextError::Rtc(error) => SimulatorError::Rtc(error),
+            ContextError::Battery(never) | ContextError::Motion(never) => match never {},
+        })?;
+        if !super::needs_drawing(face, &context) {
+            return Ok(());
+        }
+
+        *self.frame.inner_mut() = FrameBuffer::new();
+        face.draw(&context, &mut self.frame)
+            .unwrap_or_else(|never| match never {});
+        let frame = self.frame.inner();
+
+        let Some(mode) = super::choose_refresh_mode(
+            face.refresh_mode(&context),
+            self.retained.as_ref(),
+            frame,
+            self.partial_refreshes,
+            &self.config,
+        ) else {
+            return Ok(());
+        };
+
+        // NOTE: As on the hardware, the refresh is refused if the temperature of the
+        // accelerometer is outside of the rated range of the panel.
+        let temperature = MotionSensor::temperature(&mut self.accelerometer)
+            .unwrap_or_else(|never| match never {});
+        self.panel.set_temperature(temperature);
+
+        // NOTE: As on the hardware, the panel was put to sleep, from which only a
+        // hardware reset wakes it, which resuming with the previous frame also does.
+        let mut delay = sim::Delay;
+        match mode {
+            RefreshMode::Full => self.panel.init(&mut delay)?,
+            RefreshMode::Partial => self.panel.resume(&mut delay, self.retained.as_ref())?,
+        }
+        self.panel.refresh(&mut delay, frame, mode)?;
+        self.panel.sleep()?;
+        self.retained = Some(frame.clone());
+        self.partial_refreshes = match mode {
+            RefreshMode::Full => 0,
+            RefreshMode::Partial => self.partial_refreshes + 1,
+        };
+
+        if self.record {
+            self.frames += 1;
+            self.write_image(&self.output.join(format!("frame-{:05}.pbm", self.frames)))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the image shown on the display to a PBM file.
+    fn write_image(&self, path: &Path) -> Result<(), SimulatorError> {
+        std::fs::write(path, sim::encode_pbm(&self.watch.displayed()))?;
+        Ok(())
+    }
+}
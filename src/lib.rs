@@ -9,17 +9,73 @@
 //! - Display (GDEH0154D67)
 //! - I2C driver setup
 //! - Pin sets
+//! - Vibration motor (VC1020B111F)
 //!
 //! Incomplete:
-//! - Real time clock (PCF8563)
-//! - Vibration motor (VC1020B111F)
+//! - Real time clock (PCF8563 or DS3231), which supports time keeping and reading or setting the daily alarm, but does not yet wake the watch when the alarm goes off
+//!
+//! Board revisions v1.0, v1.5, v2.0, and v3.0 are supported by enabling exactly one of the `board-v1`, `board-v1_5`, `board-v2`, or `board-v3` features, with `board-v2` being enabled by default.
+//! To use another revision, disable the default features, for example:
+//! ```toml
+//! watchy = { version = "0.1.0", default-features = false, features = ["std", "esp-idf", "board-v1_5"] }
+//! ```
+//! Alternatively, the `board-auto` feature supports all of the v1.x and v2.0 revisions in a single firmware, with the revision detected at boot (see the `board` module).
+//! The v1.x and v2.0 revisions differ in their RTC chip, battery ADC pin, and button 3 pin.
+//! The v3.0 revision uses an ESP32-S3 with different pins throughout and uses its internal RTC, so the binary crate must be built for the `xtensa-esp32s3-espidf` target with `MCU="esp32s3"` (see the comments in `.cargo/config.toml` of this repository).
+//! The pin sets and drivers have the same API for every revision, so the same application code builds for all of them.
+//!
+//! The crate is `no_std` when the default `std` feature is disabled, in which case the `alloc` feature is also optional.
+//! Without `alloc`, the items that use NVS storage are not available.
+//!
+//! By default, this uses the IDF version of the ESP crates, which is selected by the `esp-idf` feature.
+//! Alternatively, the bare-metal `esp-hal` crate can be used instead by disabling the default features and enabling the `esp-hal` feature, for example:
+//! ```toml
+//! watchy = { version = "0.1.0", default-features = false, features = ["board-v3", "esp-hal"] }
+//! ```
+//! The pin sets, I2C driver, display driver, battery status driver, and button drivers then have equivalent constructors that take `esp-hal` peripherals, with the pins taken using the `take_pins` macro.
+//! On the v3.0 revision, the RTC driver additionally takes the `LPWR` peripheral, which contains the RTC timer that keeps the time.
+//! The binary crate must be built for the `xtensa-esp32-none-elf` target, or `xtensa-esp32s3-none-elf` for the v3.0 revision (see the comments in `.cargo/config.toml` of this repository).
+//! The `esp-hal` backend does not support the `board-auto` feature, and `esp-hal` cannot calibrate the ADC of the ESP32, so the battery voltage is only approximate on the other revisions.
+//! It is also `no_std`, so the `std` feature must be disabled, and the items that use NVS storage are not available.
+//!
+//! Watch faces and apps can be run on the host without the hardware by instead enabling the `sim` feature in place of the backend and board features, for example:
+//! ```toml
+//! watchy = { version = "0.1.0", default-features = false, features = ["sim"] }
+//! ```
+//! The drivers then act on a simulated watch from the `sim` module, with the real RTC and display drivers talking to simulated chips, and the face `Simulator` fast-forwarding through deep sleep in virtual time.
+//! It is driven by a script of commands to set the time, battery voltage, and step count, press buttons, wait, and take screenshots, and writes each displayed frame as a PBM image.
+//! To catch regressions in CI, the face `Golden` renders a face for a simulated time, battery voltage, and step count and compares it against a golden PBM image, writing an image of the differing pixels on a mismatch, and overwriting the golden images instead when the `WATCHY_BLESS` environment variable is set.
+//!
+//! The buttons, battery monitor, accelerometer registers, RTC, and vibration motor drivers implement the traits in the `traits` module, so that logic written against the traits runs on any backend.
+//! With the `mock` feature, which builds on the host like `sim`, the `mock` module has mock implementations of the traits for testing that logic with `cargo test`.
+//!
+//! Images can be drawn on the display as packed bitmaps, which are converted from PNG or BMP files at build time, optionally with dithering, by the `watchy-assets` crate in the `assets` directory.
+//! This runs on the host, so it is used as a build dependency from the build script of the binary crate, with the bitmaps then included using the `include_bitmap` macro.
+//! Images can also be decoded at runtime from PBM, XBM, or BMP files and drawn straight into the display framebuffer.
+//!
+//! Text is drawn using the fonts in the `text` module, which include large digits for the time, seven-segment digits of any size, and a small proportional font with the Latin-1 characters.
+//! Other BDF fonts can be converted at build time by the `watchy-assets` crate and then included using the `include_font` macro.
 //!
-//! Currently, this only supports v2.0 of the board, and only uses the IDF version of the ESP crates.
-//! If there is interest, older board versions and the alternative bare-metal ESP crates could be added as features.
+//! Watch faces are written by implementing the `WatchFace` trait in the `face` module, which draws the face given the time, battery status, step count, and wake reason.
+//! With the `esp-idf` feature, the face `Runtime` owns the drivers and draws the face whenever the watch wakes up on the minute or for a button press, refreshing the display partially or fully as needed, and then returns to deep sleep.
+//! Faces can show the battery level, date, step count from the accelerometer, and next alarm from the RTC chip using the built-in complications, each drawn in a slot of the face, with the display only refreshed when the frame changes.
+//! Faces can also be described declaratively by a layout of the time, date, text, complications, and images in a TOML file, which is compiled at build time by the `watchy-assets` crate, included using the `include_layout` macro, and interpreted by the `Layout` face, so that the layout can be swapped by loading different data, such as from NVS, without recompiling.
+//!
+//! The `ui` module has menus driven by the buttons, with scrolling lists, submenus, toggles, numeric spinners, and confirmation dialogs, which are drawn so as to suit partial refreshes.
+//!
+//! Several apps, such as the watch face, a stopwatch, and settings, can share the watch by implementing the `App` trait in the `app` module and being registered with its `Launcher`, which lists them in a menu.
+//! The launcher is itself a watch face run by the face `Runtime`, with the running app restored after each wake from deep sleep, and each app having its own slot of RTC memory and NVS namespace in which to keep its state.
+//!
+//! With the `esp-idf` backend and `std`, the `panic` module has an optional panic hook, which shows the panic message, where it occurred, and the battery voltage on the display instead of leaving the last frame frozen there, and then sleeps until a button is pressed to restart, so that failures in the field can be reported with a photo of the watch.
+//!
+//! The tests of the `assets` crate compare the converted images and fonts against golden files, and also cover the runtime image decoders, the comparison of frames against images, text rendering, menu navigation, layouts, and the panic screen, which are included from this crate since they do not depend on the hardware.
+//! They must be run for the host target, for example `cargo test --target x86_64-unknown-linux-gnu`, with the `WATCHY_BLESS` environment variable set to update the golden files.
+//! This also regenerates the built-in fonts from the BDF files in `assets/fonts`.
 //!
 //! To use this crate, follow the instructions in [The Rust on ESP Book](https://esp-rs.github.io/book/) to setup the build environment and create a binary crate project.
 //! Note that the standard ESP-IDF crates (viz. `esp-idf-hal`, `esp-idf-sys`, and `esp-idf-svc`) are re-exported from this crate with their features exposed.
 //! A binary crate will still need to include `esp-idf-sys` as a direct dependency.
+//! When using the `esp-hal` feature, the `esp-hal` crate is instead re-exported, and the binary crate should be created from the bare-metal template.
 //!
 //! In order to release this crate on [crates.io](https://crates.io/), all dependencies must also be released there.
 //! However, there are currently a couple of issues that require dependencies to be patched to `git` repositories:
@@ -33,26 +89,102 @@
 //!
 //! Contributions and API suggestions are welcome.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(clippy::missing_docs_in_private_items)]
 
+#[cfg(any(
+    all(feature = "board-v1", feature = "board-v1_5"),
+    all(feature = "board-v1", feature = "board-v2"),
+    all(feature = "board-v1", feature = "board-v3"),
+    all(feature = "board-v1", feature = "board-auto"),
+    all(feature = "board-v1_5", feature = "board-v2"),
+    all(feature = "board-v1_5", feature = "board-v3"),
+    all(feature = "board-v1_5", feature = "board-auto"),
+    all(feature = "board-v2", feature = "board-v3"),
+    all(feature = "board-v2", feature = "board-auto"),
+    all(feature = "board-v3", feature = "board-auto"),
+))]
+compile_error!(
+    "Only one of the `board-v1`, `board-v1_5`, `board-v2`, `board-v3`, or `board-auto` features can be enabled"
+);
+#[cfg(all(
+    feature = "sim",
+    any(
+        feature = "board-v1",
+        feature = "board-v1_5",
+        feature = "board-v2",
+        feature = "board-v3",
+        feature = "board-auto",
+    ),
+))]
+compile_error!("The board features cannot be enabled with the `sim` feature");
+#[cfg(not(any(
+    feature = "board-v1",
+    feature = "board-v1_5",
+    feature = "board-v2",
+    feature = "board-v3",
+    feature = "board-auto",
+    feature = "sim",
+)))]
+compile_error!(
+    "One of the `board-v1`, `board-v1_5`, `board-v2`, `board-v3`, or `board-auto` features must be enabled, or the `sim` feature instead"
+);
+#[cfg(any(
+    all(feature = "esp-idf", feature = "esp-hal"),
+    all(feature = "esp-idf", feature = "sim"),
+    all(feature = "esp-hal", feature = "sim"),
+))]
+compile_error!("Only one of the `esp-idf`, `esp-hal`, or `sim` features can be enabled");
+#[cfg(not(any(feature = "esp-idf", feature = "esp-hal", feature = "sim")))]
+compile_error!("One of the `esp-idf`, `esp-hal`, or `sim` features must be enabled");
+#[cfg(all(feature = "esp-hal", feature = "std"))]
+compile_error!("The `esp-hal` feature requires the `std` feature to be disabled");
+#[cfg(all(feature = "esp-hal", feature = "board-auto"))]
+compile_error!("The `board-auto` feature requires the `esp-idf` feature");
+
+/// Re-export of the bare-metal [`esp-hal`](https://docs.espressif.com/projects/rust/esp-hal/latest/) crate.
+#[cfg(feature = "esp-hal")]
+pub use esp_hal;
+#[cfg(feature = "esp-idf")]
 pub use esp_idf_svc as svc;
 /// Re-export of the [`esp-idf-hal`](https://esp-rs.github.io/esp-idf-hal/esp_idf_hal/index.html) crate.
+#[cfg(feature = "esp-idf")]
 pub use esp_idf_svc::hal;
 /// Re-export of the [`esp-idf-sys`](https://esp-rs.github.io/esp-idf-sys/esp_idf_sys/index.html) crate.
+#[cfg(feature = "esp-idf")]
 pub use esp_idf_svc::sys;
 
 pub mod accelerometer;
+pub mod app;
 pub mod battery;
+#[cfg(not(feature = "sim"))]
+pub mod board;
 pub mod button;
 pub mod display;
+pub mod face;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(all(feature = "esp-idf", feature = "std"))]
+pub mod panic;
+#[cfg(not(feature = "sim"))]
 pub mod pins;
+pub mod rtc;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod text;
+pub mod traits;
+pub mod ui;
+pub mod vibration;
 
+#[cfg(feature = "esp-idf")]
 use enumset::EnumSet;
+#[cfg(feature = "esp-idf")]
 use hal::{i2c, peripheral, units::FromValueType};
 
 /// Result type alias for functions for which an [`EspError`](sys::EspError)
 /// may occur.
+#[cfg(feature = "esp-idf")]
 pub type EspResult<T> = Result<T, sys::EspError>;
 
 /// Sets up the I2C driver for use with the accelerometer and/or RTC.
@@ -66,22 +198,69 @@ pub type EspResult<T> = Result<T, sys::EspError>;
 /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
 /// let i2c_driver = watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap();
 /// ```
+#[cfg(feature = "esp-idf")]
 pub fn i2c_driver<'d, I2C: i2c::I2c>(
     i2c_pins: pins::I2CBus,
     i2c_periph: impl peripheral::Peripheral<P = I2C> + 'd,
 ) -> EspResult<i2c::I2cDriver<'d>> {
-    i2c::I2cDriver::new(
+    i2c::I2cDriver::new(i2c_periph, i2c_pins.sda, i2c_pins.scl, &i2c_config())
+}
+
+/// Sets up the I2C driver for use with the accelerometer and/or RTC.
+///
+/// The `embedded-hal-bus` crate can be used to share the I2C driver
+/// between both devices.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let i2c_driver = watchy::i2c_driver(pin_sets.i2c, peripherals.I2C0).unwrap();
+/// ```
+#[cfg(feature = "esp-hal")]
+pub fn i2c_driver<'d>(
+    i2c_pins: pins::I2CBus,
+    i2c_periph: impl esp_hal::i2c::master::Instance + 'd,
+) -> Result<esp_hal::i2c::master::I2c<'d, esp_hal::Blocking>, esp_hal::i2c::master::ConfigError> {
+    // NOTE: The bus lines are pulled up externally.
+    Ok(esp_hal::i2c::master::I2c::new(
         i2c_periph,
-        i2c_pins.sda,
-        i2c_pins.scl,
-        &i2c::config::Config {
+        esp_hal::i2c::master::Config::default()
             // Fast mode
-            baudrate: 400.kHz().into(),
-            // NOTE: These are pulled up externally.
-            sda_pullup_enabled: false,
-            scl_pullup_enabled: false,
-            timeout: None,
-            intr_flags: EnumSet::empty(),
-        },
-    )
+            .with_frequency(esp_hal::time::Rate::from_khz(400)),
+    )?
+    .with_sda(i2c_pins.sda)
+    .with_scl(i2c_pins.scl))
+}
+
+/// The I2C configuration for the accelerometer and RTC chips.
+#[cfg(feature = "esp-idf")]
+pub(crate) fn i2c_config() -> i2c::config::Config {
+    i2c::config::Config {
+        // Fast mode
+        baudrate: 400.kHz().into(),
+        // NOTE: These are pulled up externally.
+        sda_pullup_enabled: false,
+        scl_pullup_enabled: false,
+        timeout: None,
+        intr_flags: EnumSet::empty(),
+    }
+}
+
+/// Creates a blocking delay provider for the selected backend.
+#[cfg(feature = "esp-idf")]
+pub(crate) fn delay() -> hal::delay::Delay {
+    hal::delay::Delay::new_default()
+}
+
+/// Creates a blocking delay provider for the selected backend.
+#[cfg(feature = "esp-hal")]
+pub(crate) fn delay() -> esp_hal::delay::Delay {
+    esp_hal::delay::Delay::new()
+}
+
+/// Creates a blocking delay provider for the selected backend.
+#[cfg(feature = "sim")]
+pub(crate) fn delay() -> sim::Delay {
+    sim::Delay
 }
@@ -0,0 +1,200 @@
+//! Mock implementations of the [hardware traits](crate::traits), so that the logic of
+//! watch faces and apps can be tested on the host with `cargo test`.
+//!
+//! Each mock is a plain struct whose public fields are the state of the peripheral,
+//! which a test sets up beforehand and checks afterwards.
+//! Setting `fail` makes every fallible operation return a [`MockError`], to test the
+//! handling of hardware errors.
+//!
+//! # Example
+//! ```
+//! use watchy::{
+//!     mock::{MockBatteryMonitor, MockHaptics},
+//!     traits::{BatteryMonitor, Haptics},
+//! };
+//!
+//! /// Buzzes if the battery is low.
+//! fn warn_low_battery<B: BatteryMonitor, H: Haptics>(battery: &mut B, haptics: &mut H) {
+//!     if battery
+//!         .status()
+//!         .is_ok_and(|status| status.percentage() < 10)
+//!     {
+//!         let _ = haptics.buzz(&mut watchy::sim::Delay, 200);
+//!     }
+//! }
+//!
+//! let mut battery = MockBatteryMonitor {
+//!     voltage: 3450,
+//!     ..Default::default()
+//! };
+//! let mut haptics = MockHaptics::default();
+//! warn_low_battery(&mut battery, &mut haptics);
+//! assert_eq!(haptics.activations, 1);
+//! assert!(!haptics.vibrating);
+//! ```
+
+use crate::{
+    battery::BatteryStatus,
+    rtc::{Alarm, DateTime, RtcError},
+    traits::{BatteryMonitor, Button, Clock, Haptics, MotionSensor},
+};
+use thiserror::Error;
+
+/// Error returned by a mock that has been set to `fail`.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Mock failure")]
+pub struct MockError;
+
+/// Returns an error if a mock has been set to fail.
+fn check(fail: bool) -> Result<(), MockError> {
+    if fail {
+        Err(MockError)
+    } else {
+        Ok(())
+    }
+}
+
+/// Mock [`Button`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MockButton {
+    /// Whether the button is pressed.
+    pub pressed: bool,
+}
+impl Button for MockButton {
+    fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+}
+
+/// Mock [`BatteryMonitor`], which has a fully charged battery by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockBatteryMonitor {
+    /// The battery voltage in mV.
+    pub voltage: u32,
+    /// Whether sampling the voltage fails.
+    pub fail: bool,
+}
+impl Default for MockBatteryMonitor {
+    fn default() -> Self {
+        Self {
+            voltage: 4200,
+            fail: false,
+        }
+    }
+}
+impl BatteryMonitor for MockBatteryMonitor {
+    type Error = MockError;
+
+    fn status(&mut self) -> Result<BatteryStatus, Self::Error> {
+        check(self.fail)?;
+        Ok(BatteryStatus::from_voltage(self.voltage))
+    }
+}
+
+/// Mock [`MotionSensor`], which is lying flat at room temperature and counts steps by
+/// default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockMotionSensor {
+    /// The acceleration along each axis in mg.
+    pub acceleration: [i32; 3],
+    /// The number of steps counted, or `None` if steps are not counted.
+    pub steps: Option<u32>,
+    /// The die temperature in °C, or `None` if it is not measured.
+    pub temperature: Option<i8>,
+    /// Whether reading the sensor fails.
+    pub fail: bool,
+}
+impl Default for MockMotionSensor {
+    fn default() -> Self {
+        Self {
+            acceleration: [0, 0, 1000],
+            steps: Some(0),
+            temperature: Some(20),
+            fail: false,
+        }
+    }
+}
+impl MotionSensor for MockMotionSensor {
+    type Error = MockError;
+
+    fn acceleration(&mut self) -> Result<[i32; 3], Self::Error> {
+        check(self.fail)?;
+        Ok(self.acceleration)
+    }
+
+    fn steps(&mut self) -> Result<Option<u32>, Self::Error> {
+        check(self.fail)?;
+        Ok(self.steps)
+    }
+
+    fn temperature(&mut self) -> Result<Option<i8>, Self::Error> {
+        check(self.fail)?;
+        Ok(self.temperature)
+    }
+}
+
+/// Mock [`Clock`], which has not been set and has no alarm by default.
+///
+/// Like the RTC chips, this only accepts years from 2000 to 2199.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MockClock {
+    /// The current date and time, or `None` if the clock has stopped.
+    pub now: Option<DateTime>,
+    /// The time of day of the daily alarm, or `None` if no alarm is set.
+    pub alarm: Option<Alarm>,
+    /// Whether accessing the clock fails with a bus error.
+    pub fail: bool,
+}
+impl MockClock {
+    /// Moves the clock forward by a number of seconds, if it is running.
+    pub fn advance(&mut self, seconds: i64) {
+        self.now = self
+            .now
+            .map(|now| DateTime::from_timestamp(now.timestamp() + seconds));
+    }
+}
+impl Clock for MockClock {
+    type BusError = MockError;
+
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>> {
+        check(self.fail).map_err(RtcError::Bus)?;
+        self.now.ok_or(RtcError::ClockStopped)
+    }
+
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>> {
+        check(self.fail).map_err(RtcError::Bus)?;
+        if !(2000..=2199).contains(&date_time.year) || !date_time.is_valid() {
+            return Err(RtcError::OutOfRange);
+        }
+        self.now = Some(*date_time);
+        Ok(())
+    }
+
+    fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<Self::BusError>> {
+        check(self.fail).map_err(RtcError::Bus)?;
+        Ok(self.alarm)
+    }
+}
+
+/// Mock [`Haptics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MockHaptics {
+    /// Whether the vibration is on.
+    pub vibrating: bool,
+    /// The number of times that the vibration has been turned on.
+    pub activations: u32,
+    /// Whether driving the motor fails.
+    pub fail: bool,
+}
+impl Haptics for MockHaptics {
+    type Error = MockError;
+
+    fn set_vibrating(&mut self, vibrating: bool) -> Result<(), Self::Error> {
+        check(self.fail)?;
+        if vibrating && !self.vibrating {
+            self.activations += 1;
+        }
+        self.vibrating = vibrating;
+        Ok(())
+    }
+}
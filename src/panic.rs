@@ -0,0 +1,165 @@
+//! Panic hook that shows the panic on the display.
+//!
+//! When the firmware panics, the display would otherwise keep showing the last frame, so
+//! that the watch just appears to have frozen.
+//! Once [set](set_hook), the hook instead shows the panic message and where it occurred,
+//! along with the battery voltage, so that a failure in the field can be reported with a
+//! photo of the watch.
+//! The ESP32 then goes into deep sleep until a button is pressed, which restarts the
+//! firmware.
+
+mod screen;
+
+#[cfg(feature = "board-auto")]
+use crate::board::BoardRevision;
+use crate::{
+    battery::{BatteryStatus, BatteryStatusDriver},
+    button,
+    display::{self, FrameBuffer, RefreshMode},
+    hal::{
+        gpio::{Pin, Pins},
+        peripherals::Peripherals,
+    },
+    pins, sys,
+};
+use core::sync::atomic::{AtomicU32, Ordering};
+use std::panic::PanicHookInfo;
+
+/// The battery voltage in mV that was last [recorded](set_battery_status), or zero if
+/// none has been.
+static BATTERY_VOLTAGE: AtomicU32 = AtomicU32::new(0);
+
+/// Sets the panic hook to show the panic on the display and then sleep until a button
+/// is pressed.
+///
+/// The previous hook, which by default prints the panic to the console, is run first.
+/// The display, battery, and button pins are then taken over regardless of the drivers
+/// that own them, since the ESP32 goes into deep sleep without returning to them.
+/// The display is driven with the SPI2 peripheral, or SPI3 if SPI2 is still in use, and
+/// if the ADC is still in use, the battery voltage shown is that last
+/// [recorded](set_battery_status).
+///
+/// # Example
+/// ```no_run
+/// watchy::panic::set_hook();
+///
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// ```
+#[cfg(not(feature = "board-auto"))]
+pub fn set_hook() {
+    install(pins::Sets::new);
+}
+
+/// Sets the panic hook to show the panic on the display of a board revision that was
+/// detected at runtime, and then sleep until a button is pressed.
+///
+/// See the other revisions for details.
+///
+/// # Example
+/// ```no_run
+/// use watchy::board::BoardRevision;
+/// let mut peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let revision = BoardRevision::detect(
+///     &mut peripherals.pins,
+///     &mut peripherals.i2c0,
+///     &mut peripherals.adc1,
+/// )
+/// .unwrap();
+/// watchy::panic::set_hook(revision);
+/// ```
+#[cfg(feature = "board-auto")]
+pub fn set_hook(revision: BoardRevision) {
+    install(move |pins| pins::Sets::for_revision(pins, revision));
+}
+
+/// Records the battery status to show after a panic if the ADC is then still in use by
+/// the driver that sampled it.
+///
+/// The face [`Runtime`](crate::face::Runtime) records the status that it reads on
+/// every wake, so this is only needed when sampling the battery otherwise.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let mut battery =
+///     watchy::battery::BatteryStatusDriver::new(pin_sets.battery, peripherals.adc1).unwrap();
+/// watchy::panic::set_battery_status(battery.status().unwrap());
+/// ```
+pub fn set_battery_status(status: BatteryStatus) {
+    BATTERY_VOLTAGE.store(status.voltage(), Ordering::Relaxed);
+}
+
+/// Returns the battery status that was last recorded, if any.
+fn battery_status() -> Option<BatteryStatus> {
+    match BATTERY_VOLTAGE.load(Ordering::Relaxed) {
+        0 => None,
+        voltage => Some(BatteryStatus::from_voltage(voltage)),
+    }
+}
+
+/// Sets the panic hook, which labels the pins using a function.
+fn install(pin_sets: impl Fn(Pins) -> pins::Sets + Send + Sync + 'static) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+        show(info, &pin_sets);
+    }));
+}
+
+/// Shows a panic on the display and then sleeps until a button is pressed.
+fn show(info: &PanicHookInfo, pin_sets: &impl Fn(Pins) -> pins::Sets) -> ! {
+    // SAFETY: The drivers that own the peripherals are never used again, since this
+    // does not return.
+    let peripherals = unsafe { Peripherals::steal() };
+    let sets = pin_sets(peripherals.pins);
+    let battery = BatteryStatusDriver::new(sets.battery, peripherals.adc1)
+        .and_then(|mut driver| driver.status())
+        .ok()
+        .or_else(battery_status);
+    let button_pins = [
+        sets.buttons.btn_1.pin(),
+        sets.buttons.btn_2.pin(),
+        sets.buttons.btn_3.pin(),
+        sets.buttons.btn_4.pin(),
+    ];
+
+    let mut frame = FrameBuffer::new();
+    screen::draw(&mut frame, message(info), info.location(), battery)
+        .unwrap_or_else(|never| match never {});
+
+    let panel = display::panel_driver(sets.display, peripherals.spi2).or_else(|_| {
+        // SAFETY: As above, and setting the pins up for SPI3 disconnects them from the
+        // driver that still owns SPI2, since each pin outputs one signal.
+        let peripherals = unsafe { Peripherals::steal() };
+        display::panel_driver(pin_sets(peripherals.pins).display, peripherals.spi3)
+    });
+    // NOTE: Nothing can be done about display errors, but the ESP32 should still sleep.
+    if let Ok(mut panel) = panel {
+        // The panel may have been part way through a refresh, so it is fully initialized
+        let mut delay = crate::delay();
+        let _ = panel
+            .init(&mut delay)
+            .and_then(|()| panel.refresh(&mut delay, &frame, RefreshMode::Full))
+            .and_then(|()| panel.sleep());
+    }
+    // The display no longer shows the retained frame, so the next refresh must be full
+    display::discard_retained();
+
+    // SAFETY: This has no preconditions.
+    unsafe { sys::esp_sleep_disable_wakeup_source(sys::esp_sleep_source_t_ESP_SLEEP_WAKEUP_ALL) };
+    let _ = button::enable_wakeup(button_pins);
+    // SAFETY: This has no preconditions, and does not return.
+    unsafe { sys::esp_deep_sleep_start() }
+}
+
+/// Returns the message of a panic.
+fn message<'a>(info: &'a PanicHookInfo) -> &'a str {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
@@ -0,0 +1,123 @@
+//! Drawing of the panic screen, which does not depend on the hardware.
+
+use crate::{
+    battery::BatteryStatus,
+    display::{HEIGHT, WIDTH},
+    text::{Font, FontStyle, Glyph, SMALL},
+};
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use std::panic::Location;
+
+/// The height of the title bar, which matches that of the menus.
+const TITLE_HEIGHT: u32 = 20;
+/// The space between the text and the edges of the display.
+const MARGIN: i32 = 4;
+/// The width available for a line of text.
+pub(super) const TEXT_WIDTH: u32 = WIDTH - 2 * MARGIN as u32;
+
+/// Draws the panic message, where it occurred, and the battery status.
+///
+/// The message is cut short if it does not fit on the display.
+pub(super) fn draw<D>(
+    target: &mut D,
+    message: &str,
+    location: Option<&Location>,
+    battery: Option<BatteryStatus>,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let title = Rectangle::new(Point::zero(), Size::new(WIDTH, TITLE_HEIGHT));
+    target.fill_solid(&title, BinaryColor::On)?;
+    Text::with_text_style(
+        "Panicked",
+        title.center(),
+        FontStyle::new(&SMALL, BinaryColor::Off),
+        TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build(),
+    )
+    .draw(target)?;
+
+    let location = location.map_or("Unknown location".into(), ToString::to_string);
+    let location = wrap(&location, &SMALL, TEXT_WIDTH);
+    let battery = match battery {
+        Some(status) => format!(
+            "Battery: {}.{:02} V",
+            status.voltage() / 1000,
+            status.voltage() % 1000 / 10
+        ),
+        None => "Battery: unknown".into(),
+    };
+    let footer = [battery.as_str(), "Press a button to restart"];
+
+    // The location and footer are always shown, with a blank line before each
+    let line_height = SMALL.line_height() as i32;
+    let rows = (HEIGHT - TITLE_HEIGHT) as i32 / line_height;
+    let message_rows = rows - location.len() as i32 - footer.len() as i32 - 2;
+    let lines = wrap(message, &SMALL, TEXT_WIDTH)
+        .into_iter()
+        .take(message_rows.max(0) as usize)
+        .chain([""])
+        .chain(location)
+        .chain([""])
+        .chain(footer);
+
+    let style = FontStyle::new(&SMALL, BinaryColor::On);
+    for (row, line) in lines.enumerate() {
+        let position = Point::new(
+            MARGIN,
+            TITLE_HEIGHT as i32 + MARGIN + row as i32 * line_height,
+        );
+        Text::with_baseline(line, position, style, Baseline::Top).draw(target)?;
+    }
+    Ok(())
+}
+
+/// Splits text into lines that fit a width in a font, breaking at spaces where possible.
+///
+/// The spaces at a break are dropped, but those that indent a line of the text are kept.
+/// A line is only broken within a word if the word does not fit on its own, and holds at
+/// least one character even if it is wider than the line.
+pub(super) fn wrap<'a>(text: &'a str, font: &Font, width: u32) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut rest = paragraph;
+        while font.measure(rest) > width {
+            // Find where the line overflows, and the last space before that which
+            // follows a word
+            let mut line_width = 0;
+            let mut end = 0;
+            let mut space = None;
+            let mut previous = ' ';
+            for (index, character) in rest.char_indices() {
+                // NOTE: A space is checked before its width is added, since it does not
+                // need to fit at the end of a line.
+                if character == ' ' && previous != ' ' {
+                    space = Some(index);
+                }
+                line_width += font.glyph(character).map_or(0, Glyph::advance);
+                if line_width > width && end > 0 {
+                    break;
+                }
+                end = index + character.len_utf8();
+                previous = character;
+            }
+
+            let end = space.unwrap_or(end);
+            lines.push(&rest[..end]);
+            rest = rest[end..].trim_start_matches(' ');
+        }
+        // Nothing is left after a break at the trailing spaces of a paragraph
+        if !rest.is_empty() || paragraph.is_empty() {
+            lines.push(rest);
+        }
+    }
+    lines
+}
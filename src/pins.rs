@@ -9,33 +9,539 @@
 //! let peripherals = hal::peripherals::Peripherals::take().unwrap();
 //! let pin_sets = pins::Sets::new(peripherals.pins);
 //! ```
+//!
+//! Some pins differ between board revisions, which is selected using the
+//! `board-v1`, `board-v1_5`, `board-v2`, or `board-v3` feature.
+//! When using the `board-auto` feature, the revision is instead detected at boot and
+//! the pin sets are created using [`Sets::for_revision`].
+//!
+//! When using the `esp-hal` feature, the generic pins are instead individual fields of the
+//! `esp-hal` peripherals, so these are first gathered into a [`Pins`] using the
+//! [`take_pins`](crate::take_pins) macro:
+//!
+//! ```rust
+//! use watchy::{esp_hal, pins};
+//!
+//! let peripherals = esp_hal::init(esp_hal::Config::default());
+//! let pin_sets = pins::Sets::new(watchy::take_pins!(peripherals));
+//! ```
+
+#[cfg(feature = "board-auto")]
+use crate::board::BoardRevision;
+#[cfg(feature = "esp-idf")]
+use crate::hal::gpio::{self, Pins};
+
+/// The `esp-hal` GPIO pin types, named as in `esp-idf-hal` so that the pin sets
+/// are the same for both backends.
+#[cfg(feature = "esp-hal")]
+mod gpio {
+    use esp_hal::peripherals as p;
+
+    /// GPIO 0 pin.
+    pub type Gpio0 = p::GPIO0<'static>;
+    /// GPIO 1 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio1 = p::GPIO1<'static>;
+    /// GPIO 2 pin.
+    pub type Gpio2 = p::GPIO2<'static>;
+    /// GPIO 3 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio3 = p::GPIO3<'static>;
+    /// GPIO 4 pin.
+    pub type Gpio4 = p::GPIO4<'static>;
+    /// GPIO 5 pin.
+    pub type Gpio5 = p::GPIO5<'static>;
+    /// GPIO 6 pin.
+    pub type Gpio6 = p::GPIO6<'static>;
+    /// GPIO 7 pin.
+    pub type Gpio7 = p::GPIO7<'static>;
+    /// GPIO 8 pin.
+    pub type Gpio8 = p::GPIO8<'static>;
+    /// GPIO 9 pin.
+    pub type Gpio9 = p::GPIO9<'static>;
+    /// GPIO 10 pin.
+    pub type Gpio10 = p::GPIO10<'static>;
+    /// GPIO 11 pin.
+    pub type Gpio11 = p::GPIO11<'static>;
+    /// GPIO 12 pin.
+    pub type Gpio12 = p::GPIO12<'static>;
+    /// GPIO 13 pin.
+    pub type Gpio13 = p::GPIO13<'static>;
+    /// GPIO 14 pin.
+    pub type Gpio14 = p::GPIO14<'static>;
+    /// GPIO 15 pin.
+    pub type Gpio15 = p::GPIO15<'static>;
+    /// GPIO 16 pin.
+    pub type Gpio16 = p::GPIO16<'static>;
+    /// GPIO 17 pin.
+    pub type Gpio17 = p::GPIO17<'static>;
+    /// GPIO 18 pin.
+    pub type Gpio18 = p::GPIO18<'static>;
+    /// GPIO 19 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio19 = p::GPIO19<'static>;
+    /// GPIO 21 pin.
+    pub type Gpio21 = p::GPIO21<'static>;
+    /// GPIO 22 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio22 = p::GPIO22<'static>;
+    /// GPIO 23 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio23 = p::GPIO23<'static>;
+    /// GPIO 25 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio25 = p::GPIO25<'static>;
+    /// GPIO 26 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio26 = p::GPIO26<'static>;
+    /// GPIO 27 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio27 = p::GPIO27<'static>;
+    /// GPIO 32 pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub type Gpio32 = p::GPIO32<'static>;
+    /// GPIO 33 pin.
+    pub type Gpio33 = p::GPIO33<'static>;
+    /// GPIO 34 pin.
+    pub type Gpio34 = p::GPIO34<'static>;
+    /// GPIO 35 pin.
+    pub type Gpio35 = p::GPIO35<'static>;
+    /// GPIO 36 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio36 = p::GPIO36<'static>;
+    /// GPIO 37 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio37 = p::GPIO37<'static>;
+    /// GPIO 38 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio38 = p::GPIO38<'static>;
+    /// GPIO 39 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio39 = p::GPIO39<'static>;
+    /// GPIO 40 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio40 = p::GPIO40<'static>;
+    /// GPIO 41 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio41 = p::GPIO41<'static>;
+    /// GPIO 42 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio42 = p::GPIO42<'static>;
+    /// GPIO 43 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio43 = p::GPIO43<'static>;
+    /// GPIO 44 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio44 = p::GPIO44<'static>;
+    /// GPIO 45 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio45 = p::GPIO45<'static>;
+    /// GPIO 46 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio46 = p::GPIO46<'static>;
+    /// GPIO 47 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio47 = p::GPIO47<'static>;
+    /// GPIO 48 pin.
+    #[cfg(feature = "board-v3")]
+    pub type Gpio48 = p::GPIO48<'static>;
+}
 
-use crate::hal::gpio;
+/// The generic `esp-hal` pins used by the Watchy board.
+///
+/// These are most conveniently taken from the `esp-hal` peripherals using the
+/// [`take_pins`](crate::take_pins) macro, and then transformed into a [`Sets`] using [`Sets::new`].
+///
+/// NOTE: GPIO1 and GPIO3 are used for the USB so should not be used and
+/// are not included here.
+#[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+pub struct Pins {
+    /// GPIO 0 pin.
+    pub gpio0: gpio::Gpio0,
+    /// GPIO 2 pin.
+    pub gpio2: gpio::Gpio2,
+    /// GPIO 4 pin.
+    pub gpio4: gpio::Gpio4,
+    /// GPIO 5 pin.
+    pub gpio5: gpio::Gpio5,
+    /// GPIO 6 pin.
+    pub gpio6: gpio::Gpio6,
+    /// GPIO 7 pin.
+    pub gpio7: gpio::Gpio7,
+    /// GPIO 8 pin.
+    pub gpio8: gpio::Gpio8,
+    /// GPIO 9 pin.
+    pub gpio9: gpio::Gpio9,
+    /// GPIO 10 pin.
+    pub gpio10: gpio::Gpio10,
+    /// GPIO 11 pin.
+    pub gpio11: gpio::Gpio11,
+    /// GPIO 12 pin.
+    pub gpio12: gpio::Gpio12,
+    /// GPIO 13 pin.
+    pub gpio13: gpio::Gpio13,
+    /// GPIO 14 pin.
+    pub gpio14: gpio::Gpio14,
+    /// GPIO 15 pin.
+    pub gpio15: gpio::Gpio15,
+    /// GPIO 16 pin.
+    pub gpio16: gpio::Gpio16,
+    /// GPIO 17 pin.
+    pub gpio17: gpio::Gpio17,
+    /// GPIO 18 pin.
+    pub gpio18: gpio::Gpio18,
+    /// GPIO 19 pin.
+    pub gpio19: gpio::Gpio19,
+    /// GPIO 21 pin.
+    pub gpio21: gpio::Gpio21,
+    /// GPIO 22 pin.
+    pub gpio22: gpio::Gpio22,
+    /// GPIO 23 pin.
+    pub gpio23: gpio::Gpio23,
+    /// GPIO 25 pin.
+    pub gpio25: gpio::Gpio25,
+    /// GPIO 26 pin.
+    pub gpio26: gpio::Gpio26,
+    /// GPIO 27 pin.
+    pub gpio27: gpio::Gpio27,
+    /// GPIO 32 pin.
+    pub gpio32: gpio::Gpio32,
+    /// GPIO 33 pin.
+    pub gpio33: gpio::Gpio33,
+    /// GPIO 34 pin.
+    pub gpio34: gpio::Gpio34,
+    /// GPIO 35 pin.
+    pub gpio35: gpio::Gpio35,
+}
+
+/// The generic `esp-hal` pins used by the Watchy board.
+///
+/// These are most conveniently taken from the `esp-hal` peripherals using the
+/// [`take_pins`](crate::take_pins) macro, and then transformed into a [`Sets`] using [`Sets::new`].
+///
+/// NOTE: GPIO19 and GPIO20 are used for the USB, and GPIO26 through GPIO32
+/// are used for the flash, so these should not be used and are not included here.
+#[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+pub struct Pins {
+    /// GPIO 0 pin.
+    pub gpio0: gpio::Gpio0,
+    /// GPIO 1 pin.
+    pub gpio1: gpio::Gpio1,
+    /// GPIO 2 pin.
+    pub gpio2: gpio::Gpio2,
+    /// GPIO 3 pin.
+    pub gpio3: gpio::Gpio3,
+    /// GPIO 4 pin.
+    pub gpio4: gpio::Gpio4,
+    /// GPIO 5 pin.
+    pub gpio5: gpio::Gpio5,
+    /// GPIO 6 pin.
+    pub gpio6: gpio::Gpio6,
+    /// GPIO 7 pin.
+    pub gpio7: gpio::Gpio7,
+    /// GPIO 8 pin.
+    pub gpio8: gpio::Gpio8,
+    /// GPIO 9 pin.
+    pub gpio9: gpio::Gpio9,
+    /// GPIO 10 pin.
+    pub gpio10: gpio::Gpio10,
+    /// GPIO 11 pin.
+    pub gpio11: gpio::Gpio11,
+    /// GPIO 12 pin.
+    pub gpio12: gpio::Gpio12,
+    /// GPIO 13 pin.
+    pub gpio13: gpio::Gpio13,
+    /// GPIO 14 pin.
+    pub gpio14: gpio::Gpio14,
+    /// GPIO 15 pin.
+    pub gpio15: gpio::Gpio15,
+    /// GPIO 16 pin.
+    pub gpio16: gpio::Gpio16,
+    /// GPIO 17 pin.
+    pub gpio17: gpio::Gpio17,
+    /// GPIO 18 pin.
+    pub gpio18: gpio::Gpio18,
+    /// GPIO 21 pin.
+    pub gpio21: gpio::Gpio21,
+    /// GPIO 33 pin.
+    pub gpio33: gpio::Gpio33,
+    /// GPIO 34 pin.
+    pub gpio34: gpio::Gpio34,
+    /// GPIO 35 pin.
+    pub gpio35: gpio::Gpio35,
+    /// GPIO 36 pin.
+    pub gpio36: gpio::Gpio36,
+    /// GPIO 37 pin.
+    pub gpio37: gpio::Gpio37,
+    /// GPIO 38 pin.
+    pub gpio38: gpio::Gpio38,
+    /// GPIO 39 pin.
+    pub gpio39: gpio::Gpio39,
+    /// GPIO 40 pin.
+    pub gpio40: gpio::Gpio40,
+    /// GPIO 41 pin.
+    pub gpio41: gpio::Gpio41,
+    /// GPIO 42 pin.
+    pub gpio42: gpio::Gpio42,
+    /// GPIO 43 pin.
+    pub gpio43: gpio::Gpio43,
+    /// GPIO 44 pin.
+    pub gpio44: gpio::Gpio44,
+    /// GPIO 45 pin.
+    pub gpio45: gpio::Gpio45,
+    /// GPIO 46 pin.
+    pub gpio46: gpio::Gpio46,
+    /// GPIO 47 pin.
+    pub gpio47: gpio::Gpio47,
+    /// GPIO 48 pin.
+    pub gpio48: gpio::Gpio48,
+}
+
+/// Takes the generic pins used by the Watchy board out of the `esp-hal` peripherals,
+/// gathering them into a [`Pins`](crate::pins::Pins).
+///
+/// Only the pin fields are moved out of the peripherals, so the rest of them are still
+/// available afterwards.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let i2c_driver = watchy::i2c_driver(pin_sets.i2c, peripherals.I2C0).unwrap();
+/// ```
+#[cfg(all(feature = "esp-hal", not(feature = "board-v3")))]
+#[macro_export]
+macro_rules! take_pins {
+    ($peripherals:ident) => {
+        $crate::pins::Pins {
+            gpio0: $peripherals.GPIO0,
+            gpio2: $peripherals.GPIO2,
+            gpio4: $peripherals.GPIO4,
+            gpio5: $peripherals.GPIO5,
+            gpio6: $peripherals.GPIO6,
+            gpio7: $peripherals.GPIO7,
+            gpio8: $peripherals.GPIO8,
+            gpio9: $peripherals.GPIO9,
+            gpio10: $peripherals.GPIO10,
+            gpio11: $peripherals.GPIO11,
+            gpio12: $peripherals.GPIO12,
+            gpio13: $peripherals.GPIO13,
+            gpio14: $peripherals.GPIO14,
+            gpio15: $peripherals.GPIO15,
+            gpio16: $peripherals.GPIO16,
+            gpio17: $peripherals.GPIO17,
+            gpio18: $peripherals.GPIO18,
+            gpio19: $peripherals.GPIO19,
+            gpio21: $peripherals.GPIO21,
+            gpio22: $peripherals.GPIO22,
+            gpio23: $peripherals.GPIO23,
+            gpio25: $peripherals.GPIO25,
+            gpio26: $peripherals.GPIO26,
+            gpio27: $peripherals.GPIO27,
+            gpio32: $peripherals.GPIO32,
+            gpio33: $peripherals.GPIO33,
+            gpio34: $peripherals.GPIO34,
+            gpio35: $peripherals.GPIO35,
+        }
+    };
+}
+
+/// Takes the generic pins used by the Watchy board out of the `esp-hal` peripherals,
+/// gathering them into a [`Pins`](crate::pins::Pins).
+///
+/// Only the pin fields are moved out of the peripherals, so the rest of them are still
+/// available afterwards.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+/// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+/// let i2c_driver = watchy::i2c_driver(pin_sets.i2c, peripherals.I2C0).unwrap();
+/// ```
+#[cfg(all(feature = "esp-hal", feature = "board-v3"))]
+#[macro_export]
+macro_rules! take_pins {
+    ($peripherals:ident) => {
+        $crate::pins::Pins {
+            gpio0: $peripherals.GPIO0,
+            gpio1: $peripherals.GPIO1,
+            gpio2: $peripherals.GPIO2,
+            gpio3: $peripherals.GPIO3,
+            gpio4: $peripherals.GPIO4,
+            gpio5: $peripherals.GPIO5,
+            gpio6: $peripherals.GPIO6,
+            gpio7: $peripherals.GPIO7,
+            gpio8: $peripherals.GPIO8,
+            gpio9: $peripherals.GPIO9,
+            gpio10: $peripherals.GPIO10,
+            gpio11: $peripherals.GPIO11,
+            gpio12: $peripherals.GPIO12,
+            gpio13: $peripherals.GPIO13,
+            gpio14: $peripherals.GPIO14,
+            gpio15: $peripherals.GPIO15,
+            gpio16: $peripherals.GPIO16,
+            gpio17: $peripherals.GPIO17,
+            gpio18: $peripherals.GPIO18,
+            gpio21: $peripherals.GPIO21,
+            gpio33: $peripherals.GPIO33,
+            gpio34: $peripherals.GPIO34,
+            gpio35: $peripherals.GPIO35,
+            gpio36: $peripherals.GPIO36,
+            gpio37: $peripherals.GPIO37,
+            gpio38: $peripherals.GPIO38,
+            gpio39: $peripherals.GPIO39,
+            gpio40: $peripherals.GPIO40,
+            gpio41: $peripherals.GPIO41,
+            gpio42: $peripherals.GPIO42,
+            gpio43: $peripherals.GPIO43,
+            gpio44: $peripherals.GPIO44,
+            gpio45: $peripherals.GPIO45,
+            gpio46: $peripherals.GPIO46,
+            gpio47: $peripherals.GPIO47,
+            gpio48: $peripherals.GPIO48,
+        }
+    };
+}
 
 /// Pins used for the e-Ink display.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 pub struct Display {
     /// The display SPI serial clock pin.
+    #[cfg(not(feature = "board-v3"))]
     pub spi_sclk: gpio::Gpio18,
+    /// The display SPI serial clock pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_sclk: gpio::Gpio47,
     /// The display SPI serial data out pin.
+    #[cfg(not(feature = "board-v3"))]
     pub spi_sdo: gpio::Gpio23,
+    /// The display SPI serial data out pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_sdo: gpio::Gpio48,
     /// The display SPI chip select pin.
+    #[cfg(not(feature = "board-v3"))]
     pub spi_cs: gpio::Gpio5,
+    /// The display SPI chip select pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_cs: gpio::Gpio33,
     /// The display data/command pin.
-    pub disp_dc: gpio::Gpio10,
+    pub disp_dc: DisplayDc,
     /// The display reset pin.
-    pub disp_reset: gpio::Gpio9,
+    pub disp_reset: DisplayReset,
     /// The display busy pin.
-    pub disp_busy: gpio::Gpio19,
+    pub disp_busy: DisplayBusy,
+}
+impl Display {
+    /// Splits the pins into those for the SPI bus and those for the display itself, so
+    /// that the SPI bus can be shared with other devices.
+    pub fn split(self) -> (DisplayBus, DisplayDevice) {
+        (
+            DisplayBus {
+                spi_sclk: self.spi_sclk,
+                spi_sdo: self.spi_sdo,
+            },
+            DisplayDevice {
+                spi_cs: self.spi_cs,
+                disp_dc: self.disp_dc,
+                disp_reset: self.disp_reset,
+                disp_busy: self.disp_busy,
+            },
+        )
+    }
 }
 
+/// Pins of the SPI bus used for the e-Ink display, which can be shared with other
+/// devices.
+///
+/// Created by [`Display::split`].
+pub struct DisplayBus {
+    /// The display SPI serial clock pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub spi_sclk: gpio::Gpio18,
+    /// The display SPI serial clock pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_sclk: gpio::Gpio47,
+    /// The display SPI serial data out pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub spi_sdo: gpio::Gpio23,
+    /// The display SPI serial data out pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_sdo: gpio::Gpio48,
+}
+
+/// Pins used only by the e-Ink display when its SPI bus is shared with other devices.
+///
+/// Created by [`Display::split`].
+pub struct DisplayDevice {
+    /// The display SPI chip select pin.
+    #[cfg(not(feature = "board-v3"))]
+    pub spi_cs: gpio::Gpio5,
+    /// The display SPI chip select pin.
+    #[cfg(feature = "board-v3")]
+    pub spi_cs: gpio::Gpio33,
+    /// The display data/command pin.
+    pub disp_dc: DisplayDc,
+    /// The display reset pin.
+    pub disp_reset: DisplayReset,
+    /// The display busy pin.
+    pub disp_busy: DisplayBusy,
+}
+
+/// The display data/command pin, which depends on the board revision.
+#[cfg(not(feature = "board-v3"))]
+pub type DisplayDc = gpio::Gpio10;
+/// The display data/command pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type DisplayDc = gpio::Gpio34;
+/// The display reset pin, which depends on the board revision.
+#[cfg(not(feature = "board-v3"))]
+pub type DisplayReset = gpio::Gpio9;
+/// The display reset pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type DisplayReset = gpio::Gpio35;
+/// The display busy pin, which depends on the board revision.
+#[cfg(not(feature = "board-v3"))]
+pub type DisplayBusy = gpio::Gpio19;
+/// The display busy pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type DisplayBusy = gpio::Gpio36;
+
 /// Pins to monitor the battery voltage.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 pub struct Battery {
     /// The ADC pin used to monitor the battery voltage.
-    pub adc: gpio::Gpio34,
+    pub adc: BatteryAdc,
+    /// The charge status pin, which is low while the battery is charging.
+    #[cfg(feature = "board-v3")]
+    pub charge_status: gpio::Gpio10,
+    /// The USB detect pin, which is high while USB power is connected.
+    #[cfg(feature = "board-v3")]
+    pub usb_detect: gpio::Gpio21,
+}
+
+/// The ADC pin used to monitor the battery voltage, which depends on the board revision.
+#[cfg(feature = "board-v1")]
+pub type BatteryAdc = gpio::Gpio33;
+/// The ADC pin used to monitor the battery voltage, which depends on the board revision.
+#[cfg(feature = "board-v1_5")]
+pub type BatteryAdc = gpio::Gpio35;
+/// The ADC pin used to monitor the battery voltage, which depends on the board revision.
+#[cfg(feature = "board-v2")]
+pub type BatteryAdc = gpio::Gpio34;
+/// The ADC pin used to monitor the battery voltage, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type BatteryAdc = gpio::Gpio9;
+/// The ADC pin used to monitor the battery voltage, which depends on the board revision.
+#[cfg(feature = "board-auto")]
+pub enum BatteryAdc {
+    /// The pin used on v1.0 boards.
+    Gpio33(gpio::Gpio33),
+    /// The pin used on v2.0 boards.
+    Gpio34(gpio::Gpio34),
+    /// The pin used on v1.5 boards.
+    Gpio35(gpio::Gpio35),
 }
 
 /// Pins attached to the watch buttons.
@@ -43,23 +549,56 @@ pub struct Battery {
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 pub struct Buttons {
     /// The button 1 discrete pin.
+    #[cfg(not(feature = "board-v3"))]
     pub btn_1: gpio::Gpio26,
+    /// The button 1 discrete pin.
+    #[cfg(feature = "board-v3")]
+    pub btn_1: gpio::Gpio7,
     /// The button 2 discrete pin.
+    #[cfg(not(feature = "board-v3"))]
     pub btn_2: gpio::Gpio25,
+    /// The button 2 discrete pin.
+    #[cfg(feature = "board-v3")]
+    pub btn_2: gpio::Gpio6,
     /// The button 3 discrete pin.
-    pub btn_3: gpio::Gpio35,
+    pub btn_3: Button3,
     /// The button 4 discrete pin.
+    #[cfg(not(feature = "board-v3"))]
     pub btn_4: gpio::Gpio4,
+    /// The button 4 discrete pin.
+    #[cfg(feature = "board-v3")]
+    pub btn_4: gpio::Gpio8,
 }
 
+/// The button 3 discrete pin, which depends on the board revision.
+#[cfg(any(feature = "board-v1", feature = "board-v1_5"))]
+pub type Button3 = gpio::Gpio32;
+/// The button 3 discrete pin, which depends on the board revision.
+#[cfg(feature = "board-v2")]
+pub type Button3 = gpio::Gpio35;
+/// The button 3 discrete pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type Button3 = gpio::Gpio0;
+/// The button 3 discrete pin, which depends on the board revision.
+#[cfg(feature = "board-auto")]
+pub type Button3 = gpio::AnyInputPin;
+
 /// Pins used for the I2C bus, on which is the accelerometer and RTC.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 pub struct I2CBus {
     /// The I2C bus serial data line pin.
+    #[cfg(not(feature = "board-v3"))]
     pub sda: gpio::Gpio21,
+    /// The I2C bus serial data line pin.
+    #[cfg(feature = "board-v3")]
+    pub sda: gpio::Gpio12,
     /// The I2C bus serial clock line pin.
+    #[cfg(not(feature = "board-v3"))]
     pub scl: gpio::Gpio22,
+    /// The I2C bus serial clock line pin.
+    #[cfg(feature = "board-v3")]
+    pub scl: gpio::Gpio11,
 }
 
 /// Pins used for the accelerometer chip.
@@ -70,32 +609,63 @@ pub struct Accelerometer {
     /// Accelerometer interrupt 1 pin.
     pub int_1: gpio::Gpio14,
     /// Accelerometer interrupt 2 pin.
-    pub int_2: gpio::Gpio12,
+    pub int_2: AccelerometerInt2,
 }
 
-/// Pins used for the real time clock chip.
+/// The accelerometer interrupt 2 pin, which depends on the board revision.
+#[cfg(not(feature = "board-v3"))]
+pub type AccelerometerInt2 = gpio::Gpio12;
+/// The accelerometer interrupt 2 pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type AccelerometerInt2 = gpio::Gpio13;
+
+/// Pins used for the real time clock chip, which is a PCF8563 or DS3231
+/// depending on the board revision.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 /// Note that the [`I2CBus`] pins are used to communicate with the RTC chip.
+#[cfg(not(feature = "board-v3"))]
 pub struct Rtc {
     /// RTC interrupt pin.
     pub int: gpio::Gpio27,
 }
 
+/// Pins used for the real time clock, which is internal to the ESP32-S3 and
+/// driven by an external 32 kHz crystal.
+///
+/// Most conveniently created as part of the pin set using [`Sets::new`].
+/// These are only provided so that they are not otherwise used, since the
+/// crystal is configured in the ESP-IDF `sdkconfig`.
+#[cfg(feature = "board-v3")]
+pub struct Rtc {
+    /// Positive 32 kHz crystal pin.
+    pub xtal_32k_p: gpio::Gpio15,
+    /// Negative 32 kHz crystal pin.
+    pub xtal_32k_n: gpio::Gpio16,
+}
+
 /// Pins to control the vibration motor.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 pub struct VibrationMotor {
     /// Pin that controls the DC vibration motor power.
-    pub power: gpio::Gpio13,
+    pub power: VibrationMotorPower,
 }
 
+/// The vibration motor power pin, which depends on the board revision.
+#[cfg(not(feature = "board-v3"))]
+pub type VibrationMotorPower = gpio::Gpio13;
+/// The vibration motor power pin, which depends on the board revision.
+#[cfg(feature = "board-v3")]
+pub type VibrationMotorPower = gpio::Gpio17;
+
 /// Pins unused by the Watchy board.
 ///
 /// Most conveniently created as part of the pin set using [`Sets::new`].
 ///
 /// NOTE: GPIO1 and GPIO3 are used for the USB so should not be used and
 /// are not included here.
+#[cfg(not(feature = "board-v3"))]
 pub struct Unused {
     /// GPIO 0 pin.
     pub gpio0: gpio::Gpio0,
@@ -116,9 +686,71 @@ pub struct Unused {
     /// GPIO 17 pin.
     pub gpio17: gpio::Gpio17,
     /// GPIO 32 pin.
+    #[cfg(feature = "board-v2")]
     pub gpio32: gpio::Gpio32,
     /// GPIO 33 pin.
+    #[cfg(any(feature = "board-v1_5", feature = "board-v2"))]
     pub gpio33: gpio::Gpio33,
+    /// GPIO 34 pin.
+    #[cfg(any(feature = "board-v1", feature = "board-v1_5"))]
+    pub gpio34: gpio::Gpio34,
+    /// GPIO 35 pin.
+    #[cfg(feature = "board-v1")]
+    pub gpio35: gpio::Gpio35,
+    /// GPIO 32 pin, which is only unused on v2.0 boards.
+    #[cfg(feature = "board-auto")]
+    pub gpio32: Option<gpio::Gpio32>,
+    /// GPIO 33 pin, which is only unused on v1.5 and v2.0 boards.
+    #[cfg(feature = "board-auto")]
+    pub gpio33: Option<gpio::Gpio33>,
+    /// GPIO 34 pin, which is only unused on v1.0 and v1.5 boards.
+    #[cfg(feature = "board-auto")]
+    pub gpio34: Option<gpio::Gpio34>,
+    /// GPIO 35 pin, which is only unused on v1.0 boards.
+    #[cfg(feature = "board-auto")]
+    pub gpio35: Option<gpio::Gpio35>,
+}
+
+/// Pins unused by the Watchy board.
+///
+/// Most conveniently created as part of the pin set using [`Sets::new`].
+///
+/// NOTE: GPIO19 and GPIO20 are used for the USB, and GPIO26 through GPIO32
+/// are used for the flash, so these should not be used and are not included here.
+#[cfg(feature = "board-v3")]
+pub struct Unused {
+    /// GPIO 1 pin.
+    pub gpio1: gpio::Gpio1,
+    /// GPIO 2 pin.
+    pub gpio2: gpio::Gpio2,
+    /// GPIO 3 pin.
+    pub gpio3: gpio::Gpio3,
+    /// GPIO 4 pin.
+    pub gpio4: gpio::Gpio4,
+    /// GPIO 5 pin.
+    pub gpio5: gpio::Gpio5,
+    /// GPIO 18 pin.
+    pub gpio18: gpio::Gpio18,
+    /// GPIO 37 pin.
+    pub gpio37: gpio::Gpio37,
+    /// GPIO 38 pin.
+    pub gpio38: gpio::Gpio38,
+    /// GPIO 39 pin.
+    pub gpio39: gpio::Gpio39,
+    /// GPIO 40 pin.
+    pub gpio40: gpio::Gpio40,
+    /// GPIO 41 pin.
+    pub gpio41: gpio::Gpio41,
+    /// GPIO 42 pin.
+    pub gpio42: gpio::Gpio42,
+    /// GPIO 43 pin.
+    pub gpio43: gpio::Gpio43,
+    /// GPIO 44 pin.
+    pub gpio44: gpio::Gpio44,
+    /// GPIO 45 pin.
+    pub gpio45: gpio::Gpio45,
+    /// GPIO 46 pin.
+    pub gpio46: gpio::Gpio46,
 }
 
 /// Sets of pins organized by hardware devices featured on the board.
@@ -143,6 +775,100 @@ pub struct Sets {
     pub unused: Unused,
 }
 impl Sets {
+    /// Transforms the generic HAL pins, into labeled sets of pins specific to
+    /// a Watchy board revision detected at runtime.
+    ///
+    /// # Panics
+    /// This will panic if the revision is v3.0, which requires the `board-v3` feature.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use watchy::board::BoardRevision;
+    /// let mut peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let revision = BoardRevision::detect(
+    ///     &mut peripherals.pins,
+    ///     &mut peripherals.i2c0,
+    ///     &mut peripherals.adc1,
+    /// )
+    /// .unwrap();
+    /// let pin_sets = watchy::pins::Sets::for_revision(peripherals.pins, revision);
+    /// ```
+    #[cfg(feature = "board-auto")]
+    pub fn for_revision(pins: Pins, revision: BoardRevision) -> Self {
+        use gpio::InputPin;
+
+        let (battery_adc, btn_3, gpio32, gpio33, gpio34, gpio35) = match revision {
+            BoardRevision::V1_0 => (
+                BatteryAdc::Gpio33(pins.gpio33),
+                pins.gpio32.downgrade_input(),
+                None,
+                None,
+                Some(pins.gpio34),
+                Some(pins.gpio35),
+            ),
+            BoardRevision::V1_5 => (
+                BatteryAdc::Gpio35(pins.gpio35),
+                pins.gpio32.downgrade_input(),
+                None,
+                Some(pins.gpio33),
+                Some(pins.gpio34),
+                None,
+            ),
+            BoardRevision::V2_0 => (
+                BatteryAdc::Gpio34(pins.gpio34),
+                pins.gpio35.downgrade_input(),
+                Some(pins.gpio32),
+                Some(pins.gpio33),
+                None,
+                None,
+            ),
+            BoardRevision::V3_0 => panic!("The v3.0 board requires the `board-v3` feature"),
+        };
+
+        Self {
+            display: Display {
+                spi_sclk: pins.gpio18,
+                spi_sdo: pins.gpio23,
+                spi_cs: pins.gpio5,
+                disp_dc: pins.gpio10,
+                disp_reset: pins.gpio9,
+                disp_busy: pins.gpio19,
+            },
+            battery: Battery { adc: battery_adc },
+            buttons: Buttons {
+                btn_1: pins.gpio26,
+                btn_2: pins.gpio25,
+                btn_3,
+                btn_4: pins.gpio4,
+            },
+            accelerometer: Accelerometer {
+                int_1: pins.gpio14,
+                int_2: pins.gpio12,
+            },
+            i2c: I2CBus {
+                sda: pins.gpio21,
+                scl: pins.gpio22,
+            },
+            rtc: Rtc { int: pins.gpio27 },
+            vibration_motor: VibrationMotor { power: pins.gpio13 },
+            unused: Unused {
+                gpio0: pins.gpio0,
+                gpio2: pins.gpio2,
+                gpio6: pins.gpio6,
+                gpio7: pins.gpio7,
+                gpio8: pins.gpio8,
+                gpio11: pins.gpio11,
+                gpio15: pins.gpio15,
+                gpio16: pins.gpio16,
+                gpio17: pins.gpio17,
+                gpio32,
+                gpio33,
+                gpio34,
+                gpio35,
+            },
+        }
+    }
+
     /// Transforms the generic HAL pins, into labeled sets of pins specific to
     /// the Watchy board.
     ///
@@ -151,7 +877,8 @@ impl Sets {
     /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
     /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
     /// ```
-    pub fn new(pins: gpio::Pins) -> Self {
+    #[cfg(any(feature = "board-v1", feature = "board-v1_5", feature = "board-v2"))]
+    pub fn new(pins: Pins) -> Self {
         Self {
             display: Display {
                 spi_sclk: pins.gpio18,
@@ -161,10 +888,18 @@ impl Sets {
                 disp_reset: pins.gpio9,
                 disp_busy: pins.gpio19,
             },
+            #[cfg(feature = "board-v1")]
+            battery: Battery { adc: pins.gpio33 },
+            #[cfg(feature = "board-v1_5")]
+            battery: Battery { adc: pins.gpio35 },
+            #[cfg(feature = "board-v2")]
             battery: Battery { adc: pins.gpio34 },
             buttons: Buttons {
                 btn_1: pins.gpio26,
                 btn_2: pins.gpio25,
+                #[cfg(any(feature = "board-v1", feature = "board-v1_5"))]
+                btn_3: pins.gpio32,
+                #[cfg(feature = "board-v2")]
                 btn_3: pins.gpio35,
                 btn_4: pins.gpio4,
             },
@@ -188,8 +923,81 @@ impl Sets {
                 gpio15: pins.gpio15,
                 gpio16: pins.gpio16,
                 gpio17: pins.gpio17,
+                #[cfg(feature = "board-v2")]
                 gpio32: pins.gpio32,
+                #[cfg(any(feature = "board-v1_5", feature = "board-v2"))]
                 gpio33: pins.gpio33,
+                #[cfg(any(feature = "board-v1", feature = "board-v1_5"))]
+                gpio34: pins.gpio34,
+                #[cfg(feature = "board-v1")]
+                gpio35: pins.gpio35,
+            },
+        }
+    }
+
+    /// Transforms the generic HAL pins, into labeled sets of pins specific to
+    /// the Watchy board.
+    ///
+    /// When using the `esp-hal` feature, the generic pins are obtained using the
+    /// [`take_pins`](crate::take_pins) macro instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// ```
+    #[cfg(feature = "board-v3")]
+    pub fn new(pins: Pins) -> Self {
+        Self {
+            display: Display {
+                spi_sclk: pins.gpio47,
+                spi_sdo: pins.gpio48,
+                spi_cs: pins.gpio33,
+                disp_dc: pins.gpio34,
+                disp_reset: pins.gpio35,
+                disp_busy: pins.gpio36,
+            },
+            battery: Battery {
+                adc: pins.gpio9,
+                charge_status: pins.gpio10,
+                usb_detect: pins.gpio21,
+            },
+            buttons: Buttons {
+                btn_1: pins.gpio7,
+                btn_2: pins.gpio6,
+                btn_3: pins.gpio0,
+                btn_4: pins.gpio8,
+            },
+            accelerometer: Accelerometer {
+                int_1: pins.gpio14,
+                int_2: pins.gpio13,
+            },
+            i2c: I2CBus {
+                sda: pins.gpio12,
+                scl: pins.gpio11,
+            },
+            rtc: Rtc {
+                xtal_32k_p: pins.gpio15,
+                xtal_32k_n: pins.gpio16,
+            },
+            vibration_motor: VibrationMotor { power: pins.gpio17 },
+            unused: Unused {
+                gpio1: pins.gpio1,
+                gpio2: pins.gpio2,
+                gpio3: pins.gpio3,
+                gpio4: pins.gpio4,
+                gpio5: pins.gpio5,
+                gpio18: pins.gpio18,
+                gpio37: pins.gpio37,
+                gpio38: pins.gpio38,
+                gpio39: pins.gpio39,
+                gpio40: pins.gpio40,
+                gpio41: pins.gpio41,
+                gpio42: pins.gpio42,
+                gpio43: pins.gpio43,
+                gpio44: pins.gpio44,
+                gpio45: pins.gpio45,
+                gpio46: pins.gpio46,
             },
         }
     }
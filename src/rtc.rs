@@ -0,0 +1,908 @@
+//! Drivers for the real time clock (RTC) chip.
+//!
+//! Depending on the board revision, the RTC is either a
+//! [PCF8563](https://www.nxp.com/docs/en/data-sheet/PCF8563.pdf) (v1.5 and v2.0), a
+//! [DS3231](https://www.analog.com/media/en/technical-documentation/data-sheets/DS3231.pdf) (v1.0),
+//! or the internal RTC of the ESP32-S3 (v3.0).
+//! The [`RtcDriver`] alias refers to the correct driver for the board revision
+//! selected by the `board-*` feature, or to the PCF8563 driver with the `sim` feature.
+//!
+//! The external chips are on the same I2C bus as the accelerometer.
+//! Besides keeping the time, they hold a daily [`Alarm`], which survives deep sleep so
+//! that watch faces can show it, whereas the internal RTC has no alarm.
+
+use crate::traits::Clock;
+use embedded_hal::i2c;
+use thiserror::Error;
+
+/// Error for RTC problems.
+#[derive(Error, Debug)]
+pub enum RtcError<E: core::fmt::Debug> {
+    /// An error on the I2C bus.
+    #[error("I2C bus error: {0:?}")]
+    Bus(E),
+    /// The clock stopped at some point, so the time is no longer valid and needs to be set.
+    #[error("Clock integrity lost")]
+    ClockStopped,
+    /// An attempt was made to set a date and time that the chip cannot represent.
+    #[error("Date and time out of range")]
+    OutOfRange,
+    /// The chip returned a date and time that is not valid, such as after a brown-out.
+    #[error("Invalid date and time read")]
+    InvalidData,
+}
+
+/// A day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    /// Sunday.
+    Sunday,
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+}
+impl Weekday {
+    /// All days of the week, starting with Sunday.
+    const ALL: [Self; 7] = [
+        Self::Sunday,
+        Self::Monday,
+        Self::Tuesday,
+        Self::Wednesday,
+        Self::Thursday,
+        Self::Friday,
+        Self::Saturday,
+    ];
+
+    /// Returns the number of days since Sunday.
+    pub fn number_from_sunday(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// A calendar date and time of day with no time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    /// The full year (e.g. 2024).
+    pub year: u16,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 59.
+    pub second: u8,
+}
+impl DateTime {
+    /// Returns the day of the week, or `None` if the date is not valid.
+    pub fn weekday(&self) -> Option<Weekday> {
+        // NOTE: This is Sakamoto's method, using euclidean division so that years
+        // before 1 AD are also correct.
+        const MONTH_OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        if !self.is_valid() {
+            return None;
+        }
+        let year = i32::from(self.year) - i32::from(self.month < 3);
+        let days = year
+            + year.div_euclid(4)
+            + year.div_euclid(400)
+            + MONTH_OFFSETS[usize::from(self.month - 1)]
+            + i32::from(self.day)
+            - year.div_euclid(100);
+
+        Some(Weekday::ALL[days.rem_euclid(7) as usize])
+    }
+
+    /// Creates a date and time from the number of seconds since the Unix epoch.
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        // NOTE: This is Howard Hinnant's civil from days algorithm.
+        let days = timestamp.div_euclid(86400) + 719468;
+        let seconds = timestamp.rem_euclid(86400);
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year: year.try_into().unwrap_or_default(),
+            month: month.try_into().unwrap(),
+            day: (day_of_year - (153 * shifted_month + 2) / 5 + 1)
+                .try_into()
+                .unwrap(),
+            hour: (seconds / 3600).try_into().unwrap(),
+            minute: (seconds / 60 % 60).try_into().unwrap(),
+            second: (seconds % 60).try_into().unwrap(),
+        }
+    }
+
+    /// Returns the number of seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        // NOTE: This is Howard Hinnant's days from civil algorithm.
+        let month = i64::from(self.month);
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146097 + day_of_era - 719468;
+
+        days * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// Returns the number of days in the month, or zero if the month is not valid.
+    fn days_in_month(&self) -> u8 {
+        let leap = self.year.is_multiple_of(4)
+            && (!self.year.is_multiple_of(100) || self.year.is_multiple_of(400));
+        match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    /// Returns whether every field is within its valid range.
+    pub(crate) fn is_valid(&self) -> bool {
+        (1..=self.days_in_month()).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+}
+
+/// The time of day at which an alarm goes off every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alarm {
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+}
+impl Alarm {
+    /// Returns when the alarm next goes off from a date and time, which is within its
+    /// minute if the alarm goes off during that minute.
+    pub fn next(&self, now: &DateTime) -> DateTime {
+        let today = DateTime {
+            hour: self.hour,
+            minute: self.minute,
+            second: 0,
+            ..*now
+        };
+        if (self.hour, self.minute) < (now.hour, now.minute) {
+            DateTime::from_timestamp(today.timestamp() + 86400)
+        } else {
+            today
+        }
+    }
+
+    /// Returns whether the hour and minute are within their valid ranges.
+    fn is_valid(&self) -> bool {
+        self.hour < 24 && self.minute < 60
+    }
+}
+
+/// Converts a BCD encoded byte to binary.
+pub(crate) fn from_bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0F)
+}
+
+/// Converts BCD encoded bytes to binary, or returns `None` if any is not valid BCD.
+fn decode_bcd<const N: usize>(values: [u8; N]) -> Option<[u8; N]> {
+    values
+        .iter()
+        .all(|value| value >> 4 < 10 && value & 0x0F < 10)
+        .then(|| values.map(from_bcd))
+}
+
+/// Returns a date and time read from a chip, or an error if it is not valid.
+fn checked<E: core::fmt::Debug>(date_time: DateTime) -> Result<DateTime, RtcError<E>> {
+    if date_time.is_valid() {
+        Ok(date_time)
+    } else {
+        Err(RtcError::InvalidData)
+    }
+}
+
+/// Converts a binary byte that is less than 100 to BCD.
+pub(crate) fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+/// Returns whether a chip responds at an I2C address.
+pub(crate) fn is_present<I2C: i2c::I2c>(i2c: &mut I2C, address: u8) -> bool {
+    i2c.write_read(address, &[0], &mut [0]).is_ok()
+}
+
+/// Driver for the PCF8563 RTC chip used on board revisions v1.5 and v2.0.
+pub struct Pcf8563<I2C> {
+    /// The I2C driver.
+    i2c: I2C,
+}
+impl<I2C: i2c::I2c> Pcf8563<I2C> {
+    /// The I2C address of the chip.
+    pub const ADDRESS: u8 = 0x51;
+    /// The seconds register, which is the first of the time and date registers.
+    const REG_SECONDS: u8 = 0x02;
+    /// Voltage low bit in the seconds register, indicating that the clock stopped.
+    const VL_BIT: u8 = 0x80;
+    /// Century bit in the months register.
+    const CENTURY_BIT: u8 = 0x80;
+    /// The minute alarm register, which is followed by the hour, day, and weekday alarm
+    /// registers.
+    const REG_MINUTE_ALARM: u8 = 0x09;
+    /// Alarm disable bit in each of the alarm registers.
+    const AE_BIT: u8 = 0x80;
+
+    /// Creates a new driver.
+    ///
+    /// It is recommended to setup the `i2c_driver` using the [`i2c_driver`](crate::i2c_driver) function
+    /// as this will configure the I2C with the correct settings for the chip.
+    pub fn new(i2c_driver: I2C) -> Self {
+        Self { i2c: i2c_driver }
+    }
+
+    /// Returns the I2C driver.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Reads the time of day of the daily alarm, or `None` if the alarm is disabled or
+    /// is not daily.
+    pub fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<I2C::Error>> {
+        let mut buf = [0; 4];
+        self.i2c
+            .write_read(Self::ADDRESS, &[Self::REG_MINUTE_ALARM], &mut buf)
+            .map_err(RtcError::Bus)?;
+
+        // Only the minute and hour alarms are enabled for a daily alarm
+        if buf[0] & Self::AE_BIT != 0
+            || buf[1] & Self::AE_BIT != 0
+            || buf[2] & buf[3] & Self::AE_BIT == 0
+        {
+            return Ok(None);
+        }
+        let alarm = decode_bcd([buf[0] & 0x7F, buf[1] & 0x3F])
+            .map(|[minute, hour]| Alarm { hour, minute })
+            .filter(Alarm::is_valid)
+            .ok_or(RtcError::InvalidData)?;
+        Ok(Some(alarm))
+    }
+
+    /// Sets the time of day of the daily alarm, or disables the alarm if `None`.
+    ///
+    /// Only the alarm time is set, so the alarm flag and interrupt are left to the
+    /// application.
+    pub fn set_alarm(&mut self, alarm: Option<Alarm>) -> Result<(), RtcError<I2C::Error>> {
+        let [minute, hour] = match alarm {
+            Some(alarm) if !alarm.is_valid() => return Err(RtcError::OutOfRange),
+            Some(alarm) => [to_bcd(alarm.minute), to_bcd(alarm.hour)],
+            None => [Self::AE_BIT; 2],
+        };
+        self.i2c
+            .write(
+                Self::ADDRESS,
+                &[
+                    Self::REG_MINUTE_ALARM,
+                    minute,
+                    hour,
+                    Self::AE_BIT,
+                    Self::AE_BIT,
+                ],
+            )
+            .map_err(RtcError::Bus)
+    }
+
+    /// Reads the current date and time.
+    pub fn now(&mut self) -> Result<DateTime, RtcError<I2C::Error>> {
+        let mut buf = [0; 7];
+        self.i2c
+            .write_read(Self::ADDRESS, &[Self::REG_SECONDS], &mut buf)
+            .map_err(RtcError::Bus)?;
+
+        if buf[0] & Self::VL_BIT != 0 {
+            return Err(RtcError::ClockStopped);
+        }
+
+        // NOTE: The weekday in buf[4] is ignored since it can be calculated.
+        let [second, minute, hour, day, month, year] = decode_bcd([
+            buf[0] & 0x7F,
+            buf[1] & 0x7F,
+            buf[2] & 0x3F,
+            buf[3] & 0x3F,
+            buf[5] & 0x1F,
+            buf[6],
+        ])
+        .ok_or(RtcError::InvalidData)?;
+        let century = if buf[5] & Self::CENTURY_BIT != 0 {
+            2100
+        } else {
+            2000
+        };
+        checked(DateTime {
+            year: century + u16::from(year),
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Sets the current date and time, which also clears any lost clock integrity.
+    ///
+    /// The year must be from 2000 to 2199.
+    pub fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<I2C::Error>> {
+        let Some(weekday) = date_time
+            .weekday()
+            .filter(|_| (2000..2200).contains(&date_time.year))
+        else {
+            return Err(RtcError::OutOfRange);
+        };
+        let (century, year) = if date_time.year >= 2100 {
+            (Self::CENTURY_BIT, date_time.year - 2100)
+        } else {
+            (0, date_time.year - 2000)
+        };
+
+        self.i2c
+            .write(
+                Self::ADDRESS,
+                &[
+                    Self::REG_SECONDS,
+                    to_bcd(date_time.second),
+                    to_bcd(date_time.minute),
+                    to_bcd(date_time.hour),
+                    to_bcd(date_time.day),
+                    weekday.number_from_sunday(),
+                    century | to_bcd(date_time.month),
+                    to_bcd(year.try_into().unwrap()),
+                ],
+            )
+            .map_err(RtcError::Bus)
+    }
+}
+impl<I2C: i2c::I2c> Clock for Pcf8563<I2C> {
+    type BusError = I2C::Error;
+
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>> {
+        Pcf8563::now(self)
+    }
+
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>> {
+        Pcf8563::set(self, date_time)
+    }
+
+    fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<Self::BusError>> {
+        Pcf8563::alarm(self)
+    }
+}
+
+/// Driver for the DS3231 RTC chip used on board revision v1.0.
+pub struct Ds3231<I2C> {
+    /// The I2C driver.
+    i2c: I2C,
+}
+impl<I2C: i2c::I2c> Ds3231<I2C> {
+    /// The I2C address of the chip.
+    pub const ADDRESS: u8 = 0x68;
+    /// The seconds register, which is the first of the time and date registers.
+    const REG_SECONDS: u8 = 0x00;
+    /// The minutes register of alarm 2, which is followed by its hours and day
+    /// registers.
+    const REG_ALARM_2: u8 = 0x0B;
+    /// The control register.
+    const REG_CONTROL: u8 = 0x0E;
+    /// The status register.
+    const REG_STATUS: u8 = 0x0F;
+    /// Oscillator stop flag in the status register, indicating that the clock stopped.
+    const OSF_BIT: u8 = 0x80;
+    /// Century bit in the months register.
+    const CENTURY_BIT: u8 = 0x80;
+    /// 12 hour mode bit in the hours register.
+    const HOUR_12_BIT: u8 = 0x40;
+    /// PM bit in the hours register when in 12 hour mode.
+    const PM_BIT: u8 = 0x20;
+    /// Mask bit in each of the alarm registers, which makes the alarm ignore that field.
+    const MASK_BIT: u8 = 0x80;
+    /// Alarm 2 interrupt enable bit in the control register.
+    const A2IE_BIT: u8 = 0x02;
+
+    /// Creates a new driver.
+    ///
+    /// It is recommended to setup the `i2c_driver` using the [`i2c_driver`](crate::i2c_driver) function
+    /// as this will configure the I2C with the correct settings for the chip.
+    pub fn new(i2c_driver: I2C) -> Self {
+        Self { i2c: i2c_driver }
+    }
+
+    /// Returns the I2C driver.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Reads a register.
+    fn read_register(&mut self, register: u8) -> Result<u8, RtcError<I2C::Error>> {
+        let mut buf = [0];
+        self.i2c
+            .write_read(Self::ADDRESS, &[register], &mut buf)
+            .map_err(RtcError::Bus)?;
+        Ok(buf[0])
+    }
+
+    /// Reads the status register.
+    fn status(&mut self) -> Result<u8, RtcError<I2C::Error>> {
+        self.read_register(Self::REG_STATUS)
+    }
+
+    /// Converts the hours register of the time or an alarm to the hour from 0 to 23,
+    /// or returns `None` if it is not valid.
+    fn decode_hour(register: u8) -> Option<u8> {
+        // The chip may have been set to 12 hour mode by other firmware
+        if register & Self::HOUR_12_BIT == 0 {
+            return decode_bcd([register & 0x3F]).map(|[hour]| hour);
+        }
+        let [hour] = decode_bcd([register & 0x1F])?;
+        if !(1..=12).contains(&hour) {
+            None
+        } else if register & Self::PM_BIT != 0 {
+            Some(hour % 12 + 12)
+        } else {
+            Some(hour % 12)
+        }
+    }
+
+    /// Reads the time of day of alarm 2, which is used as the daily alarm, or `None` if
+    /// its interrupt is disabled or it is not daily.
+    pub fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<I2C::Error>> {
+        if self.read_register(Self::REG_CONTROL)? & Self::A2IE_BIT == 0 {
+            return Ok(None);
+        }
+        let mut buf = [0; 3];
+        self.i2c
+            .write_read(Self::ADDRESS, &[Self::REG_ALARM_2], &mut buf)
+            .map_err(RtcError::Bus)?;
+
+        // Only the day is masked for a daily alarm
+        if buf[0] & Self::MASK_BIT != 0
+            || buf[1] & Self::MASK_BIT != 0
+            || buf[2] & Self::MASK_BIT == 0
+        {
+            return Ok(None);
+        }
+        let alarm = decode_bcd([buf[0] & 0x7F])
+            .zip(Self::decode_hour(buf[1]))
+            .map(|([minute], hour)| Alarm { hour, minute })
+            .filter(Alarm::is_valid)
+            .ok_or(RtcError::InvalidData)?;
+        Ok(Some(alarm))
+    }
+
+    /// Sets the time of day of alarm 2, which is used as the daily alarm, or disables
+    /// its interrupt if `None`.
+    ///
+    /// The interrupt is enabled when an alarm is set, but clearing the alarm flag is left
+    /// to the application.
+    pub fn set_alarm(&mut self, alarm: Option<Alarm>) -> Result<(), RtcError<I2C::Error>> {
+        if let Some(alarm) = alarm {
+            if !alarm.is_valid() {
+                return Err(RtcError::OutOfRange);
+            }
+            // NOTE: As for the time, 24 hour mode is always set.
+            self.i2c
+                .write(
+                    Self::ADDRESS,
+                    &[
+                        Self::REG_ALARM_2,
+                        to_bcd(alarm.minute),
+                        to_bcd(alarm.hour),
+                        Self::MASK_BIT,
+                    ],
+                )
+                .map_err(RtcError::Bus)?;
+        }
+
+        let control = self.read_register(Self::REG_CONTROL)?;
+        let control = if alarm.is_some() {
+            control | Self::A2IE_BIT
+        } else {
+            control & !Self::A2IE_BIT
+        };
+        self.i2c
+            .write(Self::ADDRESS, &[Self::REG_CONTROL, control])
+            .map_err(RtcError::Bus)
+    }
+
+    /// Reads the current date and time.
+    pub fn now(&mut self) -> Result<DateTime, RtcError<I2C::Error>> {
+        if self.status()? & Self::OSF_BIT != 0 {
+            return Err(RtcError::ClockStopped);
+        }
+
+        let mut buf = [0; 7];
+        self.i2c
+            .write_read(Self::ADDRESS, &[Self::REG_SECONDS], &mut buf)
+            .map_err(RtcError::Bus)?;
+
+        // NOTE: The weekday in buf[3] is ignored since it can be calculated.
+        let [second, minute, day, month, year] = decode_bcd([
+            buf[0] & 0x7F,
+            buf[1] & 0x7F,
+            buf[4] & 0x3F,
+            buf[5] & 0x1F,
+            buf[6],
+        ])
+        .ok_or(RtcError::InvalidData)?;
+        let hour = Self::decode_hour(buf[2]).ok_or(RtcError::InvalidData)?;
+
+        let century = if buf[5] & Self::CENTURY_BIT != 0 {
+            2100
+        } else {
+            2000
+        };
+        checked(DateTime {
+            year: century + u16::from(year),
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Sets the current date and time, which also clears any lost clock integrity.
+    ///
+    /// The year must be from 2000 to 2199.
+    pub fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<I2C::Error>> {
+        let Some(weekday) = date_time
+            .weekday()
+            .filter(|_| (2000..2200).contains(&date_time.year))
+        else {
+            return Err(RtcError::OutOfRange);
+        };
+        let (century, year) = if date_time.year >= 2100 {
+            (Self::CENTURY_BIT, date_time.year - 2100)
+        } else {
+            (0, date_time.year - 2000)
+        };
+
+        // NOTE: The chip numbers weekdays from 1 to 7, and 24 hour mode is always set.
+        self.i2c
+            .write(
+                Self::ADDRESS,
+                &[
+                    Self::REG_SECONDS,
+                    to_bcd(date_time.second),
+                    to_bcd(date_time.minute),
+                    to_bcd(date_time.hour),
+                    weekday.number_from_sunday() + 1,
+                    to_bcd(date_time.day),
+                    century | to_bcd(date_time.month),
+                    to_bcd(year.try_into().unwrap()),
+                ],
+            )
+            .map_err(RtcError::Bus)?;
+
+        // Clear the oscillator stop flag
+        let status = self.status()?;
+        self.i2c
+            .write(Self::ADDRESS, &[Self::REG_STATUS, status & !Self::OSF_BIT])
+            .map_err(RtcError::Bus)
+    }
+}
+impl<I2C: i2c::I2c> Clock for Ds3231<I2C> {
+    type BusError = I2C::Error;
+
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>> {
+        Ds3231::now(self)
+    }
+
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>> {
+        Ds3231::set(self, date_time)
+    }
+
+    fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<Self::BusError>> {
+        Ds3231::alarm(self)
+    }
+}
+
+/// Driver for whichever external RTC chip is on the board, which is selected at runtime.
+///
+/// This is useful when the board revision is not known at compile time.
+#[cfg(not(feature = "board-v3"))]
+pub enum AnyRtc<I2C> {
+    /// The PCF8563 RTC chip used on board revisions v1.5 and v2.0.
+    Pcf8563(Pcf8563<I2C>),
+    /// The DS3231 RTC chip used on board revision v1.0.
+    Ds3231(Ds3231<I2C>),
+}
+#[cfg(not(feature = "board-v3"))]
+impl<I2C: i2c::I2c> AnyRtc<I2C> {
+    /// Creates a new driver for the chip that is present on the I2C bus.
+    ///
+    /// The DS3231 is used if it responds, otherwise the PCF8563 is assumed.
+    ///
+    /// It is recommended to setup the `i2c_driver` using the [`i2c_driver`](crate::i2c_driver) function
+    /// as this will configure the I2C with the correct settings for the chips.
+    pub fn new(mut i2c_driver: I2C) -> Self {
+        if is_present(&mut i2c_driver, Ds3231::<I2C>::ADDRESS) {
+            Self::Ds3231(Ds3231::new(i2c_driver))
+        } else {
+            Self::Pcf8563(Pcf8563::new(i2c_driver))
+        }
+    }
+
+    /// Returns the I2C driver.
+    pub fn release(self) -> I2C {
+        match self {
+            Self::Pcf8563(driver) => driver.release(),
+            Self::Ds3231(driver) => driver.release(),
+        }
+    }
+
+    /// Reads the current date and time.
+    pub fn now(&mut self) -> Result<DateTime, RtcError<I2C::Error>> {
+        match self {
+            Self::Pcf8563(driver) => driver.now(),
+            Self::Ds3231(driver) => driver.now(),
+        }
+    }
+
+    /// Sets the current date and time, which also clears any lost clock integrity.
+    ///
+    /// The year must be from 2000 to 2199.
+    pub fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<I2C::Error>> {
+        match self {
+            Self::Pcf8563(driver) => driver.set(date_time),
+            Self::Ds3231(driver) => driver.set(date_time),
+        }
+    }
+
+    /// Reads the time of day of the daily alarm, or `None` if the alarm is disabled or
+    /// is not daily.
+    pub fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<I2C::Error>> {
+        match self {
+            Self::Pcf8563(driver) => driver.alarm(),
+            Self::Ds3231(driver) => driver.alarm(),
+        }
+    }
+
+    /// Sets the time of day of the daily alarm, or disables the alarm if `None`.
+    pub fn set_alarm(&mut self, alarm: Option<Alarm>) -> Result<(), RtcError<I2C::Error>> {
+        match self {
+            Self::Pcf8563(driver) => driver.set_alarm(alarm),
+            Self::Ds3231(driver) => driver.set_alarm(alarm),
+        }
+    }
+}
+#[cfg(not(feature = "board-v3"))]
+impl<I2C: i2c::I2c> Clock for AnyRtc<I2C> {
+    type BusError = I2C::Error;
+
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>> {
+        AnyRtc::now(self)
+    }
+
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>> {
+        AnyRtc::set(self, date_time)
+    }
+
+    fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<Self::BusError>> {
+        AnyRtc::alarm(self)
+    }
+}
+
+/// Driver for the internal RTC of the ESP32-S3 used on board revision v3.0.
+///
+/// The internal RTC keeps the ESP-IDF system time running through deep sleep.
+/// For it to be accurate, the external 32 kHz crystal must be selected as the RTC
+/// clock source by setting `CONFIG_RTC_CLK_SRC_EXT_CRYS=y` in the `sdkconfig.defaults`
+/// of the binary crate.
+/// When using the `esp-hal` feature, the time is instead kept using the RTC timer directly,
+/// so the driver also takes the low power management peripheral that contains it.
+///
+/// The internal RTC has no alarm, so [`Clock::alarm`] always returns `None`.
+///
+/// The I2C driver is not needed by the internal RTC, but is taken so that this has the same
+/// API as the external RTC drivers of other board revisions.
+/// It can be recovered using [`InternalRtc::release`].
+#[cfg(feature = "board-v3")]
+pub struct InternalRtc<I2C> {
+    /// The I2C driver, which is unused.
+    i2c: I2C,
+    /// The `esp-hal` driver for the low power management peripheral, which contains the
+    /// RTC timer.
+    #[cfg(feature = "esp-hal")]
+    rtc: esp_hal::rtc_cntl::Rtc<'static>,
+}
+#[cfg(feature = "board-v3")]
+impl<I2C: i2c::I2c> InternalRtc<I2C> {
+    /// Creates a new driver.
+    #[cfg(feature = "esp-idf")]
+    pub fn new(i2c_driver: I2C) -> Self {
+        Self { i2c: i2c_driver }
+    }
+
+    /// Creates a new driver, which owns the low power management peripheral.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+    /// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+    /// let mut rtc_driver = watchy::rtc::RtcDriver::new(
+    ///     watchy::i2c_driver(pin_sets.i2c, peripherals.I2C0).unwrap(),
+    ///     peripherals.LPWR,
+    /// );
+    /// let now = rtc_driver.now().unwrap();
+    /// ```
+    #[cfg(feature = "esp-hal")]
+    pub fn new(i2c_driver: I2C, lpwr: esp_hal::peripherals::LPWR<'static>) -> Self {
+        Self {
+            i2c: i2c_driver,
+            rtc: esp_hal::rtc_cntl::Rtc::new(lpwr),
+        }
+    }
+
+    /// Returns the I2C driver.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Reads the current date and time.
+    ///
+    /// If the time was never set since the last power loss, it will be before
+    /// the year 2000 so that [`RtcError::ClockStopped`] is returned.
+    pub fn now(&mut self) -> Result<DateTime, RtcError<I2C::Error>> {
+        #[cfg(feature = "esp-idf")]
+        let timestamp = {
+            let mut time = crate::sys::timeval {
+                tv_sec: 0,
+                tv_usec: 0,
+            };
+            // SAFETY: The time value is valid and the time zone may be null.
+            unsafe { crate::sys::gettimeofday(&mut time, core::ptr::null_mut()) };
+            time.tv_sec.into()
+        };
+        #[cfg(feature = "esp-hal")]
+        let timestamp = (self.rtc.current_time_us() / 1_000_000) as i64;
+
+        let date_time = DateTime::from_timestamp(timestamp);
+        if date_time.year < 2000 {
+            Err(RtcError::ClockStopped)
+        } else {
+            Ok(date_time)
+        }
+    }
+
+    /// Sets the current date and time.
+    ///
+    /// The year must be from 2000 to 2199.
+    pub fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<I2C::Error>> {
+        if !date_time.is_valid() || !(2000..2200).contains(&date_time.year) {
+            return Err(RtcError::OutOfRange);
+        }
+
+        #[cfg(feature = "esp-idf")]
+        {
+            let time = crate::sys::timeval {
+                tv_sec: date_time.timestamp(),
+                tv_usec: 0,
+            };
+            // SAFETY: The time value is valid and the time zone may be null.
+            unsafe { crate::sys::settimeofday(&time, core::ptr::null()) };
+        }
+        // NOTE: The timestamp is always positive since the year was checked above.
+        #[cfg(feature = "esp-hal")]
+        self.rtc
+            .set_current_time_us(date_time.timestamp() as u64 * 1_000_000);
+        Ok(())
+    }
+}
+#[cfg(feature = "board-v3")]
+impl<I2C: i2c::I2c> Clock for InternalRtc<I2C> {
+    type BusError = I2C::Error;
+
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>> {
+        InternalRtc::now(self)
+    }
+
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>> {
+        InternalRtc::set(self, date_time)
+    }
+}
+
+/// The RTC driver for the board revision selected by the `board-*` feature.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let mut rtc_driver =
+///     watchy::rtc::RtcDriver::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+/// let now = rtc_driver.now().unwrap();
+/// ```
+#[cfg(any(feature = "board-v1_5", feature = "board-v2"))]
+pub type RtcDriver<I2C> = Pcf8563<I2C>;
+
+/// The RTC driver for the board revision selected by the `board-*` feature.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let mut rtc_driver =
+///     watchy::rtc::RtcDriver::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+/// let now = rtc_driver.now().unwrap();
+/// ```
+#[cfg(feature = "board-v1")]
+pub type RtcDriver<I2C> = Ds3231<I2C>;
+
+/// The RTC driver for the board revision selected by the `board-*` feature.
+///
+/// # Example
+/// ```no_run
+/// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+/// let mut rtc_driver =
+///     watchy::rtc::RtcDriver::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+/// let now = rtc_driver.now().unwrap();
+/// ```
+#[cfg(feature = "board-v3")]
+pub type RtcDriver<I2C> = InternalRtc<I2C>;
+
+/// The RTC driver for the board revision selected by the `board-*` feature.
+///
+/// # Example
+/// ```no_run
+/// use watchy::board::BoardRevision;
+/// let mut peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+/// let revision = BoardRevision::detect(
+///     &mut peripherals.pins,
+///     &mut peripherals.i2c0,
+///     &mut peripherals.adc1,
+/// )
+/// .unwrap();
+/// let pin_sets = watchy::pins::Sets::for_revision(peripherals.pins, revision);
+/// let mut rtc_driver =
+///     watchy::rtc::RtcDriver::new(watchy::i2c_driver(pin_sets.i2c, peripherals.i2c0).unwrap());
+/// let now = rtc_driver.now().unwrap();
+/// ```
+#[cfg(feature = "board-auto")]
+pub type RtcDriver<I2C> = AnyRtc<I2C>;
+
+/// The RTC driver of a simulated watch, which has a PCF8563 chip.
+///
+/// # Example
+/// ```
+/// let watch = watchy::sim::Watch::new();
+/// let mut rtc_driver = watchy::rtc::RtcDriver::new(watch.i2c());
+/// assert!(matches!(
+///     rtc_driver.now(),
+///     Err(watchy::rtc::RtcError::ClockStopped)
+/// ));
+/// ```
+#[cfg(feature = "sim")]
+pub type RtcDriver<I2C> = Pcf8563<I2C>;
@@ -0,0 +1,257 @@
+//! Simulation of the Watchy on the host.
+//!
+//! With the `sim` feature in place of the `esp-idf` or `esp-hal` backend and the
+//! `board-*` features, this crate builds for the host so that watch faces and apps can
+//! be run and tested without the hardware.
+//! The drivers are then swapped for simulated ones with the same names, all of which act
+//! on the shared state of a simulated [`Watch`]:
+//! - The [`RtcDriver`](crate::rtc::RtcDriver) is the real PCF8563 driver, which talks
+//!   over a simulated [`I2c`] bus to a simulated chip that keeps the virtual time.
+//! - The [`PanelDriver`](crate::display::PanelDriver) is the real panel driver, which
+//!   talks over a simulated [`Spi`] device and pins to a simulated SSD1681 controller.
+//!   This only drives the pixels that changed during a partial refresh, so that faces
+//!   resuming the display incorrectly look wrong just as they would on the hardware.
+//! - The [`ButtonDriver`](crate::button::ButtonDriver),
+//!   [`BatteryStatusDriver`](crate::battery::BatteryStatusDriver), and
+//!   [`AccelerometerDriver`](crate::accelerometer::AccelerometerDriver) read which button
+//!   is held, the battery voltage, and the acceleration and step count of the watch.
+//! - The [`VibrationMotorDriver`](crate::vibration::VibrationMotorDriver) turns the
+//!   motor of the watch on and off.
+//!
+//! The simulated watch is otherwise like a v2.0 board, and the `board` and `pins` modules
+//! are not available.
+//!
+//! Time on the watch is virtual and only moves when [advanced](Watch::advance), so that
+//! the [`Simulator`](crate::face::Simulator) can fast-forward through deep sleep, running
+//! a day of a watch face in moments.
+//! It is driven by [`Command`]s, typically from a script or the standard input.
+
+mod bus;
+mod controller;
+mod script;
+
+pub use bus::I2c;
+pub use controller::{Busy, DataCommand, Reset, Spi};
+pub use script::{Command, ScriptError};
+
+use crate::{
+    button::Button,
+    display::{FrameBuffer, RefreshMode, HEIGHT, WIDTH},
+    rtc::DateTime,
+};
+use bus::Pcf8563;
+use controller::Controller;
+use std::{cell::RefCell, rc::Rc};
+
+/// The state of the simulated hardware.
+#[derive(Debug)]
+struct State {
+    /// The virtual time in seconds since the watch was powered on.
+    uptime: u64,
+    /// The time of the RTC when the watch was powered on as a Unix timestamp, or `None`
+    /// if the clock has not been set.
+    clock_offset: Option<i64>,
+    /// The battery voltage in mV.
+    battery_voltage: u32,
+    /// The number of steps counted by the accelerometer.
+    steps: u32,
+    /// The acceleration along each axis in mg.
+    acceleration: [i32; 3],
+    /// Whether the vibration motor is on.
+    vibrating: bool,
+    /// The button being held down, if any.
+    pressed: Option<Button>,
+    /// The RTC chip.
+    rtc: Pcf8563,
+    /// The display controller.
+    controller: Controller,
+}
+impl State {
+    /// Returns the current time of the RTC, or `None` if the clock has not been set.
+    fn now(&self) -> Option<DateTime> {
+        self.clock_offset
+            .map(|offset| DateTime::from_timestamp(offset + self.uptime as i64))
+    }
+
+    /// Sets the time of the RTC, or stops the clock if `None`.
+    fn set_now(&mut self, now: Option<DateTime>) {
+        self.clock_offset = now.map(|now| now.timestamp() - self.uptime as i64);
+    }
+}
+
+/// The state of a simulated Watchy, which is shared by the simulated drivers.
+///
+/// Cloning the watch gives another handle to the same state.
+/// When created, the watch has a fully charged battery, no steps counted, and a clock
+/// that has not been set, as if it had just been powered on for the first time, and is
+/// lying [flat](Self::FLAT).
+///
+/// # Example
+/// ```
+/// use watchy::{rtc::DateTime, sim::Watch};
+///
+/// let watch = Watch::new();
+/// watch.set_time(&DateTime {
+///     year: 2024,
+///     month: 10,
+///     day: 19,
+///     hour: 13,
+///     minute: 5,
+///     second: 0,
+/// });
+/// watch.advance(3600);
+/// assert_eq!(watch.now().map(|now| now.hour), Some(14));
+///
+/// let mut rtc_driver = watchy::rtc::RtcDriver::new(watch.i2c());
+/// assert_eq!(rtc_driver.now().unwrap().hour, 14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Watch {
+    /// The shared state.
+    state: Rc<RefCell<State>>,
+}
+impl Watch {
+    /// The acceleration in mg when lying flat on its back, with gravity along the Z axis.
+    pub const FLAT: [i32; 3] = [0, 0, 1000];
+
+    /// Creates a watch that has just been powered on.
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(State {
+                uptime: 0,
+                clock_offset: None,
+                battery_voltage: 4200,
+                steps: 0,
+                acceleration: Self::FLAT,
+                vibrating: false,
+                pressed: None,
+                rtc: Pcf8563::new(),
+                controller: Controller::new(),
+            })),
+        }
+    }
+
+    /// Returns the virtual time in seconds since the watch was powered on.
+    pub fn uptime(&self) -> u64 {
+        self.state.borrow().uptime
+    }
+
+    /// Moves virtual time forward.
+    pub fn advance(&self, seconds: u64) {
+        self.state.borrow_mut().uptime += seconds;
+    }
+
+    /// Returns the current time of the RTC, or `None` if the clock has not been set.
+    pub fn now(&self) -> Option<DateTime> {
+        self.state.borrow().now()
+    }
+
+    /// Sets the time of the RTC.
+    pub fn set_time(&self, now: &DateTime) {
+        self.state.borrow_mut().set_now(Some(*now));
+    }
+
+    /// Stops the clock of the RTC, as if it had lost power, so that it needs to be set
+    /// again.
+    pub fn stop_clock(&self) {
+        self.state.borrow_mut().set_now(None);
+    }
+
+    /// Returns the battery voltage in mV.
+    pub fn battery_voltage(&self) -> u32 {
+        self.state.borrow().battery_voltage
+    }
+
+    /// Sets the battery voltage in mV.
+    pub fn set_battery_voltage(&self, voltage: u32) {
+        self.state.borrow_mut().battery_voltage = voltage;
+    }
+
+    /// Returns the number of steps counted by the accelerometer.
+    pub fn steps(&self) -> u32 {
+        self.state.borrow().steps
+    }
+
+    /// Sets the number of steps counted by the accelerometer.
+    pub fn set_steps(&self, steps: u32) {
+        self.state.borrow_mut().steps = steps;
+    }
+
+    /// Returns the acceleration along each axis in mg.
+    pub fn acceleration(&self) -> [i32; 3] {
+        self.state.borrow().acceleration
+    }
+
+    /// Sets the acceleration along each axis in mg.
+    pub fn set_acceleration(&self, acceleration: [i32; 3]) {
+        self.state.borrow_mut().acceleration = acceleration;
+    }
+
+    /// Returns whether the vibration motor is on.
+    pub fn is_vibrating(&self) -> bool {
+        self.state.borrow().vibrating
+    }
+
+    /// Turns the vibration motor on or off.
+    pub fn set_vibrating(&self, vibrating: bool) {
+        self.state.borrow_mut().vibrating = vibrating;
+    }
+
+    /// Returns the button being held down, if any.
+    pub fn pressed(&self) -> Option<Button> {
+        self.state.borrow().pressed
+    }
+
+    /// Holds a button down, or releases any button if `None`.
+    pub fn set_pressed(&self, button: Option<Button>) {
+        self.state.borrow_mut().pressed = button;
+    }
+
+    /// Returns the image shown on the display.
+    ///
+    /// This is what the panel shows after its refreshes, which may differ from the
+    /// frames sent to it if it was not driven correctly.
+    pub fn displayed(&self) -> FrameBuffer {
+        self.state.borrow().controller.shown().clone()
+    }
+
+    /// Returns the number of times that the display has been refreshed in a mode.
+    pub fn refreshes(&self, mode: RefreshMode) -> u32 {
+        self.state.borrow().controller.refreshes(mode)
+    }
+
+    /// Returns a handle to the I2C bus, on which the RTC chip responds.
+    pub fn i2c(&self) -> I2c {
+        I2c::new(self)
+    }
+}
+impl Default for Watch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Blocking delay provider for the simulated hardware.
+///
+/// This returns straight away, since the simulated hardware is never busy and virtual
+/// time only moves when the watch is [advanced](Watch::advance).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Delay;
+impl embedded_hal::delay::DelayNs for Delay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Encodes a frame as a binary PBM image, which most image viewers can open.
+///
+/// # Example
+/// ```
+/// let pbm = watchy::sim::encode_pbm(&watchy::display::FrameBuffer::new());
+/// assert!(pbm.starts_with(b"P4\n200 200\n"));
+/// ```
+pub fn encode_pbm(frame: &FrameBuffer) -> Vec<u8> {
+    let mut pbm = format!("P4\n{WIDTH} {HEIGHT}\n").into_bytes();
+    // NOTE: A set bit is black in PBM but white in the frame, which is otherwise the
+    // same layout.
+    pbm.extend(frame.as_bytes().iter().map(|byte| !byte));
+    pbm
+}
@@ -0,0 +1,170 @@
+//! Simulated I2C bus, on which the simulated RTC chip responds.
+
+use super::Watch;
+use crate::rtc::{from_bcd, to_bcd, DateTime};
+use embedded_hal::i2c::{self, ErrorKind, NoAcknowledgeSource, Operation};
+
+/// Raw PCF8563 registers and values.
+mod reg {
+    /// The I2C address of the chip.
+    pub const ADDRESS: u8 = 0x51;
+    /// The number of registers.
+    pub const COUNT: usize = 16;
+    /// The seconds register, which is the first of the time and date registers.
+    pub const SECONDS: usize = 0x02;
+    /// The number of time and date registers.
+    pub const TIME_COUNT: usize = 7;
+    /// Voltage low bit in the seconds register, indicating that the clock stopped.
+    pub const VL_BIT: u8 = 0x80;
+    /// Century bit in the months register.
+    pub const CENTURY_BIT: u8 = 0x80;
+    /// The minute alarm register, which is the first of the alarm registers.
+    pub const MINUTE_ALARM: usize = 0x09;
+    /// The number of alarm registers.
+    pub const ALARM_COUNT: usize = 4;
+    /// Alarm disable bit in each of the alarm registers.
+    pub const AE_BIT: u8 = 0x80;
+}
+
+/// Simulated PCF8563 RTC chip, which keeps the time of the [`Watch`].
+#[derive(Debug, Clone)]
+pub(super) struct Pcf8563 {
+    /// The registers, of which the time and date registers are only brought up to date
+    /// when accessed.
+    registers: [u8; reg::COUNT],
+    /// The register address pointer, which increments after each byte.
+    pointer: usize,
+}
+impl Pcf8563 {
+    /// Creates the chip with its registers cleared, other than the alarms being
+    /// disabled as on a reset.
+    pub(super) fn new() -> Self {
+        let mut registers = [0; reg::COUNT];
+        registers[reg::MINUTE_ALARM..reg::MINUTE_ALARM + reg::ALARM_COUNT].fill(reg::AE_BIT);
+        Self {
+            registers,
+            pointer: 0,
+        }
+    }
+
+    /// Brings the time and date registers up to date with the clock.
+    fn load(&mut self, now: Option<DateTime>) {
+        let registers = match now {
+            // NOTE: The chip still counts after losing integrity, but the time is
+            // meaningless, so it is left at zero.
+            None => [reg::VL_BIT, 0, 0, 1, 0, 1, 0],
+            Some(now) => {
+                let century = if now.year >= 2100 {
+                    reg::CENTURY_BIT
+                } else {
+                    0
+                };
+                [
+                    to_bcd(now.second),
+                    to_bcd(now.minute),
+                    to_bcd(now.hour),
+                    to_bcd(now.day),
+                    now.weekday()
+                        .map_or(0, |weekday| weekday.number_from_sunday()),
+                    century | to_bcd(now.month),
+                    to_bcd((now.year % 100) as u8),
+                ]
+            }
+        };
+        self.registers[reg::SECONDS..reg::SECONDS + reg::TIME_COUNT].copy_from_slice(&registers);
+    }
+
+    /// Returns the time in the time and date registers, or `None` if the clock is
+    /// marked as stopped.
+    fn time(&self) -> Option<DateTime> {
+        let registers = &self.registers[reg::SECONDS..reg::SECONDS + reg::TIME_COUNT];
+        if registers[0] & reg::VL_BIT != 0 {
+            return None;
+        }
+        let century = if registers[5] & reg::CENTURY_BIT != 0 {
+            2100
+        } else {
+            2000
+        };
+        Some(DateTime {
+            year: century + u16::from(from_bcd(registers[6])),
+            month: from_bcd(registers[5] & 0x1F),
+            day: from_bcd(registers[3] & 0x3F),
+            hour: from_bcd(registers[2] & 0x3F),
+            minute: from_bcd(registers[1] & 0x7F),
+            second: from_bcd(registers[0] & 0x7F),
+        })
+        .filter(DateTime::is_valid)
+    }
+
+    /// Reads consecutive registers from the pointer.
+    fn read(&mut self, now: Option<DateTime>, buf: &mut [u8]) {
+        self.load(now);
+        for byte in buf {
+            *byte = self.registers[self.pointer];
+            self.pointer = (self.pointer + 1) % reg::COUNT;
+        }
+    }
+
+    /// Sets the pointer to the first byte and writes the rest to consecutive registers,
+    /// returning the new time if any of the time and date registers were written.
+    fn write(&mut self, now: Option<DateTime>, data: &[u8]) -> Option<Option<DateTime>> {
+        let (pointer, data) = data.split_first()?;
+        self.pointer = usize::from(*pointer) % reg::COUNT;
+
+        self.load(now);
+        let mut time_written = false;
+        for byte in data {
+            self.registers[self.pointer] = *byte;
+            time_written |= (reg::SECONDS..reg::SECONDS + reg::TIME_COUNT).contains(&self.pointer);
+            self.pointer = (self.pointer + 1) % reg::COUNT;
+        }
+        time_written.then(|| self.time())
+    }
+}
+
+/// Simulated I2C bus of a [`Watch`], on which only the RTC chip responds.
+///
+/// Since this is only a handle to the watch, it can be cloned to share the bus between
+/// drivers.
+#[derive(Debug, Clone)]
+pub struct I2c {
+    /// The watch.
+    watch: Watch,
+}
+impl I2c {
+    /// Creates a handle to the bus of a watch.
+    pub(super) fn new(watch: &Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+        }
+    }
+}
+impl i2c::ErrorType for I2c {
+    type Error = ErrorKind;
+}
+impl i2c::I2c for I2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != reg::ADDRESS {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        let mut state = self.watch.state.borrow_mut();
+        for operation in operations {
+            let now = state.now();
+            match operation {
+                Operation::Read(buf) => state.rtc.read(now, buf),
+                Operation::Write(data) => {
+                    if let Some(now) = state.rtc.write(now, data) {
+                        state.set_now(now);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
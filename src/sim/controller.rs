@@ -0,0 +1,324 @@
+//! Simulated SSD1681 display controller, and the SPI device and pins through which it is
+//! driven.
+
+use super::Watch;
+use crate::display::{FrameBuffer, RefreshMode, HEIGHT};
+use core::convert::Infallible;
+use embedded_hal::{digital, spi};
+
+/// Raw SSD1681 commands and values that the simulated controller acts on.
+mod cmd {
+    /// Deep sleep mode command.
+    pub const DEEP_SLEEP: u8 = 0x10;
+    /// Software reset command.
+    pub const SW_RESET: u8 = 0x12;
+    /// Master activation command, which runs the display update sequence.
+    pub const MASTER_ACTIVATION: u8 = 0x20;
+    /// Display update sequence option command.
+    pub const UPDATE_CONTROL_2: u8 = 0x22;
+    /// Command to write to the RAM of the new frame.
+    pub const WRITE_RAM_NEW: u8 = 0x24;
+    /// Command to write to the RAM of the previous frame.
+    pub const WRITE_RAM_OLD: u8 = 0x26;
+    /// RAM X address counter command.
+    pub const RAM_X_COUNTER: u8 = 0x4E;
+    /// RAM Y address counter command.
+    pub const RAM_Y_COUNTER: u8 = 0x4F;
+
+    /// Bit of the [`UPDATE_CONTROL_2`] values that displays the frame.
+    pub const UPDATE_DISPLAY: u8 = 0x04;
+    /// Bit of the [`UPDATE_CONTROL_2`] values that selects display mode 2, which only
+    /// drives the pixels that differ between the RAMs.
+    pub const UPDATE_MODE_2: u8 = 0x08;
+}
+
+/// Simulated SSD1681 controller, which shows the frames written to it.
+///
+/// Only the commands used to write the RAMs, refresh the display, and sleep are
+/// simulated, with the RAM always being written from left to right and top to bottom
+/// as configured by the panel driver.
+/// Grayscale refreshes show the low plane in black and white.
+#[derive(Debug, Clone)]
+pub(super) struct Controller {
+    /// Whether the data/command pin is high, so that bytes are data.
+    data: bool,
+    /// The last command, to which data bytes belong.
+    command: u8,
+    /// The number of data bytes received for the command.
+    index: usize,
+    /// The RAM X address counter in bytes.
+    x: usize,
+    /// The RAM Y address counter in rows.
+    y: usize,
+    /// The display update sequence option.
+    option: u8,
+    /// Whether the controller is in deep sleep, in which it ignores everything until
+    /// a hardware reset.
+    asleep: bool,
+    /// The RAM of the new frame.
+    ram_new: FrameBuffer,
+    /// The RAM of the previous frame.
+    ram_old: FrameBuffer,
+    /// The pixels shown on the display.
+    shown: FrameBuffer,
+    /// The number of full refreshes.
+    full_refreshes: u32,
+    /// The number of partial refreshes.
+    partial_refreshes: u32,
+}
+impl Controller {
+    /// Creates the controller with a white display, as from the factory.
+    pub(super) fn new() -> Self {
+        Self {
+            data: false,
+            command: 0,
+            index: 0,
+            x: 0,
+            y: 0,
+            option: 0,
+            asleep: false,
+            ram_new: FrameBuffer::new(),
+            ram_old: FrameBuffer::new(),
+            shown: FrameBuffer::new(),
+            full_refreshes: 0,
+            partial_refreshes: 0,
+        }
+    }
+
+    /// Returns the pixels shown on the display.
+    pub(super) fn shown(&self) -> &FrameBuffer {
+        &self.shown
+    }
+
+    /// Returns the number of refreshes in a mode.
+    pub(super) fn refreshes(&self, mode: RefreshMode) -> u32 {
+        match mode {
+            RefreshMode::Full => self.full_refreshes,
+            RefreshMode::Partial => self.partial_refreshes,
+        }
+    }
+
+    /// Resets the controller using the reset pin, which wakes it from deep sleep but
+    /// retains the RAM.
+    fn reset(&mut self) {
+        self.asleep = false;
+        self.software_reset();
+    }
+
+    /// Resets the registers, but not the RAM.
+    fn software_reset(&mut self) {
+        self.command = 0;
+        self.index = 0;
+        self.x = 0;
+        self.y = 0;
+        self.option = 0;
+    }
+
+    /// Receives bytes over the SPI bus.
+    fn write(&mut self, bytes: &[u8]) {
+        if self.asleep {
+            return;
+        }
+        for byte in bytes {
+            if self.data {
+                self.receive_data(*byte);
+            } else {
+                self.receive_command(*byte);
+            }
+        }
+    }
+
+    /// Acts on a command byte.
+    fn receive_command(&mut self, command: u8) {
+        self.command = command;
+        self.index = 0;
+        match command {
+            cmd::SW_RESET => self.software_reset(),
+            cmd::MASTER_ACTIVATION => self.activate(),
+            _ => (),
+        }
+    }
+
+    /// Acts on a data byte of the last command.
+    fn receive_data(&mut self, byte: u8) {
+        match (self.command, self.index) {
+            (cmd::DEEP_SLEEP, 0) => self.asleep = byte & 0x03 != 0,
+            (cmd::UPDATE_CONTROL_2, 0) => self.option = byte,
+            (cmd::RAM_X_COUNTER, 0) => self.x = usize::from(byte & 0x1F),
+            (cmd::RAM_Y_COUNTER, 0) => self.y = usize::from(byte),
+            (cmd::RAM_Y_COUNTER, 1) => self.y |= usize::from(byte & 0x01) << 8,
+            (cmd::WRITE_RAM_NEW | cmd::WRITE_RAM_OLD, _) => {
+                let ram = if self.command == cmd::WRITE_RAM_NEW {
+                    &mut self.ram_new
+                } else {
+                    &mut self.ram_old
+                };
+                let address = self.y * FrameBuffer::ROW_BYTES + self.x;
+                if let Some(ram_byte) = ram.as_bytes_mut().get_mut(address) {
+                    *ram_byte = byte;
+                }
+
+                // Increment X then Y, wrapping within the window of the whole display
+                self.x += 1;
+                if self.x == FrameBuffer::ROW_BYTES {
+                    self.x = 0;
+                    self.y = (self.y + 1) % HEIGHT as usize;
+                }
+            }
+            _ => (),
+        }
+        self.index += 1;
+    }
+
+    /// Runs the display update sequence.
+    fn activate(&mut self) {
+        if self.option & cmd::UPDATE_DISPLAY == 0 {
+            return;
+        }
+
+        if self.option & cmd::UPDATE_MODE_2 != 0 {
+            // Only the pixels that differ between the RAMs are driven
+            for ((shown, new), old) in self
+                .shown
+                .as_bytes_mut()
+                .iter_mut()
+                .zip(self.ram_new.as_bytes())
+                .zip(self.ram_old.as_bytes())
+            {
+                let changed = new ^ old;
+                *shown = (*shown & !changed) | (new & changed);
+            }
+            self.partial_refreshes += 1;
+        } else {
+            self.shown.clone_from(&self.ram_new);
+            self.full_refreshes += 1;
+        }
+    }
+}
+
+/// Simulated SPI device of the display controller of a [`Watch`].
+#[derive(Debug, Clone)]
+pub struct Spi {
+    /// The watch.
+    watch: Watch,
+}
+impl Spi {
+    /// Creates the SPI device of the display controller of a watch.
+    pub(crate) fn new(watch: &Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+        }
+    }
+}
+impl spi::ErrorType for Spi {
+    type Error = Infallible;
+}
+impl spi::SpiDevice for Spi {
+    fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let mut state = self.watch.state.borrow_mut();
+        let controller = &mut state.controller;
+        for operation in operations {
+            // NOTE: The controller is only written to, so nothing is ever read back.
+            match operation {
+                spi::Operation::Write(bytes) => controller.write(bytes),
+                spi::Operation::Transfer(read, write) => {
+                    controller.write(write);
+                    read.fill(0);
+                }
+                spi::Operation::TransferInPlace(bytes) => {
+                    controller.write(bytes);
+                    bytes.fill(0);
+                }
+                spi::Operation::Read(buf) => buf.fill(0),
+                spi::Operation::DelayNs(_) => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Simulated data/command pin of the display controller of a [`Watch`].
+#[derive(Debug, Clone)]
+pub struct DataCommand {
+    /// The watch.
+    watch: Watch,
+}
+impl DataCommand {
+    /// Creates the data/command pin of the display controller of a watch.
+    pub(crate) fn new(watch: &Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+        }
+    }
+}
+impl digital::ErrorType for DataCommand {
+    type Error = Infallible;
+}
+impl digital::OutputPin for DataCommand {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.watch.state.borrow_mut().controller.data = false;
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.watch.state.borrow_mut().controller.data = true;
+        Ok(())
+    }
+}
+
+/// Simulated reset pin of the display controller of a [`Watch`].
+#[derive(Debug, Clone)]
+pub struct Reset {
+    /// The watch.
+    watch: Watch,
+}
+impl Reset {
+    /// Creates the reset pin of the display controller of a watch.
+    pub(crate) fn new(watch: &Watch) -> Self {
+        Self {
+            watch: watch.clone(),
+        }
+    }
+}
+impl digital::ErrorType for Reset {
+    type Error = Infallible;
+}
+impl digital::OutputPin for Reset {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.watch.state.borrow_mut().controller.reset();
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Simulated busy pin of the display controller, which is never high since the
+/// simulated controller finishes everything straight away.
+#[derive(Debug, Clone)]
+pub struct Busy {
+    /// Prevents construction outside of this crate.
+    _private: (),
+}
+impl Busy {
+    /// Creates the busy pin of the display controller.
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+impl digital::ErrorType for Busy {
+    type Error = Infallible;
+}
+impl digital::InputPin for Busy {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
@@ -0,0 +1,202 @@
+//! Commands that drive the simulator, and their parsing from scripts.
+
+use crate::{
+    button::Button,
+    rtc::{Alarm, DateTime},
+};
+use thiserror::Error;
+
+/// Error for a line of a script that is not a valid [`Command`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The command is not known.
+    #[error("Unknown command `{0}`")]
+    UnknownCommand(String),
+    /// The command is missing an argument.
+    #[error("Missing argument")]
+    MissingArgument,
+    /// An argument of the command is not valid.
+    #[error("Invalid argument `{0}`")]
+    InvalidArgument(String),
+    /// The command has more arguments than it takes.
+    #[error("Unexpected argument `{0}`")]
+    UnexpectedArgument(String),
+}
+
+/// A command that drives the [`Simulator`](crate::face::Simulator).
+///
+/// In a script, each line holds a command followed by its arguments, separated by
+/// whitespace.
+/// Blank lines and lines starting with `#` are ignored.
+///
+/// # Example
+/// ```text
+/// # Set up the watch before it boots
+/// time 2024-10-19 07:55
+/// battery 3900
+/// alarm 08:30
+///
+/// # Open the menu and move down it
+/// press menu
+/// press down
+/// screenshot menu
+///
+/// # Go back and let the rest of the day go by
+/// press back
+/// steps 2500
+/// wait 1d
+/// screenshot tomorrow
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Sets the clock, such as `time 2024-10-19 13:05:00`, with the seconds being
+    /// optional.
+    ///
+    /// Like the RTC chip, the year must be from 2000 to 2199.
+    SetTime(DateTime),
+    /// Sets the battery voltage in mV, such as `battery 3800`.
+    SetBattery(u32),
+    /// Sets the step count, such as `steps 1234`.
+    SetSteps(u32),
+    /// Sets the time of day of the daily alarm of the RTC chip, such as `alarm 07:30`, or
+    /// clears it with `alarm off`.
+    SetAlarm(Option<Alarm>),
+    /// Presses and releases a button, such as `press menu`, with the buttons being
+    /// `menu`, `back`, `up`, and `down`.
+    Press(Button),
+    /// Fast-forwards through deep sleep by a number of seconds, waking every minute,
+    /// such as `wait 90s`, `wait 10m`, `wait 2h`, or `wait 1d`.
+    Wait(u64),
+    /// Resets the ESP32, such as `reset`.
+    Reset,
+    /// Writes the image shown on the display to a PBM file with a name in the output
+    /// directory, such as `screenshot menu` for `menu.pbm`.
+    Screenshot(String),
+}
+impl Command {
+    /// Parses a line of a script, returning `None` if it is blank or a comment.
+    ///
+    /// # Example
+    /// ```
+    /// use watchy::{button::Button, sim::Command};
+    ///
+    /// assert_eq!(
+    ///     Command::parse("press menu"),
+    ///     Ok(Some(Command::Press(Button::Menu)))
+    /// );
+    /// assert_eq!(Command::parse("wait 2h"), Ok(Some(Command::Wait(7200))));
+    /// assert_eq!(Command::parse("# Comment"), Ok(None));
+    /// assert!(Command::parse("time 1999-12-31 23:59").is_err());
+    /// ```
+    pub fn parse(line: &str) -> Result<Option<Self>, ScriptError> {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next().filter(|name| !name.starts_with('#')) else {
+            return Ok(None);
+        };
+        let mut argument = || words.next().ok_or(ScriptError::MissingArgument);
+
+        let command = match name {
+            "time" => {
+                let date = argument()?;
+                let time = argument()?;
+                Self::SetTime(parse_date_time(date, time)?)
+            }
+            "battery" => Self::SetBattery(parse_number(argument()?)?),
+            "steps" => Self::SetSteps(parse_number(argument()?)?),
+            "alarm" => match argument()? {
+                "off" => Self::SetAlarm(None),
+                time => {
+                    let [hour, minute] = parse_fields(time, ':')?;
+                    if hour >= 24 || minute >= 60 {
+                        return Err(ScriptError::InvalidArgument(time.into()));
+                    }
+                    Self::SetAlarm(Some(Alarm { hour, minute }))
+                }
+            },
+            "press" => Self::Press(match argument()? {
+                "menu" => Button::Menu,
+                "back" => Button::Back,
+                "up" => Button::Up,
+                "down" => Button::Down,
+                button => return Err(ScriptError::InvalidArgument(button.into())),
+            }),
+            "wait" => Self::Wait(parse_duration(argument()?)?),
+            "reset" => Self::Reset,
+            "screenshot" => {
+                let name = argument()?;
+                if name.contains(['/', '\\']) {
+                    return Err(ScriptError::InvalidArgument(name.into()));
+                }
+                Self::Screenshot(name.into())
+            }
+            _ => return Err(ScriptError::UnknownCommand(name.into())),
+        };
+
+        match words.next() {
+            Some(extra) => Err(ScriptError::UnexpectedArgument(extra.into())),
+            None => Ok(Some(command)),
+        }
+    }
+}
+
+/// Parses a decimal number.
+fn parse_number<T: core::str::FromStr>(word: &str) -> Result<T, ScriptError> {
+    word.parse()
+        .map_err(|_| ScriptError::InvalidArgument(word.into()))
+}
+
+/// Parses a number of fields separated by a character, such as the parts of a date.
+fn parse_fields<T: core::str::FromStr, const N: usize>(
+    word: &str,
+    separator: char,
+) -> Result<[T; N], ScriptError> {
+    let fields = word
+        .split(separator)
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+    fields
+        .try_into()
+        .map_err(|_| ScriptError::InvalidArgument(word.into()))
+}
+
+/// Parses a date such as `2024-10-19` and a time such as `13:05` or `13:05:00`.
+fn parse_date_time(date: &str, time: &str) -> Result<DateTime, ScriptError> {
+    let [year, month, day] = parse_fields::<u16, 3>(date, '-')?;
+    let (hour, minute, second) = match parse_fields::<u8, 3>(time, ':') {
+        Ok([hour, minute, second]) => (hour, minute, second),
+        Err(_) => {
+            let [hour, minute] = parse_fields::<u8, 2>(time, ':')?;
+            (hour, minute, 0)
+        }
+    };
+
+    let date_time = DateTime {
+        year,
+        month: month
+            .try_into()
+            .map_err(|_| ScriptError::InvalidArgument(date.into()))?,
+        day: day
+            .try_into()
+            .map_err(|_| ScriptError::InvalidArgument(date.into()))?,
+        hour,
+        minute,
+        second,
+    };
+    (date_time.is_valid() && (2000..=2199).contains(&date_time.year))
+        .then_some(date_time)
+        .ok_or_else(|| ScriptError::InvalidArgument(format!("{date} {time}")))
+}
+
+/// Parses a duration such as `90s`, `10m`, `2h`, or `1d` into seconds.
+fn parse_duration(word: &str) -> Result<u64, ScriptError> {
+    let invalid = || ScriptError::InvalidArgument(word.into());
+    let unit = match word.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 3600,
+        Some('d') => 86400,
+        _ => return Err(invalid()),
+    };
+    let count: u64 = word[..word.len() - 1].parse().map_err(|_| invalid())?;
+    count.checked_mul(unit).ok_or_else(invalid)
+}
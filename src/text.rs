@@ -0,0 +1,103 @@
+//! Fonts and text rendering tuned for the 200x200 display.
+//!
+//! Text is drawn using [`Text`](embedded_graphics::text::Text) with either a
+//! [`FontStyle`] for one of the bitmap fonts or a [`SevenSegmentStyle`], which can draw
+//! on a [`FrameBuffer`](crate::display::FrameBuffer) or anything else.
+//! Strings can be measured, and are aligned using the
+//! [`Alignment`](embedded_graphics::text::Alignment) and
+//! [`Baseline`](embedded_graphics::text::Baseline) of the text.
+//!
+//! The built-in fonts are:
+//! - [`LARGE_DIGITS`] for the time, which fits `12:34` across the display.
+//! - [`SMALL`] for dates, labels, and menus, which is proportional and has all of the
+//!   Latin-1 characters.
+//!
+//! The [`SevenSegmentStyle`] draws the digits of a digital clock at any size.
+//!
+//! Other BDF fonts, such as those with Cyrillic or other characters, can be converted
+//! at build time using the `watchy-assets` crate and then included using
+//! [`include_font`](crate::include_font).
+//!
+//! # Example
+//! ```no_run
+//! use embedded_graphics::{
+//!     pixelcolor::BinaryColor,
+//!     prelude::*,
+//!     text::{Alignment, Baseline, Text, TextStyleBuilder},
+//! };
+//! use watchy::{
+//!     display::FrameBuffer,
+//!     text::{FontStyle, LARGE_DIGITS, SMALL},
+//! };
+//!
+//! let mut frame = FrameBuffer::new();
+//! let centered = TextStyleBuilder::new()
+//!     .alignment(Alignment::Center)
+//!     .baseline(Baseline::Middle)
+//!     .build();
+//!
+//! Text::with_text_style(
+//!     "12:34",
+//!     Point::new(100, 90),
+//!     FontStyle::new(&LARGE_DIGITS, BinaryColor::On),
+//!     centered,
+//! )
+//! .draw(&mut frame)
+//! .unwrap();
+//! Text::with_text_style(
+//!     "Mon 1 Jan",
+//!     Point::new(100, 150),
+//!     FontStyle::new(&SMALL, BinaryColor::On),
+//!     centered,
+//! )
+//! .draw(&mut frame)
+//! .unwrap();
+//! ```
+
+mod font;
+mod format;
+mod seven_segment;
+
+pub use font::{Font, FontStyle, Glyph};
+pub(crate) use format::Formatted;
+pub use seven_segment::SevenSegmentStyle;
+
+/// Large digits for the time, which are 64 pixels high and 44 pixels wide including the
+/// spacing.
+///
+/// Only the digits, `:`, `.`, `-`, and space are included, with `:` and `.` being
+/// narrow.
+/// Space is the same width as the digits so that it can be used for padding.
+pub const LARGE_DIGITS: Font<'static> = include!("text/large_digits.rs");
+
+/// Small proportional font for dates, labels, and menus, with all of the Latin-1
+/// characters.
+///
+/// This is 13 pixels high, and is converted from the public domain `6x13` X11 fixed
+/// font.
+pub const SMALL: Font<'static> = include!("text/small.rs");
+
+/// Includes a [`Font`](crate::text::Font) that was converted from a BDF font file by the
+/// build script using the `watchy-assets` crate.
+///
+/// The name is that given to the build helper, which writes the font to the `OUT_DIR`
+/// of the crate.
+///
+/// # Example
+/// In `build.rs`:
+/// ```ignore
+/// fn main() {
+///     watchy_assets::include_font("cyrillic", "fonts/cyrillic.bdf", &Default::default())
+///         .unwrap();
+/// }
+/// ```
+/// Then in the crate:
+/// ```ignore
+/// const CYRILLIC: watchy::text::Font = watchy::include_font!("cyrillic");
+/// ```
+#[macro_export]
+macro_rules! include_font {
+    ($name:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $name, ".font.rs"))
+    };
+}
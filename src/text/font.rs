@@ -0,0 +1,324 @@
+//! Proportional bitmap fonts.
+
+use embedded_graphics::{
+    pixelcolor::PixelColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        renderer::{CharacterStyle, TextMetrics, TextRenderer},
+        Baseline,
+    },
+};
+
+/// The bitmap and metrics of a single character of a [`Font`].
+///
+/// The bitmap is packed one bit per pixel in rows from the top left, with the most
+/// significant bit of each byte being the leftmost pixel and each row padded to a whole
+/// byte.
+/// Unlike a [`Bitmap`](crate::display::Bitmap), a set bit is drawn in the text color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    /// The character.
+    character: char,
+    /// The horizontal distance to the start of the next character in pixels.
+    advance: u8,
+    /// The width of the bitmap in pixels.
+    width: u8,
+    /// The height of the bitmap in pixels.
+    height: u8,
+    /// The horizontal offset of the left of the bitmap from the start of the character.
+    x_offset: i8,
+    /// The vertical offset of the bottom of the bitmap above the baseline.
+    y_offset: i8,
+    /// The index of the bitmap in the font data.
+    offset: u32,
+}
+impl Glyph {
+    /// Creates a glyph, which is normally only done by code generated by the
+    /// `watchy-assets` crate.
+    ///
+    /// The offsets are those of the bounding box of the bitmap in a BDF font, with the
+    /// vertical offset being that of the bottom row above the baseline.
+    pub const fn new(
+        character: char,
+        advance: u8,
+        width: u8,
+        height: u8,
+        x_offset: i8,
+        y_offset: i8,
+        offset: u32,
+    ) -> Self {
+        Self {
+            character,
+            advance,
+            width,
+            height,
+            x_offset,
+            y_offset,
+            offset,
+        }
+    }
+
+    /// Returns the character.
+    pub const fn character(&self) -> char {
+        self.character
+    }
+
+    /// Returns the horizontal distance to the start of the next character in pixels.
+    pub const fn advance(&self) -> u32 {
+        self.advance as u32
+    }
+}
+
+/// A bitmap font in which each character can have a different width.
+///
+/// The built-in fonts are constants in the [`text`](super) module, and others can be
+/// converted from BDF fonts at build time using the `watchy-assets` crate and then
+/// included using [`include_font`](crate::include_font).
+/// Text is drawn with a [`FontStyle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Font<'a> {
+    /// The glyphs sorted by character.
+    glyphs: &'a [Glyph],
+    /// The packed bitmaps of all of the glyphs.
+    data: &'a [u8],
+    /// The number of rows at and above the baseline.
+    ascent: u32,
+    /// The number of rows below the baseline.
+    descent: u32,
+    /// The character drawn in place of those not in the font.
+    replacement: char,
+}
+impl<'a> Font<'a> {
+    /// Creates a font, which is normally only done by code generated by the
+    /// `watchy-assets` crate.
+    ///
+    /// # Panics
+    /// This panics if the glyphs are not sorted by character.
+    pub const fn new(
+        glyphs: &'a [Glyph],
+        data: &'a [u8],
+        ascent: u32,
+        descent: u32,
+        replacement: char,
+    ) -> Self {
+        let mut i = 1;
+        while i < glyphs.len() {
+            assert!(
+                glyphs[i - 1].character < glyphs[i].character,
+                "the glyphs must be sorted by character"
+            );
+            i += 1;
+        }
+        Self {
+            glyphs,
+            data,
+            ascent,
+            descent,
+            replacement,
+        }
+    }
+
+    /// Returns the height of a line of text in pixels.
+    pub const fn line_height(&self) -> u32 {
+        self.ascent + self.descent
+    }
+
+    /// Returns the number of rows of pixels at and above the baseline.
+    pub const fn ascent(&self) -> u32 {
+        self.ascent
+    }
+
+    /// Returns the glyph of a character, or that of the replacement character if it is
+    /// not in the font.
+    pub fn glyph(&self, character: char) -> Option<&Glyph> {
+        let find = |character| {
+            self.glyphs
+                .binary_search_by_key(&character, |glyph| glyph.character)
+                .ok()
+                .map(|index| &self.glyphs[index])
+        };
+        find(character).or_else(|| find(self.replacement))
+    }
+
+    /// Returns the width of a string in pixels.
+    pub fn measure(&self, text: &str) -> u32 {
+        text.chars()
+            .filter_map(|character| self.glyph(character))
+            .map(Glyph::advance)
+            .sum()
+    }
+
+    /// Returns the positions of the pixels of a glyph that are drawn in the text color,
+    /// relative to the start of the character on the baseline.
+    fn ink(&self, glyph: &Glyph) -> impl Iterator<Item = Point> + 'a {
+        let row_bytes = usize::from(glyph.width).div_ceil(8);
+        let bitmap = self.data.get(glyph.offset as usize..).unwrap_or_default();
+        let top = -i32::from(glyph.y_offset) - i32::from(glyph.height) + 1;
+        let (width, height, x_offset) = (glyph.width, glyph.height, glyph.x_offset);
+
+        (0..height).flat_map(move |y| {
+            (0..width).filter_map(move |x| {
+                let byte = bitmap.get(usize::from(y) * row_bytes + usize::from(x) / 8)?;
+                (byte & (0x80 >> (x % 8)) != 0).then_some(Point::new(
+                    i32::from(x_offset) + i32::from(x),
+                    top + i32::from(y),
+                ))
+            })
+        })
+    }
+}
+
+/// Style to draw text in a proportional [`Font`], for use with
+/// [`Text`](embedded_graphics::text::Text).
+///
+/// Text can be aligned horizontally by using [`Text::with_alignment`], and vertically by
+/// using [`Text::with_baseline`] or a [`TextStyle`](embedded_graphics::text::TextStyle).
+///
+/// [`Text::with_alignment`]: embedded_graphics::text::Text::with_alignment
+/// [`Text::with_baseline`]: embedded_graphics::text::Text::with_baseline
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     text::{Alignment, Text},
+/// };
+/// use watchy::{
+///     display::FrameBuffer,
+///     text::{FontStyle, SMALL},
+/// };
+///
+/// let mut frame = FrameBuffer::new();
+/// // Centered at the top of the display
+/// Text::with_alignment(
+///     "Monday 1 January",
+///     Point::new(100, 20),
+///     FontStyle::new(&SMALL, BinaryColor::On),
+///     Alignment::Center,
+/// )
+/// .draw(&mut frame)
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontStyle<'a, C> {
+    /// The font.
+    pub font: &'a Font<'a>,
+    /// The color of the characters, which are not drawn if this is `None`.
+    pub text_color: Option<C>,
+    /// The color of the background behind the characters, which is transparent if this
+    /// is `None`.
+    pub background_color: Option<C>,
+}
+impl<'a, C> FontStyle<'a, C> {
+    /// Creates a style with a transparent background.
+    pub const fn new(font: &'a Font<'a>, text_color: C) -> Self {
+        Self {
+            font,
+            text_color: Some(text_color),
+            background_color: None,
+        }
+    }
+
+    /// Returns the vertical offset between the position of the text and the top of the
+    /// line.
+    fn baseline_offset(&self, baseline: Baseline) -> i32 {
+        let height = self.font.line_height().saturating_sub(1) as i32;
+        match baseline {
+            Baseline::Top => 0,
+            Baseline::Bottom => height,
+            Baseline::Middle => height / 2,
+            Baseline::Alphabetic => self.font.ascent.saturating_sub(1) as i32,
+        }
+    }
+}
+impl<C: PixelColor> CharacterStyle for FontStyle<'_, C> {
+    type Color = C;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.text_color = text_color;
+    }
+
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+}
+impl<C: PixelColor> TextRenderer for FontStyle<'_, C> {
+    type Color = C;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top = position.y - self.baseline_offset(baseline);
+        let baseline = top + self.font.ascent.saturating_sub(1) as i32;
+        let mut x = position.x;
+
+        for glyph in text.chars().filter_map(|c| self.font.glyph(c)) {
+            if let Some(color) = self.background_color {
+                target.fill_solid(
+                    &Rectangle::new(
+                        Point::new(x, top),
+                        Size::new(glyph.advance(), self.font.line_height()),
+                    ),
+                    color,
+                )?;
+            }
+            if let Some(color) = self.text_color {
+                let origin = Point::new(x, baseline);
+                target.draw_iter(
+                    self.font
+                        .ink(glyph)
+                        .map(|point| Pixel(origin + point, color)),
+                )?;
+            }
+            x += glyph.advance() as i32;
+        }
+
+        Ok(Point::new(x, position.y))
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if let Some(color) = self.background_color {
+            target.fill_solid(
+                &Rectangle::new(
+                    position - Point::new(0, self.baseline_offset(baseline)),
+                    Size::new(width, self.font.line_height()),
+                ),
+                color,
+            )?;
+        }
+        Ok(position + Size::new(width, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let size = Size::new(self.font.measure(text), self.font.line_height());
+        TextMetrics {
+            bounding_box: Rectangle::new(
+                position - Point::new(0, self.baseline_offset(baseline)),
+                size,
+            ),
+            next_position: position + size.x_axis(),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        self.font.line_height()
+    }
+}
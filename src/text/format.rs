@@ -0,0 +1,39 @@
+//! Formatting of short strings without allocating.
+
+use core::fmt::{Arguments, Write};
+
+/// A short string formatted into a buffer of `N` bytes.
+pub(crate) struct Formatted<const N: usize> {
+    /// The formatted bytes.
+    buf: [u8; N],
+    /// The number of bytes used.
+    len: usize,
+}
+impl<const N: usize> Formatted<N> {
+    /// Formats the arguments, leaving out anything that does not fit.
+    pub(crate) fn new(args: Arguments) -> Self {
+        let mut formatted = Self {
+            buf: [0; N],
+            len: 0,
+        };
+        let _ = formatted.write_fmt(args);
+        formatted
+    }
+
+    /// Returns the formatted string.
+    pub(crate) fn as_str(&self) -> &str {
+        // The buffer only ever has whole strings written into it
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+impl<const N: usize> Write for Formatted<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
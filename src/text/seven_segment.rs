@@ -0,0 +1,331 @@
+//! Seven-segment style characters of any size.
+
+use embedded_graphics::{
+    pixelcolor::PixelColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        renderer::{CharacterStyle, TextMetrics, TextRenderer},
+        Baseline,
+    },
+};
+
+/// Bits for each of the segments, using the conventional letters.
+mod segment {
+    /// Top.
+    pub const A: u8 = 0x01;
+    /// Top right.
+    pub const B: u8 = 0x02;
+    /// Bottom right.
+    pub const C: u8 = 0x04;
+    /// Bottom.
+    pub const D: u8 = 0x08;
+    /// Bottom left.
+    pub const E: u8 = 0x10;
+    /// Top left.
+    pub const F: u8 = 0x20;
+    /// Middle.
+    pub const G: u8 = 0x40;
+}
+
+/// A character that can be displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    /// A full width character with the segments that are lit.
+    Segments(u8),
+    /// A narrow colon.
+    Colon,
+    /// A narrow decimal point.
+    Point,
+}
+impl Shape {
+    /// Returns the shape of a character, or `None` if it cannot be displayed.
+    fn new(character: char) -> Option<Self> {
+        use segment::*;
+
+        Some(Self::Segments(match character {
+            '0' | 'O' => A | B | C | D | E | F,
+            '1' => B | C,
+            '2' => A | B | D | E | G,
+            '3' => A | B | C | D | G,
+            '4' => B | C | F | G,
+            '5' | 'S' | 's' => A | C | D | F | G,
+            '6' => A | C | D | E | F | G,
+            '7' => A | B | C,
+            '8' => A | B | C | D | E | F | G,
+            '9' => A | B | C | D | F | G,
+            'A' | 'a' => A | B | C | E | F | G,
+            'B' | 'b' => C | D | E | F | G,
+            'C' => A | D | E | F,
+            'c' => D | E | G,
+            'D' | 'd' => B | C | D | E | G,
+            'E' | 'e' => A | D | E | F | G,
+            'F' | 'f' => A | E | F | G,
+            'H' => B | C | E | F | G,
+            'h' => C | E | F | G,
+            'L' | 'l' => D | E | F,
+            'n' => C | E | G,
+            'o' => C | D | E | G,
+            'P' | 'p' => A | B | E | F | G,
+            'r' => E | G,
+            't' => D | E | F | G,
+            'U' | 'u' => B | C | D | E | F,
+            '-' => G,
+            '_' => D,
+            ' ' => 0,
+            ':' => return Some(Self::Colon),
+            '.' => return Some(Self::Point),
+            _ => return None,
+        }))
+    }
+}
+
+/// Style to draw text as the seven-segment characters of a digital clock, for use with
+/// [`Text`](embedded_graphics::text::Text).
+///
+/// The digits, hexadecimal letters, some other letters, `-`, `_`, and space are full
+/// width, whereas `:` and `.` are as narrow as a segment.
+/// Other characters are not drawn.
+/// The segments are drawn with pointed ends and small gaps between them, at any size.
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::{
+///     pixelcolor::BinaryColor,
+///     prelude::*,
+///     text::{Alignment, Baseline, Text, TextStyleBuilder},
+/// };
+/// use watchy::{display::FrameBuffer, text::SevenSegmentStyle};
+///
+/// let mut frame = FrameBuffer::new();
+/// // Fills most of the width of the display
+/// Text::with_text_style(
+///     "12:34",
+///     Point::new(100, 100),
+///     SevenSegmentStyle::new(Size::new(36, 64), 7, BinaryColor::On),
+///     TextStyleBuilder::new()
+///         .alignment(Alignment::Center)
+///         .baseline(Baseline::Middle)
+///         .build(),
+/// )
+/// .draw(&mut frame)
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SevenSegmentStyle<C> {
+    /// The size of a full width character.
+    pub digit_size: Size,
+    /// The thickness of the segments.
+    pub segment_width: u32,
+    /// The horizontal space after each character.
+    pub spacing: u32,
+    /// The color of the lit segments, which are not drawn if this is `None`.
+    pub segment_color: Option<C>,
+    /// The color of the background behind the characters, which is transparent if this
+    /// is `None`.
+    pub background_color: Option<C>,
+}
+impl<C: PixelColor> SevenSegmentStyle<C> {
+    /// Creates a style with a transparent background and spacing equal to the segment
+    /// width.
+    pub const fn new(digit_size: Size, segment_width: u32, segment_color: C) -> Self {
+        Self {
+            digit_size,
+            segment_width,
+            spacing: segment_width,
+            segment_color: Some(segment_color),
+            background_color: None,
+        }
+    }
+
+    /// Returns the width of a character shape without the spacing.
+    fn shape_width(&self, shape: Shape) -> u32 {
+        match shape {
+            Shape::Segments(_) => self.digit_size.width,
+            Shape::Colon | Shape::Point => self.segment_width,
+        }
+    }
+
+    /// Returns the width of a string in pixels.
+    pub fn measure(&self, text: &str) -> u32 {
+        text.chars()
+            .filter_map(Shape::new)
+            .map(|shape| self.shape_width(shape) + self.spacing)
+            .sum()
+    }
+
+    /// Returns the vertical offset between the position of the text and the top of the
+    /// characters.
+    fn baseline_offset(&self, baseline: Baseline) -> i32 {
+        let height = self.digit_size.height.saturating_sub(1) as i32;
+        match baseline {
+            Baseline::Top => 0,
+            Baseline::Middle => height / 2,
+            Baseline::Bottom | Baseline::Alphabetic => height,
+        }
+    }
+
+    /// Draws a character shape with its top left corner at a point.
+    fn draw_shape<D>(
+        &self,
+        shape: Shape,
+        top_left: Point,
+        color: C,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let width = self.segment_width.max(1) as i32;
+        let (digit_width, digit_height) =
+            (self.digit_size.width as i32, self.digit_size.height as i32);
+        // Offset of the center line of a segment from its outside edge
+        let center = (width - 1) / 2;
+        let middle = (digit_height - 1) / 2;
+        let square = |y| {
+            Rectangle::new(
+                top_left + Point::new(0, y),
+                Size::new(width as u32, width as u32),
+            )
+        };
+
+        match shape {
+            Shape::Segments(segments) => {
+                let left = center;
+                let right = digit_width - 1 - center;
+                let top = center;
+                let bottom = digit_height - 1 - center;
+
+                // The start, end and outside edge of each segment
+                let horizontal = [
+                    (segment::A, 0),
+                    (segment::G, middle - center),
+                    (segment::D, digit_height - width),
+                ];
+                let vertical = [
+                    (segment::F, top, middle, 0),
+                    (segment::B, top, middle, digit_width - width),
+                    (segment::E, middle, bottom, 0),
+                    (segment::C, middle, bottom, digit_width - width),
+                ];
+
+                for i in 0..width {
+                    // The segments taper to a point at each end, with a gap between them
+                    let inset = (2 * i + 1 - width).abs() / 2 + 1;
+                    for (_, y) in horizontal.iter().filter(|(bit, _)| segments & bit != 0) {
+                        let (start, end) = (left + inset, right - inset);
+                        if start <= end {
+                            target.fill_solid(
+                                &Rectangle::new(
+                                    top_left + Point::new(start, y + i),
+                                    Size::new((end - start + 1) as u32, 1),
+                                ),
+                                color,
+                            )?;
+                        }
+                    }
+                    for (_, start, end, x) in
+                        vertical.iter().filter(|(bit, ..)| segments & bit != 0)
+                    {
+                        let (start, end) = (start + inset, end - inset);
+                        if start <= end {
+                            target.fill_solid(
+                                &Rectangle::new(
+                                    top_left + Point::new(x + i, start),
+                                    Size::new(1, (end - start + 1) as u32),
+                                ),
+                                color,
+                            )?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Shape::Colon => {
+                target.fill_solid(&square(digit_height / 3 - center), color)?;
+                target.fill_solid(&square(digit_height * 2 / 3 - center), color)
+            }
+            Shape::Point => target.fill_solid(&square(digit_height - width), color),
+        }
+    }
+}
+impl<C: PixelColor> CharacterStyle for SevenSegmentStyle<C> {
+    type Color = C;
+
+    fn set_text_color(&mut self, text_color: Option<Self::Color>) {
+        self.segment_color = text_color;
+    }
+
+    fn set_background_color(&mut self, background_color: Option<Self::Color>) {
+        self.background_color = background_color;
+    }
+}
+impl<C: PixelColor> TextRenderer for SevenSegmentStyle<C> {
+    type Color = C;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut top_left = position - Point::new(0, self.baseline_offset(baseline));
+
+        for shape in text.chars().filter_map(Shape::new) {
+            let advance = self.shape_width(shape) + self.spacing;
+            if let Some(color) = self.background_color {
+                target.fill_solid(
+                    &Rectangle::new(top_left, Size::new(advance, self.digit_size.height)),
+                    color,
+                )?;
+            }
+            if let Some(color) = self.segment_color {
+                self.draw_shape(shape, top_left, color, target)?;
+            }
+            top_left.x += advance as i32;
+        }
+
+        Ok(Point::new(top_left.x, position.y))
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if let Some(color) = self.background_color {
+            target.fill_solid(
+                &Rectangle::new(
+                    position - Point::new(0, self.baseline_offset(baseline)),
+                    Size::new(width, self.digit_size.height),
+                ),
+                color,
+            )?;
+        }
+        Ok(position + Size::new(width, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let size = Size::new(self.measure(text), self.digit_size.height);
+        TextMetrics {
+            bounding_box: Rectangle::new(
+                position - Point::new(0, self.baseline_offset(baseline)),
+                size,
+            ),
+            next_position: position + size.x_axis(),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        self.digit_size.height
+    }
+}
@@ -0,0 +1,298 @@
+{
+    use crate::text::{Font, Glyph};
+
+    const GLYPHS: &[Glyph] = &[
+        Glyph::new(' ', 4, 0, 0, 0, 0, 0),
+        Glyph::new('!', 2, 1, 9, 0, 0, 0),
+        Glyph::new('"', 4, 3, 3, 0, 6, 9),
+        Glyph::new('#', 6, 5, 7, 0, 1, 12),
+        Glyph::new('$', 6, 5, 9, 0, 0, 19),
+        Glyph::new('%', 6, 5, 9, 0, 0, 28),
+        Glyph::new('&', 6, 5, 8, 0, 0, 37),
+        Glyph::new('\'', 2, 1, 3, 0, 6, 45),
+        Glyph::new('(', 4, 3, 11, 0, -1, 48),
+        Glyph::new(')', 4, 3, 11, 0, -1, 59),
+        Glyph::new('*', 6, 5, 5, 0, 4, 70),
+        Glyph::new('+', 6, 5, 5, 0, 2, 75),
+        Glyph::new(',', 4, 3, 3, 0, -1, 80),
+        Glyph::new('-', 6, 5, 1, 0, 4, 83),
+        Glyph::new('.', 4, 3, 3, 0, -1, 84),
+        Glyph::new('/', 6, 5, 9, 0, 0, 87),
+        Glyph::new('0', 6, 5, 9, 0, 0, 96),
+        Glyph::new('1', 6, 5, 9, 0, 0, 105),
+        Glyph::new('2', 6, 5, 9, 0, 0, 114),
+        Glyph::new('3', 6, 5, 9, 0, 0, 123),
+        Glyph::new('4', 6, 5, 9, 0, 0, 132),
+        Glyph::new('5', 6, 5, 9, 0, 0, 141),
+        Glyph::new('6', 6, 5, 9, 0, 0, 150),
+        Glyph::new('7', 6, 5, 9, 0, 0, 159),
+        Glyph::new('8', 6, 5, 9, 0, 0, 168),
+        Glyph::new('9', 6, 5, 9, 0, 0, 177),
+        Glyph::new(':', 4, 3, 8, 0, -1, 186),
+        Glyph::new(';', 4, 3, 8, 0, -1, 194),
+        Glyph::new('<', 6, 5, 9, 0, 0, 202),
+        Glyph::new('=', 6, 5, 4, 0, 2, 211),
+        Glyph::new('>', 6, 5, 9, 0, 0, 215),
+        Glyph::new('?', 6, 5, 9, 0, 0, 224),
+        Glyph::new('@', 6, 5, 9, 0, 0, 233),
+        Glyph::new('A', 6, 5, 9, 0, 0, 242),
+        Glyph::new('B', 6, 5, 9, 0, 0, 251),
+        Glyph::new('C', 6, 5, 9, 0, 0, 260),
+        Glyph::new('D', 6, 5, 9, 0, 0, 269),
+        Glyph::new('E', 6, 5, 9, 0, 0, 278),
+        Glyph::new('F', 6, 5, 9, 0, 0, 287),
+        Glyph::new('G', 6, 5, 9, 0, 0, 296),
+        Glyph::new('H', 6, 5, 9, 0, 0, 305),
+        Glyph::new('I', 4, 3, 9, 0, 0, 314),
+        Glyph::new('J', 6, 5, 9, 0, 0, 323),
+        Glyph::new('K', 6, 5, 9, 0, 0, 332),
+        Glyph::new('L', 6, 5, 9, 0, 0, 341),
+        Glyph::new('M', 6, 5, 9, 0, 0, 350),
+        Glyph::new('N', 6, 5, 9, 0, 0, 359),
+        Glyph::new('O', 6, 5, 9, 0, 0, 368),
+        Glyph::new('P', 6, 5, 9, 0, 0, 377),
+        Glyph::new('Q', 6, 5, 10, 0, -1, 386),
+        Glyph::new('R', 6, 5, 9, 0, 0, 396),
+        Glyph::new('S', 6, 5, 9, 0, 0, 405),
+        Glyph::new('T', 6, 5, 9, 0, 0, 414),
+        Glyph::new('U', 6, 5, 9, 0, 0, 423),
+        Glyph::new('V', 6, 5, 9, 0, 0, 432),
+        Glyph::new('W', 6, 5, 9, 0, 0, 441),
+        Glyph::new('X', 6, 5, 9, 0, 0, 450),
+        Glyph::new('Y', 6, 5, 9, 0, 0, 459),
+        Glyph::new('Z', 6, 5, 9, 0, 0, 468),
+        Glyph::new('[', 4, 3, 11, 0, -1, 477),
+        Glyph::new('\\', 6, 5, 9, 0, 0, 488),
+        Glyph::new(']', 4, 3, 11, 0, -1, 497),
+        Glyph::new('^', 6, 5, 3, 0, 6, 508),
+        Glyph::new('_', 6, 5, 1, 0, -1, 511),
+        Glyph::new('`', 3, 2, 2, 0, 8, 512),
+        Glyph::new('a', 6, 5, 6, 0, 0, 514),
+        Glyph::new('b', 6, 5, 9, 0, 0, 520),
+        Glyph::new('c', 6, 5, 6, 0, 0, 529),
+        Glyph::new('d', 6, 5, 9, 0, 0, 535),
+        Glyph::new('e', 6, 5, 6, 0, 0, 544),
+        Glyph::new('f', 6, 5, 9, 0, 0, 550),
+        Glyph::new('g', 6, 5, 8, 0, -2, 559),
+        Glyph::new('h', 6, 5, 9, 0, 0, 567),
+        Glyph::new('i', 4, 3, 8, 0, 0, 576),
+        Glyph::new('j', 5, 4, 10, 0, -2, 584),
+        Glyph::new('k', 6, 5, 9, 0, 0, 594),
+        Glyph::new('l', 4, 3, 9, 0, 0, 603),
+        Glyph::new('m', 6, 5, 6, 0, 0, 612),
+        Glyph::new('n', 6, 5, 6, 0, 0, 618),
+        Glyph::new('o', 6, 5, 6, 0, 0, 624),
+        Glyph::new('p', 6, 5, 8, 0, -2, 630),
+        Glyph::new('q', 6, 5, 8, 0, -2, 638),
+        Glyph::new('r', 6, 5, 6, 0, 0, 646),
+        Glyph::new('s', 6, 5, 6, 0, 0, 652),
+        Glyph::new('t', 6, 5, 8, 0, 0, 658),
+        Glyph::new('u', 6, 5, 6, 0, 0, 666),
+        Glyph::new('v', 6, 5, 6, 0, 0, 672),
+        Glyph::new('w', 6, 5, 6, 0, 0, 678),
+        Glyph::new('x', 6, 5, 6, 0, 0, 684),
+        Glyph::new('y', 6, 5, 8, 0, -2, 690),
+        Glyph::new('z', 6, 5, 6, 0, 0, 698),
+        Glyph::new('{', 6, 5, 11, 0, -1, 704),
+        Glyph::new('|', 2, 1, 9, 0, 0, 715),
+        Glyph::new('}', 6, 5, 11, 0, -1, 724),
+        Glyph::new('~', 6, 5, 3, 0, 6, 735),
+        Glyph::new('\u{a0}', 4, 0, 0, 0, 0, 738),
+        Glyph::new('¡', 2, 1, 9, 0, 0, 738),
+        Glyph::new('¢', 6, 5, 8, 0, 1, 747),
+        Glyph::new('£', 6, 5, 9, 0, 0, 755),
+        Glyph::new('¤', 6, 5, 6, 0, 1, 764),
+        Glyph::new('¥', 6, 5, 9, 0, 0, 770),
+        Glyph::new('¦', 2, 1, 9, 0, 0, 779),
+        Glyph::new('§', 5, 4, 10, 0, 0, 788),
+        Glyph::new('¨', 4, 3, 2, 0, 8, 798),
+        Glyph::new('©', 6, 5, 9, 0, 1, 800),
+        Glyph::new('ª', 6, 5, 7, 0, 2, 809),
+        Glyph::new('«', 6, 5, 6, 0, 1, 816),
+        Glyph::new('¬', 6, 5, 3, 0, 2, 822),
+        Glyph::new('\u{ad}', 4, 3, 1, 0, 4, 825),
+        Glyph::new('®', 6, 5, 9, 0, 1, 826),
+        Glyph::new('¯', 6, 5, 1, 0, 8, 835),
+        Glyph::new('°', 5, 4, 4, 0, 5, 836),
+        Glyph::new('±', 6, 5, 7, 0, 1, 840),
+        Glyph::new('²', 4, 3, 5, 0, 5, 847),
+        Glyph::new('³', 4, 3, 5, 0, 5, 852),
+        Glyph::new('´', 3, 2, 2, 0, 8, 857),
+        Glyph::new('µ', 6, 5, 8, 0, -2, 859),
+        Glyph::new('¶', 6, 5, 9, 0, 0, 867),
+        Glyph::new('·', 3, 2, 1, 0, 4, 876),
+        Glyph::new('¸', 3, 2, 2, 0, -2, 877),
+        Glyph::new('¹', 4, 3, 5, 0, 5, 879),
+        Glyph::new('º', 6, 5, 7, 0, 2, 884),
+        Glyph::new('»', 6, 5, 6, 0, 1, 891),
+        Glyph::new('¼', 6, 5, 10, 0, 0, 897),
+        Glyph::new('½', 6, 5, 10, 0, 0, 907),
+        Glyph::new('¾', 6, 5, 10, 0, 0, 917),
+        Glyph::new('¿', 6, 5, 9, 0, 0, 927),
+        Glyph::new('À', 6, 5, 10, 0, 0, 936),
+        Glyph::new('Á', 6, 5, 10, 0, 0, 946),
+        Glyph::new('Â', 6, 5, 10, 0, 0, 956),
+        Glyph::new('Ã', 6, 5, 10, 0, 0, 966),
+        Glyph::new('Ä', 6, 5, 10, 0, 0, 976),
+        Glyph::new('Å', 6, 5, 10, 0, 0, 986),
+        Glyph::new('Æ', 6, 5, 9, 0, 0, 996),
+        Glyph::new('Ç', 6, 5, 11, 0, -2, 1005),
+        Glyph::new('È', 6, 5, 10, 0, 0, 1016),
+        Glyph::new('É', 6, 5, 10, 0, 0, 1026),
+        Glyph::new('Ê', 6, 5, 10, 0, 0, 1036),
+        Glyph::new('Ë', 6, 5, 10, 0, 0, 1046),
+        Glyph::new('Ì', 4, 3, 10, 0, 0, 1056),
+        Glyph::new('Í', 4, 3, 10, 0, 0, 1066),
+        Glyph::new('Î', 5, 4, 10, 0, 0, 1076),
+        Glyph::new('Ï', 4, 3, 10, 0, 0, 1086),
+        Glyph::new('Ð', 6, 5, 9, 0, 0, 1096),
+        Glyph::new('Ñ', 6, 5, 10, 0, 0, 1105),
+        Glyph::new('Ò', 6, 5, 10, 0, 0, 1115),
+        Glyph::new('Ó', 6, 5, 10, 0, 0, 1125),
+        Glyph::new('Ô', 6, 5, 10, 0, 0, 1135),
+        Glyph::new('Õ', 6, 5, 10, 0, 0, 1145),
+        Glyph::new('Ö', 6, 5, 10, 0, 0, 1155),
+        Glyph::new('×', 6, 5, 5, 0, 1, 1165),
+        Glyph::new('Ø', 6, 5, 11, 0, -1, 1170),
+        Glyph::new('Ù', 6, 5, 10, 0, 0, 1181),
+        Glyph::new('Ú', 6, 5, 10, 0, 0, 1191),
+        Glyph::new('Û', 6, 5, 10, 0, 0, 1201),
+        Glyph::new('Ü', 6, 5, 10, 0, 0, 1211),
+        Glyph::new('Ý', 6, 5, 10, 0, 0, 1221),
+        Glyph::new('Þ', 6, 5, 9, 0, 0, 1231),
+        Glyph::new('ß', 6, 5, 9, 0, 0, 1240),
+        Glyph::new('à', 6, 5, 9, 0, 0, 1249),
+        Glyph::new('á', 6, 5, 9, 0, 0, 1258),
+        Glyph::new('â', 6, 5, 9, 0, 0, 1267),
+        Glyph::new('ã', 6, 5, 9, 0, 0, 1276),
+        Glyph::new('ä', 6, 5, 9, 0, 0, 1285),
+        Glyph::new('å', 6, 5, 10, 0, 0, 1294),
+        Glyph::new('æ', 6, 5, 6, 0, 0, 1304),
+        Glyph::new('ç', 6, 5, 8, 0, -2, 1310),
+        Glyph::new('è', 6, 5, 9, 0, 0, 1318),
+        Glyph::new('é', 6, 5, 9, 0, 0, 1327),
+        Glyph::new('ê', 6, 5, 9, 0, 0, 1336),
+        Glyph::new('ë', 6, 5, 9, 0, 0, 1345),
+        Glyph::new('ì', 4, 3, 9, 0, 0, 1354),
+        Glyph::new('í', 4, 3, 9, 0, 0, 1363),
+        Glyph::new('î', 5, 4, 9, 0, 0, 1372),
+        Glyph::new('ï', 4, 3, 9, 0, 0, 1381),
+        Glyph::new('ð', 6, 5, 10, 0, 0, 1390),
+        Glyph::new('ñ', 6, 5, 9, 0, 0, 1400),
+        Glyph::new('ò', 6, 5, 9, 0, 0, 1409),
+        Glyph::new('ó', 6, 5, 9, 0, 0, 1418),
+        Glyph::new('ô', 6, 5, 9, 0, 0, 1427),
+        Glyph::new('õ', 6, 5, 9, 0, 0, 1436),
+        Glyph::new('ö', 6, 5, 9, 0, 0, 1445),
+        Glyph::new('÷', 6, 5, 7, 0, 1, 1454),
+        Glyph::new('ø', 6, 5, 8, 0, -1, 1461),
+        Glyph::new('ù', 6, 5, 9, 0, 0, 1469),
+        Glyph::new('ú', 6, 5, 9, 0, 0, 1478),
+        Glyph::new('û', 6, 5, 9, 0, 0, 1487),
+        Glyph::new('ü', 6, 5, 9, 0, 0, 1496),
+        Glyph::new('ý', 6, 5, 11, 0, -2, 1505),
+        Glyph::new('þ', 6, 5, 10, 0, -2, 1516),
+        Glyph::new('ÿ', 6, 5, 11, 0, -2, 1526),
+    ];
+    const DATA: &[u8] = &[
+        0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00, 0x80, 0xA0, 0xA0, 0xA0, 0x50, 0x50, 0xF8, 0x50,
+        0xF8, 0x50, 0x50, 0x20, 0x78, 0xA0, 0xA0, 0x70, 0x28, 0x28, 0xF0, 0x20, 0x48, 0xA8, 0x50, 0x10,
+        0x20, 0x40, 0x50, 0xA8, 0x90, 0x40, 0xA0, 0xA0, 0x40, 0xA0, 0x98, 0x90, 0x68, 0x80, 0x80, 0x80,
+        0x20, 0x40, 0x40, 0x80, 0x80, 0x80, 0x80, 0x80, 0x40, 0x40, 0x20, 0x80, 0x40, 0x40, 0x20, 0x20,
+        0x20, 0x20, 0x20, 0x40, 0x40, 0x80, 0x20, 0xA8, 0x70, 0xA8, 0x20, 0x20, 0x20, 0xF8, 0x20, 0x20,
+        0x60, 0x40, 0x80, 0xF8, 0x40, 0xE0, 0x40, 0x08, 0x08, 0x10, 0x10, 0x20, 0x40, 0x40, 0x80, 0x80,
+        0x20, 0x50, 0x88, 0x88, 0x88, 0x88, 0x88, 0x50, 0x20, 0x20, 0x60, 0xA0, 0x20, 0x20, 0x20, 0x20,
+        0x20, 0xF8, 0x70, 0x88, 0x88, 0x08, 0x10, 0x20, 0x40, 0x80, 0xF8, 0xF8, 0x08, 0x10, 0x20, 0x70,
+        0x08, 0x08, 0x88, 0x70, 0x10, 0x10, 0x30, 0x50, 0x50, 0x90, 0xF8, 0x10, 0x10, 0xF8, 0x80, 0x80,
+        0xB0, 0xC8, 0x08, 0x08, 0x88, 0x70, 0x70, 0x88, 0x80, 0x80, 0xF0, 0x88, 0x88, 0x88, 0x70, 0xF8,
+        0x08, 0x10, 0x10, 0x20, 0x20, 0x40, 0x40, 0x40, 0x70, 0x88, 0x88, 0x88, 0x70, 0x88, 0x88, 0x88,
+        0x70, 0x70, 0x88, 0x88, 0x88, 0x78, 0x08, 0x08, 0x88, 0x70, 0x40, 0xE0, 0x40, 0x00, 0x00, 0x40,
+        0xE0, 0x40, 0x40, 0xE0, 0x40, 0x00, 0x00, 0x60, 0x40, 0x80, 0x08, 0x10, 0x20, 0x40, 0x80, 0x40,
+        0x20, 0x10, 0x08, 0xF8, 0x00, 0x00, 0xF8, 0x80, 0x40, 0x20, 0x10, 0x08, 0x10, 0x20, 0x40, 0x80,
+        0x70, 0x88, 0x88, 0x08, 0x10, 0x20, 0x20, 0x00, 0x20, 0x70, 0x88, 0x88, 0x98, 0xA8, 0xA8, 0xB0,
+        0x80, 0x78, 0x20, 0x50, 0x88, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88, 0xF0, 0x48, 0x48, 0x48, 0x70,
+        0x48, 0x48, 0x48, 0xF0, 0x70, 0x88, 0x80, 0x80, 0x80, 0x80, 0x80, 0x88, 0x70, 0xF0, 0x48, 0x48,
+        0x48, 0x48, 0x48, 0x48, 0x48, 0xF0, 0xF8, 0x80, 0x80, 0x80, 0xF0, 0x80, 0x80, 0x80, 0xF8, 0xF8,
+        0x80, 0x80, 0x80, 0xF0, 0x80, 0x80, 0x80, 0x80, 0x70, 0x88, 0x80, 0x80, 0x80, 0x98, 0x88, 0x88,
+        0x70, 0x88, 0x88, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88, 0x88, 0xE0, 0x40, 0x40, 0x40, 0x40, 0x40,
+        0x40, 0x40, 0xE0, 0x38, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x88, 0x88, 0x90, 0xA0,
+        0xC0, 0xA0, 0x90, 0x88, 0x88, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xF8, 0x88, 0x88,
+        0xD8, 0xA8, 0xA8, 0x88, 0x88, 0x88, 0x88, 0x88, 0xC8, 0xC8, 0xA8, 0xA8, 0x98, 0x98, 0x88, 0x88,
+        0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0xF0, 0x88, 0x88, 0x88, 0xF0, 0x80, 0x80,
+        0x80, 0x80, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0xA8, 0x70, 0x08, 0xF0, 0x88, 0x88, 0x88,
+        0xF0, 0xA0, 0x90, 0x88, 0x88, 0x70, 0x88, 0x80, 0x80, 0x70, 0x08, 0x08, 0x88, 0x70, 0xF8, 0x20,
+        0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70,
+        0x88, 0x88, 0x88, 0x88, 0x50, 0x50, 0x50, 0x20, 0x20, 0x88, 0x88, 0x88, 0x88, 0xA8, 0xA8, 0xA8,
+        0xA8, 0x50, 0x88, 0x88, 0x50, 0x50, 0x20, 0x50, 0x50, 0x88, 0x88, 0x88, 0x88, 0x50, 0x50, 0x20,
+        0x20, 0x20, 0x20, 0x20, 0xF8, 0x08, 0x10, 0x10, 0x20, 0x40, 0x40, 0x80, 0xF8, 0xE0, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xE0, 0x80, 0x80, 0x40, 0x40, 0x20, 0x10, 0x10, 0x08,
+        0x08, 0xE0, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0xE0, 0x20, 0x50, 0x88, 0xF8,
+        0x80, 0x40, 0x70, 0x08, 0x78, 0x88, 0x98, 0x68, 0x80, 0x80, 0x80, 0xF0, 0x88, 0x88, 0x88, 0x88,
+        0xF0, 0x70, 0x88, 0x80, 0x80, 0x88, 0x70, 0x08, 0x08, 0x08, 0x78, 0x88, 0x88, 0x88, 0x88, 0x78,
+        0x70, 0x88, 0xF8, 0x80, 0x88, 0x70, 0x30, 0x48, 0x40, 0x40, 0xF0, 0x40, 0x40, 0x40, 0x40, 0x70,
+        0x88, 0x88, 0x88, 0x78, 0x08, 0x88, 0x70, 0x80, 0x80, 0x80, 0xB0, 0xC8, 0x88, 0x88, 0x88, 0x88,
+        0x40, 0x00, 0xC0, 0x40, 0x40, 0x40, 0x40, 0xE0, 0x10, 0x00, 0x30, 0x10, 0x10, 0x10, 0x10, 0x90,
+        0x90, 0x60, 0x80, 0x80, 0x80, 0x90, 0xA0, 0xC0, 0xA0, 0x90, 0x88, 0xC0, 0x40, 0x40, 0x40, 0x40,
+        0x40, 0x40, 0x40, 0xE0, 0xD0, 0xA8, 0xA8, 0xA8, 0xA8, 0x88, 0xB0, 0xC8, 0x88, 0x88, 0x88, 0x88,
+        0x70, 0x88, 0x88, 0x88, 0x88, 0x70, 0xF0, 0x88, 0x88, 0x88, 0xF0, 0x80, 0x80, 0x80, 0x78, 0x88,
+        0x88, 0x88, 0x78, 0x08, 0x08, 0x08, 0xB0, 0xC8, 0x80, 0x80, 0x80, 0x80, 0x70, 0x88, 0x60, 0x10,
+        0x88, 0x70, 0x40, 0x40, 0xF0, 0x40, 0x40, 0x40, 0x48, 0x30, 0x88, 0x88, 0x88, 0x88, 0x98, 0x68,
+        0x88, 0x88, 0x88, 0x50, 0x50, 0x20, 0x88, 0x88, 0xA8, 0xA8, 0xA8, 0x50, 0x88, 0x50, 0x20, 0x20,
+        0x50, 0x88, 0x88, 0x88, 0x88, 0x98, 0x68, 0x08, 0x88, 0x70, 0xF8, 0x10, 0x20, 0x40, 0x80, 0xF8,
+        0x18, 0x20, 0x20, 0x20, 0x20, 0xC0, 0x20, 0x20, 0x20, 0x20, 0x18, 0x80, 0x80, 0x80, 0x80, 0x80,
+        0x80, 0x80, 0x80, 0x80, 0xC0, 0x20, 0x20, 0x20, 0x20, 0x18, 0x20, 0x20, 0x20, 0x20, 0xC0, 0x48,
+        0xA8, 0x90, 0x80, 0x00, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x20, 0x70, 0xA8, 0xA0, 0xA0,
+        0xA8, 0x70, 0x20, 0x30, 0x48, 0x40, 0x40, 0xE0, 0x40, 0x40, 0x48, 0xB0, 0x88, 0x70, 0x50, 0x50,
+        0x70, 0x88, 0x88, 0x88, 0x50, 0x50, 0xF8, 0x20, 0xF8, 0x20, 0x20, 0x80, 0x80, 0x80, 0x80, 0x00,
+        0x80, 0x80, 0x80, 0x80, 0x60, 0x90, 0x80, 0x60, 0x90, 0x90, 0x60, 0x10, 0x90, 0x60, 0xA0, 0xA0,
+        0x70, 0x88, 0xA8, 0xD8, 0xC8, 0xD8, 0xA8, 0x88, 0x70, 0x70, 0x08, 0x78, 0x88, 0x78, 0x00, 0xF8,
+        0x28, 0x50, 0xA0, 0xA0, 0x50, 0x28, 0xF8, 0x08, 0x08, 0xE0, 0x70, 0x88, 0xE8, 0xD8, 0xD8, 0xE8,
+        0xD8, 0x88, 0x70, 0xF8, 0x60, 0x90, 0x90, 0x60, 0x20, 0x20, 0xF8, 0x20, 0x20, 0x00, 0xF8, 0x40,
+        0xA0, 0x20, 0x40, 0xE0, 0x40, 0xA0, 0x40, 0x20, 0xC0, 0x40, 0x80, 0x88, 0x88, 0x88, 0x88, 0x98,
+        0xE8, 0x80, 0x80, 0x78, 0xE8, 0xE8, 0xE8, 0xE8, 0x68, 0x28, 0x28, 0x28, 0xC0, 0x40, 0x80, 0x40,
+        0xC0, 0x40, 0x40, 0xE0, 0x70, 0x88, 0x88, 0x88, 0x70, 0x00, 0xF8, 0xA0, 0x50, 0x28, 0x28, 0x50,
+        0xA0, 0x40, 0xC0, 0x40, 0x40, 0xE0, 0x08, 0x18, 0x28, 0x38, 0x08, 0x40, 0xC0, 0x40, 0x40, 0xE0,
+        0x10, 0x28, 0x08, 0x10, 0x38, 0x40, 0xA0, 0x40, 0x20, 0xA0, 0x48, 0x18, 0x28, 0x38, 0x08, 0x20,
+        0x00, 0x20, 0x20, 0x40, 0x80, 0x88, 0x88, 0x70, 0x40, 0x20, 0x00, 0x20, 0x50, 0x88, 0x88, 0xF8,
+        0x88, 0x88, 0x10, 0x20, 0x00, 0x20, 0x50, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x30, 0x48, 0x00, 0x20,
+        0x50, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x28, 0x50, 0x00, 0x20, 0x50, 0x88, 0x88, 0xF8, 0x88, 0x88,
+        0x50, 0x50, 0x00, 0x20, 0x50, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x20, 0x50, 0x20, 0x20, 0x50, 0x88,
+        0x88, 0xF8, 0x88, 0x88, 0x58, 0xA0, 0xA0, 0xA0, 0xB0, 0xE0, 0xA0, 0xA0, 0xB8, 0x70, 0x88, 0x80,
+        0x80, 0x80, 0x80, 0x80, 0x88, 0x70, 0x20, 0x40, 0x40, 0x20, 0x00, 0xF8, 0x80, 0x80, 0xF0, 0x80,
+        0x80, 0xF8, 0x10, 0x20, 0x00, 0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8, 0x30, 0x48, 0x00, 0xF8,
+        0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8, 0x50, 0x50, 0x00, 0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8,
+        0x80, 0x40, 0x00, 0xE0, 0x40, 0x40, 0x40, 0x40, 0x40, 0xE0, 0x20, 0x40, 0x00, 0xE0, 0x40, 0x40,
+        0x40, 0x40, 0x40, 0xE0, 0x60, 0x90, 0x00, 0xE0, 0x40, 0x40, 0x40, 0x40, 0x40, 0xE0, 0xA0, 0xA0,
+        0x00, 0xE0, 0x40, 0x40, 0x40, 0x40, 0x40, 0xE0, 0xF0, 0x48, 0x48, 0x48, 0xE8, 0x48, 0x48, 0x48,
+        0xF0, 0x28, 0x50, 0x00, 0x88, 0x88, 0xC8, 0xA8, 0x98, 0x88, 0x88, 0x40, 0x20, 0x00, 0x70, 0x88,
+        0x88, 0x88, 0x88, 0x88, 0x70, 0x10, 0x20, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x30,
+        0x48, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x28, 0x50, 0x00, 0x70, 0x88, 0x88, 0x88,
+        0x88, 0x88, 0x70, 0x50, 0x50, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x88, 0x50, 0x20,
+        0x50, 0x88, 0x08, 0x70, 0x98, 0x98, 0xA8, 0xA8, 0xA8, 0xC8, 0xC8, 0x70, 0x80, 0x40, 0x20, 0x00,
+        0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x10, 0x20, 0x00, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88,
+        0x70, 0x30, 0x48, 0x00, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x50, 0x50, 0x00, 0x88, 0x88,
+        0x88, 0x88, 0x88, 0x88, 0x70, 0x10, 0x20, 0x00, 0x88, 0x88, 0x50, 0x20, 0x20, 0x20, 0x20, 0x80,
+        0xF0, 0x88, 0x88, 0x88, 0xF0, 0x80, 0x80, 0x80, 0x60, 0x90, 0x90, 0xA0, 0xA0, 0x90, 0x88, 0x88,
+        0xB0, 0x40, 0x20, 0x00, 0x70, 0x08, 0x78, 0x88, 0x98, 0x68, 0x10, 0x20, 0x00, 0x70, 0x08, 0x78,
+        0x88, 0x98, 0x68, 0x30, 0x48, 0x00, 0x70, 0x08, 0x78, 0x88, 0x98, 0x68, 0x28, 0x50, 0x00, 0x70,
+        0x08, 0x78, 0x88, 0x98, 0x68, 0x50, 0x50, 0x00, 0x70, 0x08, 0x78, 0x88, 0x98, 0x68, 0x30, 0x48,
+        0x30, 0x00, 0x70, 0x08, 0x78, 0x88, 0x98, 0x68, 0x70, 0x28, 0x70, 0xA0, 0xA8, 0x50, 0x70, 0x88,
+        0x80, 0x80, 0x88, 0x70, 0x20, 0x40, 0x40, 0x20, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x88, 0x70, 0x10,
+        0x20, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x88, 0x70, 0x30, 0x48, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x88,
+        0x70, 0x50, 0x50, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x88, 0x70, 0x80, 0x40, 0x00, 0xC0, 0x40, 0x40,
+        0x40, 0x40, 0xE0, 0x20, 0x40, 0x00, 0xC0, 0x40, 0x40, 0x40, 0x40, 0xE0, 0x60, 0x90, 0x00, 0xC0,
+        0x40, 0x40, 0x40, 0x40, 0xE0, 0xA0, 0xA0, 0x00, 0xC0, 0x40, 0x40, 0x40, 0x40, 0xE0, 0x50, 0x20,
+        0x60, 0x10, 0x70, 0x88, 0x88, 0x88, 0x88, 0x70, 0x28, 0x50, 0x00, 0xB0, 0xC8, 0x88, 0x88, 0x88,
+        0x88, 0x40, 0x20, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x70, 0x10, 0x20, 0x00, 0x70, 0x88, 0x88,
+        0x88, 0x88, 0x70, 0x30, 0x48, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x70, 0x28, 0x50, 0x00, 0x70,
+        0x88, 0x88, 0x88, 0x88, 0x70, 0x50, 0x50, 0x00, 0x70, 0x88, 0x88, 0x88, 0x88, 0x70, 0x20, 0x20,
+        0x00, 0xF8, 0x00, 0x20, 0x20, 0x08, 0x70, 0x98, 0xA8, 0xA8, 0xC8, 0x70, 0x80, 0x40, 0x20, 0x00,
+        0x88, 0x88, 0x88, 0x88, 0x98, 0x68, 0x10, 0x20, 0x00, 0x88, 0x88, 0x88, 0x88, 0x98, 0x68, 0x30,
+        0x48, 0x00, 0x88, 0x88, 0x88, 0x88, 0x98, 0x68, 0x50, 0x50, 0x00, 0x88, 0x88, 0x88, 0x88, 0x98,
+        0x68, 0x10, 0x20, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68, 0x08, 0x88, 0x70, 0x80, 0x80, 0xB0, 0xC8,
+        0x88, 0x88, 0xC8, 0xB0, 0x80, 0x80, 0x50, 0x50, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68, 0x08, 0x88,
+        0x70,
+    ];
+
+    Font::new(GLYPHS, DATA, 11, 2, '?')
+}
@@ -0,0 +1,110 @@
+//! Traits abstracting the Watchy peripherals, so that the logic of watch faces and apps
+//! can be written once and then run on the hardware, the [simulated watch](crate::sim),
+//! or against the mocks in the `mock` module under `cargo test`.
+//!
+//! Each trait is implemented by the real driver for the selected backend, which keeps
+//! its inherent methods, so the traits only need to be in scope for generic code.
+//! Since the [`Button`] trait has the same name as the [`Button`](crate::button::Button)
+//! enum, it is best imported anonymously, or with another name if it is used as a bound:
+//! ```
+//! use watchy::traits::{BatteryMonitor, Button as ButtonInput};
+//!
+//! /// Returns whether to show the low battery warning.
+//! fn low_battery<B: BatteryMonitor>(battery: &mut B) -> Result<bool, B::Error> {
+//!     Ok(battery.status()?.percentage() < 10)
+//! }
+//!
+//! /// Returns whether any of the buttons are held down.
+//! fn any_pressed(buttons: &[&dyn ButtonInput]) -> bool {
+//!     buttons.iter().any(|button| button.is_pressed())
+//! }
+//! ```
+
+use crate::{
+    battery::BatteryStatus,
+    rtc::{Alarm, DateTime, RtcError},
+};
+use embedded_hal::delay::DelayNs;
+
+/// A button that can be polled.
+pub trait Button {
+    /// Returns whether the button is currently pressed.
+    fn is_pressed(&self) -> bool;
+}
+
+/// A monitor of the battery status.
+pub trait BatteryMonitor {
+    /// The error that can occur when sampling the battery voltage.
+    type Error: core::fmt::Debug;
+
+    /// Retrieves the battery status.
+    fn status(&mut self) -> Result<BatteryStatus, Self::Error>;
+}
+
+/// A sensor of the motion of the watch.
+pub trait MotionSensor {
+    /// The error that can occur when reading the sensor.
+    type Error: core::fmt::Debug;
+
+    /// Reads the acceleration along each axis in mg.
+    ///
+    /// When the watch is lying flat on its back, this is roughly 1000 mg along the Z axis
+    /// and nothing along the others.
+    fn acceleration(&mut self) -> Result<[i32; 3], Self::Error>;
+
+    /// Returns the number of steps counted, or `None` if the sensor does not count steps.
+    ///
+    /// By default, steps are not counted.
+    fn steps(&mut self) -> Result<Option<u32>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Returns the die temperature of the sensor in °C, or `None` if it is not measured.
+    ///
+    /// By default, the temperature is not measured.
+    fn temperature(&mut self) -> Result<Option<i8>, Self::Error> {
+        Ok(None)
+    }
+}
+
+/// A clock that keeps the date and time.
+pub trait Clock {
+    /// The error that can occur on the bus to the clock.
+    type BusError: core::fmt::Debug;
+
+    /// Returns the current date and time, or [`RtcError::ClockStopped`] if the clock
+    /// needs to be set.
+    fn now(&mut self) -> Result<DateTime, RtcError<Self::BusError>>;
+
+    /// Sets the date and time.
+    fn set(&mut self, date_time: &DateTime) -> Result<(), RtcError<Self::BusError>>;
+
+    /// Returns the time of day at which the daily alarm goes off, or `None` if no alarm
+    /// is set.
+    ///
+    /// By default, the clock has no alarm.
+    fn alarm(&mut self) -> Result<Option<Alarm>, RtcError<Self::BusError>> {
+        Ok(None)
+    }
+}
+
+/// A source of haptic feedback.
+pub trait Haptics {
+    /// The error that can occur when driving the motor.
+    type Error: core::fmt::Debug;
+
+    /// Turns the vibration on or off.
+    fn set_vibrating(&mut self, vibrating: bool) -> Result<(), Self::Error>;
+
+    /// Vibrates for a number of milliseconds, blocking until done.
+    ///
+    /// This is not available on trait objects, since the delay is generic.
+    fn buzz(&mut self, delay: &mut impl DelayNs, ms: u32) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        self.set_vibrating(true)?;
+        delay.delay_ms(ms);
+        self.set_vibrating(false)
+    }
+}
@@ -0,0 +1,90 @@
+//! Menus and other user interface elements driven by the buttons.
+//!
+//! A [`Menu`] is a list of [`Entry`] items, each of which is an action, a nested
+//! submenu, a toggle, a numeric spinner, or an action that needs to be confirmed in a
+//! dialog.
+//! Menus are normally constants, with each entry identified by a number chosen by the
+//! application.
+//!
+//! The [`MenuState`] tracks where the user is in the menus, and is driven by passing
+//! each [`Button`](crate::button::Button) press to [`MenuState::press`]:
+//! - [`Up`](crate::button::Button::Up) and [`Down`](crate::button::Button::Down) move
+//!   through the entries, wrapping around at either end, and change the value of a
+//!   spinner or the choice in a dialog.
+//! - [`Menu`](crate::button::Button::Menu) selects an entry, which may produce an
+//!   [`Event`] for the application to act on.
+//! - [`Back`](crate::button::Button::Back) goes back out of a submenu, and cancels a
+//!   spinner or a dialog.
+//!
+//! The values of toggles and spinners belong to the application, which provides them
+//! through [`MenuValues`] and updates them when they are changed.
+//! This is all independent of the hardware, so the navigation can be tested on the host.
+//!
+//! The menu is drawn on the whole display.
+//! To keep partial refreshes quick and free of ghosting, the entries scroll a page at a
+//! time rather than shifting on every press, and each [`Response`] includes the area of
+//! the display that changed, if any.
+//!
+//! # Example
+//! ```no_run
+//! use watchy::{
+//!     button::Button,
+//!     display::FrameBuffer,
+//!     ui::{Entry, Event, Menu, MenuState, MenuValues},
+//! };
+//!
+//! const VIBRATE: u16 = 1;
+//! const BRIGHTNESS: u16 = 2;
+//! const RESET: u16 = 3;
+//!
+//! const DISPLAY: Menu = Menu::new(
+//!     "Display",
+//!     &[Entry::spinner(BRIGHTNESS, "Contrast", 0, 10, 1)],
+//! );
+//! const SETTINGS: Menu = Menu::new(
+//!     "Settings",
+//!     &[
+//!         Entry::toggle(VIBRATE, "Vibrate"),
+//!         Entry::submenu("Display", &DISPLAY),
+//!         Entry::confirm(RESET, "Reset", "Erase everything?"),
+//!     ],
+//! );
+//!
+//! struct Settings {
+//!     vibrate: bool,
+//!     contrast: i32,
+//! }
+//! impl MenuValues for Settings {
+//!     fn toggle(&self, _id: u16) -> bool {
+//!         self.vibrate
+//!     }
+//!
+//!     fn number(&self, _id: u16) -> i32 {
+//!         self.contrast
+//!     }
+//! }
+//!
+//! let mut settings = Settings {
+//!     vibrate: true,
+//!     contrast: 5,
+//! };
+//! let mut state = MenuState::new(&SETTINGS);
+//! let mut frame = FrameBuffer::new();
+//!
+//! let response = state.press(Button::Menu, &settings);
+//! match response.event {
+//!     Some(Event::Toggled(VIBRATE, value)) => settings.vibrate = value,
+//!     Some(Event::Changed(BRIGHTNESS, value)) => settings.contrast = value,
+//!     _ => {}
+//! }
+//! if response.dirty.is_some() {
+//!     state.draw(&settings, &mut frame).unwrap();
+//!     // Partially refresh the display...
+//! }
+//! ```
+
+mod menu;
+mod render;
+
+pub use menu::{Entry, EntryKind, Event, Menu, MenuState, MenuValues, Mode, Response};
+pub use render::VISIBLE_ROWS;
@@ -0,0 +1,375 @@
+//! Menu definitions and the navigation state machine.
+
+use super::render;
+use crate::button::Button;
+use embedded_graphics::primitives::Rectangle;
+
+/// A titled list of entries, which may contain submenus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Menu<'a> {
+    /// The title shown above the entries.
+    pub title: &'a str,
+    /// The entries.
+    pub entries: &'a [Entry<'a>],
+}
+impl<'a> Menu<'a> {
+    /// Creates a menu.
+    pub const fn new(title: &'a str, entries: &'a [Entry<'a>]) -> Self {
+        Self { title, entries }
+    }
+}
+
+/// What an [`Entry`] of a [`Menu`] does when selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind<'a> {
+    /// Produces [`Event::Selected`].
+    Action,
+    /// Opens a nested menu.
+    Submenu(&'a Menu<'a>),
+    /// Flips a value that is on or off, producing [`Event::Toggled`].
+    Toggle,
+    /// Edits a number using the up and down buttons, producing [`Event::Changed`] once
+    /// the new value is selected.
+    Spinner {
+        /// The smallest value.
+        min: i32,
+        /// The largest value.
+        max: i32,
+        /// The amount by which each press changes the value.
+        step: i32,
+    },
+    /// Asks for confirmation in a dialog with the contained prompt, producing
+    /// [`Event::Confirmed`] if confirmed.
+    Confirm(&'a str),
+}
+
+/// An entry of a [`Menu`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// The number that identifies the entry to the application, which is unused for
+    /// submenus.
+    pub id: u16,
+    /// The label.
+    pub label: &'a str,
+    /// What the entry does.
+    pub kind: EntryKind<'a>,
+}
+impl<'a> Entry<'a> {
+    /// Creates an action entry.
+    pub const fn action(id: u16, label: &'a str) -> Self {
+        Self {
+            id,
+            label,
+            kind: EntryKind::Action,
+        }
+    }
+
+    /// Creates an entry that opens a submenu.
+    pub const fn submenu(label: &'a str, menu: &'a Menu<'a>) -> Self {
+        Self {
+            id: 0,
+            label,
+            kind: EntryKind::Submenu(menu),
+        }
+    }
+
+    /// Creates a toggle entry.
+    pub const fn toggle(id: u16, label: &'a str) -> Self {
+        Self {
+            id,
+            label,
+            kind: EntryKind::Toggle,
+        }
+    }
+
+    /// Creates a numeric spinner entry.
+    ///
+    /// # Panics
+    /// This panics if `min` is greater than `max` or the `step` is not positive.
+    pub const fn spinner(id: u16, label: &'a str, min: i32, max: i32, step: i32) -> Self {
+        assert!(min <= max && step > 0, "invalid spinner range");
+        Self {
+            id,
+            label,
+            kind: EntryKind::Spinner { min, max, step },
+        }
+    }
+
+    /// Creates an entry that must be confirmed in a dialog.
+    pub const fn confirm(id: u16, label: &'a str, prompt: &'a str) -> Self {
+        Self {
+            id,
+            label,
+            kind: EntryKind::Confirm(prompt),
+        }
+    }
+}
+
+/// Provides the current values of the toggles and spinners of a menu, which belong to
+/// the application.
+pub trait MenuValues {
+    /// Returns whether the toggle with an ID is on.
+    fn toggle(&self, id: u16) -> bool;
+
+    /// Returns the value of the spinner with an ID.
+    fn number(&self, id: u16) -> i32;
+}
+
+/// Something that the application needs to act on as a result of a button press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The action with the contained ID was selected.
+    Selected(u16),
+    /// The toggle with an ID should be set to a value.
+    Toggled(u16, bool),
+    /// The spinner with an ID should be set to a value.
+    Changed(u16, i32),
+    /// The action with the contained ID was confirmed.
+    Confirmed(u16),
+    /// Back was pressed in the top level menu, which should be closed.
+    Closed,
+}
+
+/// The result of a button press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Response {
+    /// Anything that the application needs to act on.
+    pub event: Option<Event>,
+    /// The area of the display that changed and needs to be drawn again, or `None` if
+    /// nothing changed.
+    ///
+    /// NOTE: Changing a toggle or spinner value changes the display only once the
+    /// application has updated the value.
+    pub dirty: Option<Rectangle>,
+}
+impl Response {
+    /// Creates a response with no event in which an area changed.
+    fn redraw(area: Rectangle) -> Self {
+        Self {
+            event: None,
+            dirty: Some(area),
+        }
+    }
+}
+
+/// What the buttons are currently controlling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Moving through the entries of a menu.
+    Browsing,
+    /// Editing the selected spinner, with the contained value not yet set.
+    Editing(i32),
+    /// Asking for confirmation of the selected entry, with whether yes is chosen.
+    Confirming(bool),
+}
+
+/// The maximum number of menus that can be open at once.
+const MAX_DEPTH: usize = 8;
+
+/// The position in one of the open menus.
+#[derive(Debug, Clone, Copy)]
+struct Level<'a> {
+    /// The menu.
+    menu: &'a Menu<'a>,
+    /// The index of the selected entry.
+    selected: usize,
+}
+
+/// Where the user is in a tree of menus, which is changed by button presses.
+///
+/// Up to [`MenuState::MAX_DEPTH`] menus can be open at once, with any deeper submenus
+/// not opening.
+#[derive(Debug, Clone)]
+pub struct MenuState<'a> {
+    /// The open menus, starting from the top level.
+    levels: [Level<'a>; MAX_DEPTH],
+    /// The number of open menus.
+    depth: usize,
+    /// What the buttons are currently controlling.
+    mode: Mode,
+}
+impl<'a> MenuState<'a> {
+    /// The maximum number of menus that can be open at once.
+    pub const MAX_DEPTH: usize = MAX_DEPTH;
+
+    /// Creates a state with the first entry of the top level menu selected.
+    pub fn new(menu: &'a Menu<'a>) -> Self {
+        Self {
+            levels: [Level { menu, selected: 0 }; Self::MAX_DEPTH],
+            depth: 1,
+            mode: Mode::Browsing,
+        }
+    }
+
+    /// Returns the current level.
+    fn level(&self) -> &Level<'a> {
+        &self.levels[self.depth - 1]
+    }
+
+    /// Returns the current level mutably.
+    fn level_mut(&mut self) -> &mut Level<'a> {
+        &mut self.levels[self.depth - 1]
+    }
+
+    /// Returns the open menu.
+    pub fn menu(&self) -> &'a Menu<'a> {
+        self.level().menu
+    }
+
+    /// Returns the index of the selected entry in the open menu.
+    pub fn selected_index(&self) -> usize {
+        self.level().selected
+    }
+
+    /// Returns the selected entry, or `None` if the open menu is empty.
+    pub fn selected(&self) -> Option<&'a Entry<'a>> {
+        self.menu().entries.get(self.selected_index())
+    }
+
+    /// Selects an entry of the open menu by its index, which is limited to the last
+    /// entry.
+    ///
+    /// Any value being edited or dialog being shown is cancelled.
+    pub fn select(&mut self, index: usize) {
+        let count = self.menu().entries.len();
+        self.level_mut().selected = index.min(count.saturating_sub(1));
+        self.mode = Mode::Browsing;
+    }
+
+    /// Returns the number of open menus, which is one in the top level menu.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns what the buttons are currently controlling.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the index of the first entry shown on the display.
+    pub(super) fn first_visible(&self) -> usize {
+        self.selected_index() / render::VISIBLE_ROWS * render::VISIBLE_ROWS
+    }
+
+    /// Handles a button press, which may produce an event for the application.
+    pub fn press(&mut self, button: Button, values: &impl MenuValues) -> Response {
+        let Some(entry) = self.selected() else {
+            // Only going back is possible from an empty menu
+            return match button {
+                Button::Back => self.back(),
+                _ => Response::default(),
+            };
+        };
+
+        match (self.mode, button) {
+            (Mode::Browsing, Button::Up | Button::Down) => {
+                let count = self.menu().entries.len();
+                let (old, first) = (self.selected_index(), self.first_visible());
+                self.level_mut().selected = if button == Button::Up {
+                    (old + count - 1) % count
+                } else {
+                    (old + 1) % count
+                };
+
+                if self.first_visible() == first {
+                    let (old, new) = (old - first, self.selected_index() - first);
+                    Response::redraw(render::union(render::row_area(old), render::row_area(new)))
+                } else {
+                    Response::redraw(render::list_area())
+                }
+            }
+            (Mode::Browsing, Button::Menu) => {
+                let row = render::row_area(self.selected_index() - self.first_visible());
+                match entry.kind {
+                    EntryKind::Action => Response {
+                        event: Some(Event::Selected(entry.id)),
+                        dirty: None,
+                    },
+                    EntryKind::Submenu(menu) if self.depth < Self::MAX_DEPTH => {
+                        self.levels[self.depth] = Level { menu, selected: 0 };
+                        self.depth += 1;
+                        Response::redraw(render::screen_area())
+                    }
+                    EntryKind::Submenu(_) => Response::default(),
+                    EntryKind::Toggle => Response {
+                        event: Some(Event::Toggled(entry.id, !values.toggle(entry.id))),
+                        dirty: Some(row),
+                    },
+                    EntryKind::Spinner { min, max, .. } => {
+                        self.mode = Mode::Editing(values.number(entry.id).clamp(min, max));
+                        Response::redraw(row)
+                    }
+                    EntryKind::Confirm(_) => {
+                        self.mode = Mode::Confirming(false);
+                        Response::redraw(render::dialog_area())
+                    }
+                }
+            }
+            (Mode::Browsing, Button::Back) => self.back(),
+            (Mode::Editing(value), _) => {
+                // Only spinners can be edited, so anything else goes back to browsing
+                let EntryKind::Spinner { min, max, step } = entry.kind else {
+                    self.mode = Mode::Browsing;
+                    return Response::redraw(render::screen_area());
+                };
+                let row = render::row_area(self.selected_index() - self.first_visible());
+                let event = match button {
+                    Button::Up => {
+                        self.mode = Mode::Editing(value.saturating_add(step).min(max));
+                        None
+                    }
+                    Button::Down => {
+                        self.mode = Mode::Editing(value.saturating_sub(step).max(min));
+                        None
+                    }
+                    Button::Menu => {
+                        self.mode = Mode::Browsing;
+                        Some(Event::Changed(entry.id, value))
+                    }
+                    Button::Back => {
+                        self.mode = Mode::Browsing;
+                        None
+                    }
+                };
+                Response {
+                    event,
+                    dirty: Some(row),
+                }
+            }
+            (Mode::Confirming(yes), _) => {
+                let event = match button {
+                    Button::Up | Button::Down => {
+                        self.mode = Mode::Confirming(!yes);
+                        None
+                    }
+                    Button::Menu => {
+                        self.mode = Mode::Browsing;
+                        yes.then_some(Event::Confirmed(entry.id))
+                    }
+                    Button::Back => {
+                        self.mode = Mode::Browsing;
+                        None
+                    }
+                };
+                Response {
+                    event,
+                    dirty: Some(render::dialog_area()),
+                }
+            }
+        }
+    }
+
+    /// Goes back out of the open submenu, or closes the top level menu.
+    fn back(&mut self) -> Response {
+        self.mode = Mode::Browsing;
+        if self.depth > 1 {
+            self.depth -= 1;
+            Response::redraw(render::screen_area())
+        } else {
+            Response {
+                event: Some(Event::Closed),
+                dirty: None,
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Layout and drawing of the menus.
+
+use super::menu::{Entry, EntryKind, MenuState, MenuValues, Mode};
+use crate::{
+    display::{HEIGHT, WIDTH},
+    text::{FontStyle, Formatted, SMALL},
+};
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, StrokeAlignment},
+    text::{Alignment, Baseline, Text, TextStyle, TextStyleBuilder},
+};
+
+/// The height of the title bar.
+const TITLE_HEIGHT: u32 = 20;
+/// The height of each entry.
+const ROW_HEIGHT: u32 = 20;
+/// The width of the scroll bar, which is only shown when the entries do not all fit.
+const SCROLL_BAR_WIDTH: u32 = 4;
+/// The horizontal space between the edge of an entry and its text.
+const PADDING: i32 = 6;
+/// The size of a dialog.
+const DIALOG_SIZE: Size = Size::new(176, 84);
+/// The size of each button of a dialog.
+const DIALOG_BUTTON_SIZE: Size = Size::new(56, 20);
+
+/// The number of entries shown on the display at once.
+pub const VISIBLE_ROWS: usize = ((HEIGHT - TITLE_HEIGHT) / ROW_HEIGHT) as usize;
+
+/// Returns the area of the whole display.
+pub(super) fn screen_area() -> Rectangle {
+    Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT))
+}
+
+/// Returns the area of the title bar.
+fn title_area() -> Rectangle {
+    Rectangle::new(Point::zero(), Size::new(WIDTH, TITLE_HEIGHT))
+}
+
+/// Returns the area below the title bar in which the entries are shown.
+pub(super) fn list_area() -> Rectangle {
+    Rectangle::new(
+        Point::new(0, TITLE_HEIGHT as i32),
+        Size::new(WIDTH, HEIGHT - TITLE_HEIGHT),
+    )
+}
+
+/// Returns the area of an entry by its row on the display.
+pub(super) fn row_area(row: usize) -> Rectangle {
+    Rectangle::new(
+        Point::new(0, (TITLE_HEIGHT + row as u32 * ROW_HEIGHT) as i32),
+        Size::new(WIDTH, ROW_HEIGHT),
+    )
+}
+
+/// Returns the area of a dialog, which is drawn over the entries.
+pub(super) fn dialog_area() -> Rectangle {
+    Rectangle::with_center(screen_area().center(), DIALOG_SIZE)
+}
+
+/// Returns the smallest rectangle that contains two others.
+pub(super) fn union(a: Rectangle, b: Rectangle) -> Rectangle {
+    match (a.bottom_right(), b.bottom_right()) {
+        (Some(a_end), Some(b_end)) => Rectangle::with_corners(
+            a.top_left.component_min(b.top_left),
+            a_end.component_max(b_end),
+        ),
+        (Some(_), None) => a,
+        (None, _) => b,
+    }
+}
+
+/// Returns the style for text that is vertically centered on its position.
+fn text_style(alignment: Alignment) -> TextStyle {
+    TextStyleBuilder::new()
+        .alignment(alignment)
+        .baseline(Baseline::Middle)
+        .build()
+}
+
+impl MenuState<'_> {
+    /// Draws the open menu, and any dialog, over the whole display.
+    ///
+    /// The whole display is always drawn, with the [`dirty`](super::Response::dirty)
+    /// area of each response only needed to decide whether to refresh it.
+    pub fn draw<D>(&self, values: &impl MenuValues, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let menu = self.menu();
+        let title = title_area();
+        target.fill_solid(&title, BinaryColor::On)?;
+        Text::with_text_style(
+            menu.title,
+            title.center(),
+            FontStyle::new(&SMALL, BinaryColor::Off),
+            text_style(Alignment::Center),
+        )
+        .draw(target)?;
+        target.fill_solid(&list_area(), BinaryColor::Off)?;
+
+        let count = menu.entries.len();
+        let first = self.first_visible();
+        let scrolls = count > VISIBLE_ROWS;
+        for (row, entry) in menu
+            .entries
+            .iter()
+            .skip(first)
+            .take(VISIBLE_ROWS)
+            .enumerate()
+        {
+            let mut area = row_area(row);
+            if scrolls {
+                area.size.width -= SCROLL_BAR_WIDTH;
+            }
+            let selected = first + row == self.selected_index();
+            self.draw_entry(entry, area, selected, values, target)?;
+        }
+
+        if scrolls {
+            // The thumb spans the part of the list that is shown
+            let list = list_area();
+            let height = list.size.height as usize;
+            let top = height * first / count;
+            let bottom = height * (first + VISIBLE_ROWS).min(count) / count;
+            target.fill_solid(
+                &Rectangle::new(
+                    Point::new(
+                        (WIDTH - SCROLL_BAR_WIDTH) as i32,
+                        list.top_left.y + top as i32,
+                    ),
+                    Size::new(SCROLL_BAR_WIDTH, (bottom - top) as u32),
+                ),
+                BinaryColor::On,
+            )?;
+        }
+
+        if let (
+            Mode::Confirming(yes),
+            Some(Entry {
+                kind: EntryKind::Confirm(prompt),
+                ..
+            }),
+        ) = (self.mode(), self.selected())
+        {
+            draw_dialog(prompt, yes, target)?;
+        }
+        Ok(())
+    }
+
+    /// Draws an entry in an area.
+    fn draw_entry<D>(
+        &self,
+        entry: &Entry,
+        area: Rectangle,
+        selected: bool,
+        values: &impl MenuValues,
+        target: &mut D,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        // The selected entry is inverted
+        let (foreground, background) = if selected {
+            target.fill_solid(&area, BinaryColor::On)?;
+            (BinaryColor::Off, BinaryColor::On)
+        } else {
+            (BinaryColor::On, BinaryColor::Off)
+        };
+        let center = area.center().y;
+        let right = Point::new(area.top_left.x + area.size.width as i32 - PADDING, center);
+
+        Text::with_text_style(
+            entry.label,
+            Point::new(area.top_left.x + PADDING, center),
+            FontStyle::new(&SMALL, foreground),
+            text_style(Alignment::Left),
+        )
+        .draw(target)?;
+
+        match entry.kind {
+            EntryKind::Action | EntryKind::Confirm(_) => {}
+            EntryKind::Submenu(_) => {
+                Text::with_text_style(
+                    "»",
+                    right,
+                    FontStyle::new(&SMALL, foreground),
+                    text_style(Alignment::Right),
+                )
+                .draw(target)?;
+            }
+            EntryKind::Toggle => {
+                let size = Size::new_equal(11);
+                let check_box = Rectangle::new(right - size.y_axis() / 2 - size.x_axis(), size);
+                check_box
+                    .into_styled(PrimitiveStyle::with_stroke(foreground, 1))
+                    .draw(target)?;
+                if values.toggle(entry.id) {
+                    target.fill_solid(&check_box.offset(-2), foreground)?;
+                }
+            }
+            EntryKind::Spinner { .. } => {
+                let (value, editing) = match self.mode() {
+                    Mode::Editing(value) if selected => (value, true),
+                    _ => (values.number(entry.id), false),
+                };
+                // NOTE: Every `i32` fits in the buffer.
+                let value = Formatted::<12>::new(format_args!("{value}"));
+                let mut text = Text::with_text_style(
+                    value.as_str(),
+                    right,
+                    FontStyle::new(&SMALL, foreground),
+                    text_style(Alignment::Right),
+                );
+                // The value being edited is boxed in the opposite color
+                if editing {
+                    target.fill_solid(&text.bounding_box().offset(3), foreground)?;
+                    text.character_style.text_color = Some(background);
+                }
+                text.draw(target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draws a dialog asking for confirmation, with whether yes is chosen.
+fn draw_dialog<D>(prompt: &str, yes: bool, target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let area = dialog_area();
+    area.into_styled(
+        PrimitiveStyleBuilder::new()
+            .fill_color(BinaryColor::Off)
+            .stroke_color(BinaryColor::On)
+            .stroke_width(2)
+            .stroke_alignment(StrokeAlignment::Inside)
+            .build(),
+    )
+    .draw(target)?;
+    let center = area.center();
+    Text::with_text_style(
+        prompt,
+        Point::new(center.x, area.top_left.y + 24),
+        FontStyle::new(&SMALL, BinaryColor::On),
+        text_style(Alignment::Center),
+    )
+    .draw(target)?;
+
+    let button_y = area.top_left.y + area.size.height as i32 - 24;
+    for (label, offset, chosen) in [("No", -40, !yes), ("Yes", 40, yes)] {
+        let button =
+            Rectangle::with_center(Point::new(center.x + offset, button_y), DIALOG_BUTTON_SIZE);
+        let foreground = if chosen {
+            target.fill_solid(&button, BinaryColor::On)?;
+            BinaryColor::Off
+        } else {
+            button
+                .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+                .draw(target)?;
+            BinaryColor::On
+        };
+        Text::with_text_style(
+            label,
+            button.center(),
+            FontStyle::new(&SMALL, foreground),
+            text_style(Alignment::Center),
+        )
+        .draw(target)?;
+    }
+    Ok(())
+}
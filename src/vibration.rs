@@ -0,0 +1,141 @@
+//! Driver for the vibration motor.
+
+#[cfg(not(feature = "sim"))]
+use crate::pins;
+#[cfg(feature = "sim")]
+use crate::sim;
+use crate::traits::Haptics;
+#[cfg(feature = "esp-idf")]
+use crate::{hal::gpio, EspResult};
+#[cfg(feature = "sim")]
+use core::marker::PhantomData;
+#[cfg(feature = "esp-hal")]
+use esp_hal::gpio;
+
+/// Driver to turn the vibration motor on and off.
+///
+/// The motor is driven directly from a GPIO pin through a transistor, so it is either
+/// fully on or off.
+pub struct VibrationMotorDriver<'d> {
+    /// The driver for the motor power pin.
+    #[cfg(feature = "esp-idf")]
+    pin_driver: gpio::PinDriver<'d, pins::VibrationMotorPower, gpio::Output>,
+    /// The driver for the motor power pin.
+    #[cfg(feature = "esp-hal")]
+    output: gpio::Output<'d>,
+    /// The simulated watch.
+    #[cfg(feature = "sim")]
+    watch: sim::Watch,
+    /// The lifetime of the peripherals, which are not used by the simulation.
+    #[cfg(feature = "sim")]
+    _peripherals: PhantomData<&'d ()>,
+}
+impl VibrationMotorDriver<'_> {
+    /// Sets up the driver with the motor off.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::hal::peripherals::Peripherals::take().unwrap();
+    /// let pin_sets = watchy::pins::Sets::new(peripherals.pins);
+    /// let mut vibration_motor_driver =
+    ///     watchy::vibration::VibrationMotorDriver::new(pin_sets.vibration_motor).unwrap();
+    /// ```
+    #[cfg(feature = "esp-idf")]
+    pub fn new(vibration_motor_pins: pins::VibrationMotor) -> EspResult<Self> {
+        let mut pin_driver = gpio::PinDriver::output(vibration_motor_pins.power)?;
+        pin_driver.set_low()?;
+
+        Ok(Self { pin_driver })
+    }
+
+    /// Sets up the driver with the motor off.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let peripherals = watchy::esp_hal::init(watchy::esp_hal::Config::default());
+    /// let pin_sets = watchy::pins::Sets::new(watchy::take_pins!(peripherals));
+    /// let mut vibration_motor_driver =
+    ///     watchy::vibration::VibrationMotorDriver::new(pin_sets.vibration_motor);
+    /// ```
+    #[cfg(feature = "esp-hal")]
+    pub fn new(vibration_motor_pins: pins::VibrationMotor) -> Self {
+        Self {
+            output: gpio::Output::new(
+                vibration_motor_pins.power,
+                gpio::Level::Low,
+                gpio::OutputConfig::default(),
+            ),
+        }
+    }
+
+    /// Sets up the driver for a simulated watch with the motor off.
+    ///
+    /// # Example
+    /// ```
+    /// let watch = watchy::sim::Watch::new();
+    /// let mut vibration_motor_driver = watchy::vibration::VibrationMotorDriver::new(&watch);
+    /// vibration_motor_driver.set_vibrating(true);
+    /// assert!(watch.is_vibrating());
+    /// ```
+    #[cfg(feature = "sim")]
+    pub fn new(watch: &sim::Watch) -> Self {
+        watch.set_vibrating(false);
+        Self {
+            watch: watch.clone(),
+            _peripherals: PhantomData,
+        }
+    }
+
+    /// Turns the motor on or off.
+    #[cfg(feature = "esp-idf")]
+    pub fn set_vibrating(&mut self, vibrating: bool) -> EspResult<()> {
+        self.pin_driver.set_level(vibrating.into())
+    }
+
+    /// Turns the motor on or off.
+    #[cfg(feature = "esp-hal")]
+    pub fn set_vibrating(&mut self, vibrating: bool) {
+        self.output.set_level(vibrating.into());
+    }
+
+    /// Turns the motor on or off.
+    #[cfg(feature = "sim")]
+    pub fn set_vibrating(&mut self, vibrating: bool) {
+        self.watch.set_vibrating(vibrating);
+    }
+
+    /// Returns whether the motor is on.
+    #[cfg(feature = "esp-idf")]
+    pub fn is_vibrating(&self) -> bool {
+        self.pin_driver.is_set_high()
+    }
+
+    /// Returns whether the motor is on.
+    #[cfg(feature = "esp-hal")]
+    pub fn is_vibrating(&self) -> bool {
+        self.output.is_set_high()
+    }
+
+    /// Returns whether the motor is on.
+    #[cfg(feature = "sim")]
+    pub fn is_vibrating(&self) -> bool {
+        self.watch.is_vibrating()
+    }
+}
+#[cfg(feature = "esp-idf")]
+impl Haptics for VibrationMotorDriver<'_> {
+    type Error = crate::sys::EspError;
+
+    fn set_vibrating(&mut self, vibrating: bool) -> Result<(), Self::Error> {
+        VibrationMotorDriver::set_vibrating(self, vibrating)
+    }
+}
+#[cfg(not(feature = "esp-idf"))]
+impl Haptics for VibrationMotorDriver<'_> {
+    type Error = core::convert::Infallible;
+
+    fn set_vibrating(&mut self, vibrating: bool) -> Result<(), Self::Error> {
+        VibrationMotorDriver::set_vibrating(self, vibrating);
+        Ok(())
+    }
+}